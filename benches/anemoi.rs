@@ -10,8 +10,8 @@ use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
 extern crate hash;
 use cheetah::Fp;
-use hash::anemoi_64_8_4;
 use hash::traits::Hasher;
+use hash::{anemoi_64_12_8, anemoi_64_16_8, anemoi_64_8_4};
 
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("anemoi-64-8-4 - merge", |bench| {
@@ -22,6 +22,35 @@ fn criterion_benchmark(c: &mut Criterion) {
 
         bench.iter(|| anemoi_64_8_4::AnemoiHash::merge(black_box(&v)))
     });
+
+    c.bench_function("anemoi-64-12-8 - merge", |bench| {
+        let v: [anemoi_64_12_8::AnemoiDigest; 2] = [
+            anemoi_64_12_8::AnemoiHash::hash(&[Fp::zero()]),
+            anemoi_64_12_8::AnemoiHash::hash(&[Fp::one()]),
+        ];
+
+        bench.iter(|| anemoi_64_12_8::AnemoiHash::merge(black_box(&v)))
+    });
+
+    c.bench_function("anemoi-64-16-8 - merge", |bench| {
+        let v: [anemoi_64_16_8::AnemoiDigest; 2] = [
+            anemoi_64_16_8::AnemoiHash::hash(&[Fp::zero()]),
+            anemoi_64_16_8::AnemoiHash::hash(&[Fp::one()]),
+        ];
+
+        bench.iter(|| anemoi_64_16_8::AnemoiHash::merge(black_box(&v)))
+    });
+
+    c.bench_function("anemoi-64-16-8 - merge4", |bench| {
+        let v: [anemoi_64_16_8::AnemoiDigest; 4] = [
+            anemoi_64_16_8::AnemoiHash::hash(&[Fp::zero()]),
+            anemoi_64_16_8::AnemoiHash::hash(&[Fp::one()]),
+            anemoi_64_16_8::AnemoiHash::hash(&[Fp::new(2)]),
+            anemoi_64_16_8::AnemoiHash::hash(&[Fp::new(3)]),
+        ];
+
+        bench.iter(|| anemoi_64_16_8::AnemoiHash::merge4(black_box(&v)))
+    });
 }
 
 criterion_group!(
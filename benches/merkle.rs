@@ -0,0 +1,86 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+extern crate hash;
+use cheetah::Fp;
+use hash::merkle::{MatrixCommitment, MerkleTree, SaltedMerkleTree};
+use hash::traits::Hasher;
+use hash::{anemoi_64_12_8, anemoi_64_16_8, anemoi_64_8_4};
+use hash::{griffin_64_12_8, griffin_64_8_4};
+use hash::{plonky2_poseidon_64_12_8, poseidon_64_12_8, poseidon_64_8_4};
+use hash::{rescue_64_12_8, rescue_64_8_4, rpo_64_12_8};
+use rand_core::OsRng;
+
+const NUM_LEAVES: usize = 1 << 10;
+const ROW_WIDTH: usize = 8;
+
+fn bench_merkle_tree<H: Hasher<Fp>>(c: &mut Criterion, name: &str) {
+    c.bench_function(&format!("{} - merkle tree (1024 leaves)", name), |bench| {
+        let leaves: Vec<H::Digest> = (0..NUM_LEAVES)
+            .map(|i| H::hash(&[Fp::new(i as u64)]))
+            .collect();
+
+        bench.iter(|| MerkleTree::<H>::new(black_box(&leaves)))
+    });
+}
+
+// Compares committing to rows of field elements with and without salts.
+fn bench_salted_merkle_tree<H: Hasher<Fp>>(c: &mut Criterion, name: &str) {
+    let rows: Vec<Vec<Fp>> = (0..NUM_LEAVES)
+        .map(|i| {
+            (0..ROW_WIDTH)
+                .map(|j| Fp::new((i * ROW_WIDTH + j) as u64))
+                .collect()
+        })
+        .collect();
+
+    c.bench_function(&format!("{} - unsalted rows (1024 x 8)", name), |bench| {
+        bench.iter(|| MatrixCommitment::<H>::new(black_box(&rows), 0))
+    });
+
+    c.bench_function(&format!("{} - salted rows (1024 x 8)", name), |bench| {
+        let mut rng = OsRng;
+        bench.iter(|| SaltedMerkleTree::<H>::new(black_box(&rows), &mut rng))
+    });
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    bench_merkle_tree::<anemoi_64_8_4::AnemoiHash>(c, "anemoi-64-8-4");
+    bench_merkle_tree::<anemoi_64_12_8::AnemoiHash>(c, "anemoi-64-12-8");
+    bench_merkle_tree::<anemoi_64_16_8::AnemoiHash>(c, "anemoi-64-16-8");
+    bench_merkle_tree::<griffin_64_8_4::GriffinHash>(c, "griffin-64-8-4");
+    bench_merkle_tree::<griffin_64_12_8::GriffinHash>(c, "griffin-64-12-8");
+    bench_merkle_tree::<rescue_64_8_4::RescueHash>(c, "rescue-64-8-4");
+    bench_merkle_tree::<rescue_64_12_8::RescueHash>(c, "rescue-64-12-8");
+    bench_merkle_tree::<rpo_64_12_8::RpoHash>(c, "rpo-64-12-8");
+    bench_merkle_tree::<poseidon_64_8_4::PoseidonHash>(c, "poseidon-64-8-4");
+    bench_merkle_tree::<poseidon_64_12_8::PoseidonHash>(c, "poseidon-64-12-8");
+    bench_merkle_tree::<plonky2_poseidon_64_12_8::Plonky2PoseidonHash>(
+        c,
+        "plonky2-poseidon-64-12-8",
+    );
+
+    bench_salted_merkle_tree::<anemoi_64_8_4::AnemoiHash>(c, "anemoi-64-8-4");
+    bench_salted_merkle_tree::<anemoi_64_12_8::AnemoiHash>(c, "anemoi-64-12-8");
+    bench_salted_merkle_tree::<anemoi_64_16_8::AnemoiHash>(c, "anemoi-64-16-8");
+    bench_salted_merkle_tree::<griffin_64_8_4::GriffinHash>(c, "griffin-64-8-4");
+    bench_salted_merkle_tree::<griffin_64_12_8::GriffinHash>(c, "griffin-64-12-8");
+    bench_salted_merkle_tree::<rescue_64_8_4::RescueHash>(c, "rescue-64-8-4");
+    bench_salted_merkle_tree::<rescue_64_12_8::RescueHash>(c, "rescue-64-12-8");
+    bench_salted_merkle_tree::<rpo_64_12_8::RpoHash>(c, "rpo-64-12-8");
+    bench_salted_merkle_tree::<poseidon_64_8_4::PoseidonHash>(c, "poseidon-64-8-4");
+    bench_salted_merkle_tree::<poseidon_64_12_8::PoseidonHash>(c, "poseidon-64-12-8");
+}
+
+criterion_group!(
+    name = benches;
+    config = Criterion::default();
+    targets = criterion_benchmark);
+criterion_main!(benches);
@@ -11,7 +11,7 @@ use criterion::{black_box, criterion_group, criterion_main, Criterion};
 extern crate hash;
 use cheetah::Fp;
 use hash::traits::Hasher;
-use hash::{poseidon_64_12_8, poseidon_64_8_4};
+use hash::{plonky2_poseidon_64_12_8, poseidon_64_12_8, poseidon_64_8_4};
 
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("poseidon-64-8-4 - merge", |bench| {
@@ -31,6 +31,15 @@ fn criterion_benchmark(c: &mut Criterion) {
 
         bench.iter(|| poseidon_64_12_8::PoseidonHash::merge(black_box(&v)))
     });
+
+    c.bench_function("plonky2-poseidon-64-12-8 - merge", |bench| {
+        let v: [plonky2_poseidon_64_12_8::Plonky2PoseidonDigest; 2] = [
+            plonky2_poseidon_64_12_8::Plonky2PoseidonHash::hash(&[Fp::zero()]),
+            plonky2_poseidon_64_12_8::Plonky2PoseidonHash::hash(&[Fp::one()]),
+        ];
+
+        bench.iter(|| plonky2_poseidon_64_12_8::Plonky2PoseidonHash::merge(black_box(&v)))
+    });
 }
 
 criterion_group!(
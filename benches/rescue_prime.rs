@@ -11,7 +11,7 @@ use criterion::{black_box, criterion_group, criterion_main, Criterion};
 extern crate hash;
 use cheetah::Fp;
 use hash::traits::Hasher;
-use hash::{rescue_64_12_8, rescue_64_8_4};
+use hash::{rescue_64_12_8, rescue_64_8_4, rp64_256, rpo_64_12_8};
 
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("rescue-64-8-4 - merge", |bench| {
@@ -31,6 +31,24 @@ fn criterion_benchmark(c: &mut Criterion) {
 
         bench.iter(|| rescue_64_12_8::RescueHash::merge(black_box(&v)))
     });
+
+    c.bench_function("rpo-64-12-8 - merge", |bench| {
+        let v: [rpo_64_12_8::RpoDigest; 2] = [
+            rpo_64_12_8::RpoHash::hash(&[Fp::zero()]),
+            rpo_64_12_8::RpoHash::hash(&[Fp::one()]),
+        ];
+
+        bench.iter(|| rpo_64_12_8::RpoHash::merge(black_box(&v)))
+    });
+
+    c.bench_function("rp64-256 - merge", |bench| {
+        let v: [rp64_256::Rp64_256Digest; 2] = [
+            rp64_256::Rp64_256Hash::hash(&[Fp::zero()]),
+            rp64_256::Rp64_256Hash::hash(&[Fp::one()]),
+        ];
+
+        bench.iter(|| rp64_256::Rp64_256Hash::merge(black_box(&v)))
+    });
 }
 
 criterion_group!(
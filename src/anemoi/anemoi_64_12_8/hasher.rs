@@ -0,0 +1,449 @@
+// Copyright (c) 2021-2022 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Hasher trait implementation for Anemoi
+
+use super::digest::AnemoiDigest;
+use super::{
+    apply_permutation, AnemoiPermutation, CAPACITY_WIDTH, DIGEST_SIZE, RATE_WIDTH, STATE_WIDTH,
+};
+use crate::f64_utils::bytes_to_elements;
+use crate::sponge::{Padding, Sponge, XofReader};
+use crate::traits::Hasher;
+
+use cheetah::Fp;
+use subtle::ConstantTimeEq;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// A Anemoi Hash over Fp
+pub struct AnemoiHash {
+    sponge: Sponge<AnemoiPermutation, RATE_WIDTH, CAPACITY_WIDTH>,
+}
+
+impl Default for AnemoiHash {
+    fn default() -> Self {
+        Self {
+            sponge: Sponge::new(Padding::Sigma),
+        }
+    }
+}
+
+impl AnemoiHash {
+    /// Returns a new hasher keyed with `key`, to compute a message
+    /// authentication code through `update` and `finalize`.
+    ///
+    /// The key is set as the initial capacity, and the message is
+    /// always padded with a one, even when its length is a multiple
+    /// of the rate, so that no two messages share the same padding.
+    pub fn new_keyed(key: &[Fp; CAPACITY_WIDTH]) -> Self {
+        Self {
+            sponge: Sponge::with_initial_capacity(Padding::AlwaysOne, *key),
+        }
+    }
+
+    /// Absorbs a new sequence of field elements into the hasher state.
+    ///
+    /// This can be called several times on chunks of a message, the
+    /// resulting digest being identical to the one returned by
+    /// `AnemoiHash::hash` on the concatenated input.
+    pub fn update(&mut self, bytes: &[Fp]) {
+        self.sponge.absorb(bytes);
+    }
+
+    /// Pads the absorbed sequence and returns its digest.
+    pub fn finalize(self) -> AnemoiDigest {
+        let mut digest = [Fp::zero(); DIGEST_SIZE];
+        self.finalize_xof().read(&mut digest);
+
+        AnemoiDigest::new(digest)
+    }
+
+    /// Pads the absorbed sequence and returns a reader over an arbitrarily
+    /// long output, whose first `DIGEST_SIZE` elements form its digest.
+    pub fn finalize_xof(self) -> XofReader<AnemoiPermutation, RATE_WIDTH, CAPACITY_WIDTH> {
+        self.sponge.into_reader()
+    }
+
+    /// Returns a reader over an arbitrarily long hash output
+    /// of the provided sequence of field elements.
+    pub fn hash_xof(bytes: &[Fp]) -> XofReader<AnemoiPermutation, RATE_WIDTH, CAPACITY_WIDTH> {
+        let mut hasher = Self::default();
+        hasher.update(bytes);
+        hasher.finalize_xof()
+    }
+
+    /// Returns a hash of the provided sequence of bytes.
+    ///
+    /// The bytes are first packed into field elements with
+    /// `f64_utils::bytes_to_elements`.
+    pub fn hash_bytes(bytes: &[u8]) -> AnemoiDigest {
+        Self::hash(&bytes_to_elements(bytes))
+    }
+
+    /// Returns a message authentication code of the provided
+    /// sequence of field elements under `key`, as computed by a
+    /// hasher returned by `new_keyed`.
+    pub fn mac(key: &[Fp; CAPACITY_WIDTH], bytes: &[Fp]) -> AnemoiDigest {
+        let mut hasher = Self::new_keyed(key);
+        hasher.update(bytes);
+        hasher.finalize()
+    }
+
+    /// Checks in constant time that `tag` is a valid message
+    /// authentication code of `bytes` under `key`.
+    pub fn verify(key: &[Fp; CAPACITY_WIDTH], bytes: &[Fp], tag: &AnemoiDigest) -> bool {
+        Self::mac(key, bytes).ct_eq(tag).into()
+    }
+}
+
+impl Hasher<Fp> for AnemoiHash {
+    type Digest = AnemoiDigest;
+
+    fn hash(bytes: &[Fp]) -> Self::Digest {
+        let mut hasher = Self::default();
+        hasher.update(bytes);
+        hasher.finalize()
+    }
+
+    // The two digests fill the rate of the state, which allows
+    // to merge them with a single Anemoi permutation call.
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        state[..DIGEST_SIZE].copy_from_slice(values[0].as_elements());
+        state[DIGEST_SIZE..RATE_WIDTH].copy_from_slice(values[1].as_elements());
+        apply_permutation(&mut state);
+
+        AnemoiDigest::new(state[..DIGEST_SIZE].try_into().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_anemoi_hash() {
+        let input_data = [
+            vec![Fp::zero(); 12],
+            vec![Fp::one(); 12],
+            vec![
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+            ],
+            vec![
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+            ],
+            vec![Fp::new(17034304680378990593)],
+            vec![Fp::new(14717522243836148645), Fp::new(13083744320516212099)],
+            vec![
+                Fp::new(12636374470493764585),
+                Fp::new(4633685103624134705),
+                Fp::new(3387113220078373436),
+            ],
+            vec![
+                Fp::new(8456646190515084682),
+                Fp::new(13422660296625058046),
+                Fp::new(6412264356237719015),
+                Fp::new(15681324668660747245),
+            ],
+            vec![
+                Fp::new(5024098825481674854),
+                Fp::new(17074804226935063103),
+                Fp::new(12706716815026291874),
+                Fp::new(15111404609115389377),
+                Fp::new(14660803971611056841),
+            ],
+            vec![
+                Fp::new(12593193490016384806),
+                Fp::new(18163695353480745896),
+                Fp::new(12379842404544411425),
+                Fp::new(15341182951106044393),
+                Fp::new(1499922380158571885),
+                Fp::new(4387632416457430195),
+            ],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(7146437234001776362),
+                Fp::new(17821401106216238675),
+                Fp::new(7973575350080885653),
+                Fp::new(17444967668828305944),
+            ],
+            [
+                Fp::new(2113055188795986791),
+                Fp::new(635024997832690091),
+                Fp::new(7623680576300093660),
+                Fp::new(13469413631660413993),
+            ],
+            [
+                Fp::new(3631748572789351036),
+                Fp::new(15559596550406876482),
+                Fp::new(14583345933407494334),
+                Fp::new(16211933812264906494),
+            ],
+            [
+                Fp::new(17220547982869658526),
+                Fp::new(14652562463429309181),
+                Fp::new(2272654452954436331),
+                Fp::new(13019308431479856048),
+            ],
+            [
+                Fp::new(18400371953667726937),
+                Fp::new(3806196094991708848),
+                Fp::new(6646446989884725270),
+                Fp::new(5667609126496729177),
+            ],
+            [
+                Fp::new(2385811711116474672),
+                Fp::new(2422546296501859652),
+                Fp::new(12887933106925454744),
+                Fp::new(9629076071831495683),
+            ],
+            [
+                Fp::new(10898586959282715837),
+                Fp::new(15491533587281651193),
+                Fp::new(1644788560426310488),
+                Fp::new(17988456972806068323),
+            ],
+            [
+                Fp::new(2938228286131928762),
+                Fp::new(4745988401313101485),
+                Fp::new(9259760034999731521),
+                Fp::new(7529036663561896262),
+            ],
+            [
+                Fp::new(16111757284747050720),
+                Fp::new(6903632227495871465),
+                Fp::new(1410870288936857590),
+                Fp::new(11384870192562145192),
+            ],
+            [
+                Fp::new(13360301747692308135),
+                Fp::new(5132848469217002885),
+                Fp::new(14490432409963156516),
+                Fp::new(7895123030904096082),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, AnemoiHash::hash(input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_anemoi_hash_update() {
+        let mut rng = OsRng;
+        let mut input = [Fp::zero(); 3 * RATE_WIDTH + 1];
+        for element in input.iter_mut() {
+            *element = Fp::random(&mut rng);
+        }
+
+        for len in 0..=input.len() {
+            let expected = AnemoiHash::hash(&input[..len]);
+
+            for chunk_size in 1..=RATE_WIDTH + 1 {
+                let mut hasher = AnemoiHash::default();
+                for chunk in input[..len].chunks(chunk_size) {
+                    hasher.update(chunk);
+                }
+
+                assert_eq!(expected, hasher.finalize());
+            }
+        }
+    }
+
+    #[test]
+    fn test_anemoi_hash_bytes() {
+        let inputs: [&[u8]; 5] = [b"", b"\0", b"\0\0", b"0123456", b"01234567"];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let digest = AnemoiHash::hash_bytes(input);
+            assert_eq!(digest, AnemoiHash::hash(&bytes_to_elements(input)));
+
+            for other in inputs.iter().skip(i + 1) {
+                assert_ne!(digest, AnemoiHash::hash_bytes(other));
+            }
+        }
+    }
+
+    #[test]
+    fn test_anemoi_hash_xof() {
+        let input = [Fp::zero(), Fp::one(), Fp::new(2)];
+
+        // Output spanning more than two rate blocks, to cover squeezing
+        // beyond the first permutation.
+        let expected = [
+            Fp::new(17257824886124988191),
+            Fp::new(11478276491795756282),
+            Fp::new(11586456578103644925),
+            Fp::new(13710245798371648298),
+            Fp::new(6658805493226311658),
+            Fp::new(1591778676203761076),
+            Fp::new(1156883328029264674),
+            Fp::new(12563981882750501614),
+            Fp::new(6191062262964109619),
+            Fp::new(3768066366739965900),
+            Fp::new(4508289090558562874),
+            Fp::new(2549516643097123938),
+            Fp::new(12281799154791298472),
+            Fp::new(4744962838949827687),
+            Fp::new(7845832270759543108),
+            Fp::new(12205915568489056281),
+            Fp::new(2912138796459764220),
+        ];
+
+        let mut output = [Fp::zero(); 17];
+        AnemoiHash::hash_xof(&input).read(&mut output);
+        assert_eq!(output, expected);
+        assert_eq!(
+            output[..DIGEST_SIZE],
+            AnemoiHash::hash(&input).to_elements()
+        );
+
+        let mut hasher = AnemoiHash::default();
+        hasher.update(&input);
+        let mut reader = hasher.finalize_xof();
+        let mut output = [Fp::zero(); 17];
+        for chunk in output.chunks_mut(3) {
+            reader.read(chunk);
+        }
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_anemoi_merge() {
+        let input_data = [
+            [AnemoiDigest::default(), AnemoiDigest::default()],
+            [
+                AnemoiDigest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+                AnemoiDigest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+            ],
+            [
+                AnemoiDigest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+                AnemoiDigest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+            ],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(6245028065188671571),
+                Fp::new(7174927132553947745),
+                Fp::new(13847097812052451370),
+                Fp::new(15021047002362097727),
+            ],
+            [
+                Fp::new(8163010615480471493),
+                Fp::new(5111409447444106042),
+                Fp::new(12951993473707056944),
+                Fp::new(3820602135492751699),
+            ],
+            [
+                Fp::new(13443832199771713285),
+                Fp::new(12048571728043580606),
+                Fp::new(4993773724764205348),
+                Fp::new(1768999544258193320),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, AnemoiHash::merge(input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_anemoi_mac() {
+        let key = [Fp::new(1), Fp::new(2), Fp::new(3), Fp::new(4)];
+        let message = [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+        ];
+
+        let output_data = [
+            [
+                Fp::new(4402904843902828447),
+                Fp::new(13480255725914164541),
+                Fp::new(4341441535372240890),
+                Fp::new(6531530131482840525),
+            ],
+            [
+                Fp::new(15023884004158225419),
+                Fp::new(17697354170651438760),
+                Fp::new(6681189954370984204),
+                Fp::new(11494445126438008218),
+            ],
+            [
+                Fp::new(12914325082558550674),
+                Fp::new(7628252372527633549),
+                Fp::new(16940507354209636237),
+                Fp::new(1446125184721318646),
+            ],
+        ];
+
+        for (len, expected) in [0, 3, 8].into_iter().zip(output_data) {
+            let input = &message[..len];
+            let tag = AnemoiHash::mac(&key, input);
+            assert_eq!(expected, tag.to_elements());
+
+            let mut hasher = AnemoiHash::new_keyed(&key);
+            hasher.update(input);
+            assert_eq!(tag, hasher.finalize());
+
+            assert!(AnemoiHash::verify(&key, input, &tag));
+            assert!(!AnemoiHash::verify(
+                &[Fp::zero(); CAPACITY_WIDTH],
+                input,
+                &tag
+            ));
+            assert!(!AnemoiHash::verify(&key, &message[1..], &tag));
+
+            let mut forged = tag.to_elements();
+            forged[DIGEST_SIZE - 1] += Fp::one();
+            assert!(!AnemoiHash::verify(&key, input, &AnemoiDigest::new(forged)));
+        }
+
+        // Appending a one or a zero to a message invalidates its tag,
+        // wherever the message ends within the rate.
+        for len in 0..message.len() {
+            let tag = AnemoiHash::mac(&key, &message[..len]);
+            let mut extended = message[..=len].to_vec();
+            extended[len] = Fp::one();
+            assert!(!AnemoiHash::verify(&key, &extended, &tag));
+            extended[len] = Fp::zero();
+            assert!(!AnemoiHash::verify(&key, &extended, &tag));
+        }
+    }
+}
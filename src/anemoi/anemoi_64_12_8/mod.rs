@@ -0,0 +1,733 @@
+// Copyright (c) 2021-2022 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::duplex::Duplex;
+use crate::f64_utils::apply_rescue_inv_sbox;
+use cheetah::fp_arith_utils::reduce_u96;
+use cheetah::Fp;
+
+/// Digest for Anemoi
+mod digest;
+/// Hasher for Anemoi
+mod hasher;
+/// MDS matrix for Anemoi
+mod mds;
+/// Permutation for Anemoi
+mod permutation;
+/// Round constants for Anemoi
+mod round_constants;
+/// S-Box for Anemoi
+mod sbox;
+
+pub use digest::AnemoiDigest;
+pub use hasher::AnemoiHash;
+pub use permutation::AnemoiPermutation;
+
+/// A duplex sponge over the Anemoi permutation, with the rate and capacity of this instance.
+pub type AnemoiDuplex = Duplex<AnemoiPermutation, RATE_WIDTH, CAPACITY_WIDTH>;
+
+// ANEMOI CONSTANTS
+// ================================================================================================
+
+/// Function state is set to 12 field elements or 96 bytes.
+/// 4 elements of the state are reserved for capacity.
+pub const STATE_WIDTH: usize = 12;
+/// 8 elements of the state are reserved for rate.
+pub const RATE_WIDTH: usize = 8;
+/// 4 elements of the state are reserved for capacity.
+pub const CAPACITY_WIDTH: usize = STATE_WIDTH - RATE_WIDTH;
+
+/// The state is divided into two even-length rows.
+pub const NUM_COLUMNS: usize = 6;
+
+/// Four elements (32-bytes) are returned as digest.
+pub const DIGEST_SIZE: usize = 4;
+
+/// The number of rounds is set to 10 to provide 128-bit security level.
+pub const NUM_HASH_ROUNDS: usize = 10;
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+#[inline(always)]
+/// Applies application of the Anemoi S-Box
+/// to the current hash state.
+pub(crate) fn apply_sbox(state: &mut [Fp; STATE_WIDTH]) {
+    let mut x: [Fp; NUM_COLUMNS] = state[..NUM_COLUMNS].try_into().unwrap();
+    let mut y: [Fp; NUM_COLUMNS] = state[NUM_COLUMNS..].try_into().unwrap();
+
+    x.iter_mut()
+        .enumerate()
+        .for_each(|(i, t)| *t -= y[i].square().mul_by_u32(sbox::BETA));
+
+    let mut x_alpha_inv = x;
+    apply_rescue_inv_sbox(&mut x_alpha_inv);
+
+    y.iter_mut()
+        .enumerate()
+        .for_each(|(i, t)| *t -= x_alpha_inv[i]);
+
+    x.iter_mut()
+        .enumerate()
+        .for_each(|(i, t)| *t += y[i].square().mul_by_u32(sbox::BETA) + sbox::DELTA);
+
+    state[..NUM_COLUMNS].copy_from_slice(&x);
+    state[NUM_COLUMNS..].copy_from_slice(&y);
+}
+
+#[inline(always)]
+/// Applies matrix-vector multiplication of the current
+/// hash state with the Anemoi MDS matrix.
+pub(crate) fn apply_mds(state: &mut [Fp; STATE_WIDTH]) {
+    let x: [u128; NUM_COLUMNS] = [
+        state[0].output_unreduced_internal() as u128,
+        state[1].output_unreduced_internal() as u128,
+        state[2].output_unreduced_internal() as u128,
+        state[3].output_unreduced_internal() as u128,
+        state[4].output_unreduced_internal() as u128,
+        state[5].output_unreduced_internal() as u128,
+    ];
+    // The second vector is first permuted
+    let y: [u128; NUM_COLUMNS] = [
+        state[7].output_unreduced_internal() as u128,
+        state[8].output_unreduced_internal() as u128,
+        state[9].output_unreduced_internal() as u128,
+        state[10].output_unreduced_internal() as u128,
+        state[11].output_unreduced_internal() as u128,
+        state[6].output_unreduced_internal() as u128,
+    ];
+
+    // MDS layer over X and Y vectors. The matrix coefficients being
+    // small, the sums can be reduced once at the end.
+
+    let mut mds_x = [0u128; NUM_COLUMNS];
+    let mut mds_y = [0u128; NUM_COLUMNS];
+    for (i, (mx, my)) in mds_x.iter_mut().zip(mds_y.iter_mut()).enumerate() {
+        for (j, (&xj, &yj)) in x.iter().zip(y.iter()).enumerate() {
+            let m = mds::MDS[i * NUM_COLUMNS + j] as u128;
+            *mx += m * xj;
+            *my += m * yj;
+        }
+    }
+
+    // Before applying modular reduction, we perform a final
+    // Pseudo-Hadamard transform on each pair (x_i, y_i).
+
+    for (mx, my) in mds_x.iter_mut().zip(mds_y.iter_mut()) {
+        *my += *mx;
+        *mx += *my;
+    }
+
+    for (s, v) in state.iter_mut().zip(mds_x.iter().chain(mds_y.iter())) {
+        *s = Fp::from_raw_unchecked(reduce_u96(*v));
+    }
+}
+
+// ANEMOI PERMUTATION
+// ================================================================================================
+
+/// Applies Anemoi permutation to the provided state.
+pub(crate) fn apply_permutation(state: &mut [Fp; STATE_WIDTH]) {
+    for i in 0..NUM_HASH_ROUNDS {
+        apply_round(state, i);
+    }
+
+    apply_mds(state)
+}
+
+/// Anemoi round function
+#[inline(always)]
+pub(crate) fn apply_round(state: &mut [Fp; STATE_WIDTH], step: usize) {
+    // determine which round constants to use
+    let c = &round_constants::C[step % NUM_HASH_ROUNDS];
+    let d = &round_constants::D[step % NUM_HASH_ROUNDS];
+
+    for i in 0..NUM_COLUMNS {
+        state[i] += c[i];
+        state[NUM_COLUMNS + i] += d[i];
+    }
+
+    apply_mds(state);
+    apply_sbox(state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply_naive_mds(state: &mut [Fp; STATE_WIDTH]) {
+        let x: [Fp; NUM_COLUMNS] = state[..NUM_COLUMNS].try_into().unwrap();
+        let mut y: [Fp; NUM_COLUMNS] = [Fp::zero(); NUM_COLUMNS];
+        y[0..NUM_COLUMNS - 1].copy_from_slice(&state[NUM_COLUMNS + 1..]);
+        y[NUM_COLUMNS - 1] = state[NUM_COLUMNS];
+
+        let mut result = [Fp::zero(); STATE_WIDTH];
+        for (i, r) in result.iter_mut().enumerate().take(NUM_COLUMNS) {
+            for (j, s) in x.into_iter().enumerate().take(NUM_COLUMNS) {
+                *r += s.mul_by_u32(mds::MDS[i * NUM_COLUMNS + j]);
+            }
+        }
+        for (i, r) in result.iter_mut().enumerate().skip(NUM_COLUMNS) {
+            for (j, s) in y.into_iter().enumerate() {
+                *r += s.mul_by_u32(mds::MDS[(i - NUM_COLUMNS) * NUM_COLUMNS + j]);
+            }
+        }
+
+        // Pseudo-Hadamard transform
+        for i in 0..NUM_COLUMNS {
+            result[i + NUM_COLUMNS] += result[i];
+            result[i] += result[i + NUM_COLUMNS];
+        }
+
+        state.copy_from_slice(&result);
+    }
+
+    #[test]
+    fn test_sbox() {
+        let mut input = [
+            [Fp::zero(); 12],
+            [Fp::one(); 12],
+            [
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+            ],
+            [
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+            ],
+            [
+                Fp::new(17423983679330604156),
+                Fp::new(4677141341184559961),
+                Fp::new(9252460361591153058),
+                Fp::new(851270947708484361),
+                Fp::new(15749416559116541989),
+                Fp::new(10535457148059317747),
+                Fp::new(16325394655000736802),
+                Fp::new(15861696232111219618),
+                Fp::new(14952207971796381570),
+                Fp::new(5354804073368371571),
+                Fp::new(15179877018224218356),
+                Fp::new(13653188584983652491),
+            ],
+            [
+                Fp::new(4291839133129274381),
+                Fp::new(17137370297779833669),
+                Fp::new(7584705119956532426),
+                Fp::new(14666450837908662446),
+                Fp::new(10364955386008823349),
+                Fp::new(2721318136771011065),
+                Fp::new(16497507404896471324),
+                Fp::new(4508110359315115580),
+                Fp::new(2231576177088690723),
+                Fp::new(4045620135736294294),
+                Fp::new(11367967224738861189),
+                Fp::new(16595386199170881588),
+            ],
+            [
+                Fp::new(6313686134045469177),
+                Fp::new(11706204041685266408),
+                Fp::new(13914655879011897660),
+                Fp::new(14661331301840215547),
+                Fp::new(9517217765740616854),
+                Fp::new(9827707806680094201),
+                Fp::new(1258765777928525126),
+                Fp::new(8318975484353539222),
+                Fp::new(16066242592211961115),
+                Fp::new(12127866743086949057),
+                Fp::new(9338673378236916946),
+                Fp::new(4847264874520410971),
+            ],
+            [
+                Fp::new(7980948695017534993),
+                Fp::new(6141402559616558793),
+                Fp::new(10129652258982486089),
+                Fp::new(6343379184704146939),
+                Fp::new(4092068743128412131),
+                Fp::new(1762512308892867408),
+                Fp::new(3277695310327618942),
+                Fp::new(10504692281674248979),
+                Fp::new(893915709041658646),
+                Fp::new(629215277272866785),
+                Fp::new(2030632897795193594),
+                Fp::new(7537479964301897219),
+            ],
+            [
+                Fp::new(3782351136259002915),
+                Fp::new(8436287662062117153),
+                Fp::new(2432708193282115285),
+                Fp::new(13060249077668699249),
+                Fp::new(18401402791174097112),
+                Fp::new(6794016068022536505),
+                Fp::new(9070961130987747547),
+                Fp::new(10375397673695775830),
+                Fp::new(954837329913344553),
+                Fp::new(10915844169467323107),
+                Fp::new(9311587437195854371),
+                Fp::new(10120292931649366381),
+            ],
+            [
+                Fp::new(10974646260112104950),
+                Fp::new(3904360792099936662),
+                Fp::new(4559321416955878381),
+                Fp::new(15453143552822928478),
+                Fp::new(4766820171748024319),
+                Fp::new(4522413849026020583),
+                Fp::new(15228481494815102018),
+                Fp::new(6279341699506832522),
+                Fp::new(4183899199765683808),
+                Fp::new(2576394100107007278),
+                Fp::new(408668067900312651),
+                Fp::new(16317310416603821532),
+            ],
+        ];
+
+        let output = [
+            [
+                Fp::new(2635249152773512046),
+                Fp::new(2635249152773512046),
+                Fp::new(2635249152773512046),
+                Fp::new(2635249152773512046),
+                Fp::new(2635249152773512046),
+                Fp::new(2635249152773512046),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+            ],
+            [
+                Fp::new(17136669903572771321),
+                Fp::new(17136669903572771321),
+                Fp::new(17136669903572771321),
+                Fp::new(17136669903572771321),
+                Fp::new(17136669903572771321),
+                Fp::new(17136669903572771321),
+                Fp::new(9739452640566982996),
+                Fp::new(9739452640566982996),
+                Fp::new(9739452640566982996),
+                Fp::new(9739452640566982996),
+                Fp::new(9739452640566982996),
+                Fp::new(9739452640566982996),
+            ],
+            [
+                Fp::new(6928912281476859504),
+                Fp::new(6928912281476859504),
+                Fp::new(6928912281476859504),
+                Fp::new(6928912281476859504),
+                Fp::new(6928912281476859504),
+                Fp::new(6928912281476859504),
+                Fp::new(5829874566404923654),
+                Fp::new(5829874566404923654),
+                Fp::new(5829874566404923654),
+                Fp::new(5829874566404923654),
+                Fp::new(5829874566404923654),
+                Fp::new(5829874566404923654),
+            ],
+            [
+                Fp::new(2635249152773512054),
+                Fp::new(2635249152773512054),
+                Fp::new(2635249152773512054),
+                Fp::new(2635249152773512054),
+                Fp::new(2635249152773512054),
+                Fp::new(2635249152773512054),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+            ],
+            [
+                Fp::new(10991124531223827262),
+                Fp::new(17382049514848695696),
+                Fp::new(6117656612620466832),
+                Fp::new(6999044114217166427),
+                Fp::new(826369700048553696),
+                Fp::new(10553076348071834065),
+                Fp::new(14103015657051118963),
+                Fp::new(7226487513187740097),
+                Fp::new(7708939593778411339),
+                Fp::new(6209196598759116828),
+                Fp::new(13058461112415328602),
+                Fp::new(9651020443856096884),
+            ],
+            [
+                Fp::new(15646271456535185835),
+                Fp::new(15027275206755075037),
+                Fp::new(4893542627410684558),
+                Fp::new(5168776781080217354),
+                Fp::new(11331793858817558570),
+                Fp::new(5733087292948871208),
+                Fp::new(4225016796315894766),
+                Fp::new(11352958082541547626),
+                Fp::new(14981669552655619286),
+                Fp::new(5238822291716334651),
+                Fp::new(6582852808825883397),
+                Fp::new(6172176143837670989),
+            ],
+            [
+                Fp::new(2491940409519197548),
+                Fp::new(12227592439116593995),
+                Fp::new(8661718641746341183),
+                Fp::new(10184361342420348072),
+                Fp::new(8078732598547466556),
+                Fp::new(3405378656427471304),
+                Fp::new(4410562945667285784),
+                Fp::new(537814069914321462),
+                Fp::new(13800960376408249097),
+                Fp::new(3043770879607421353),
+                Fp::new(16544402934553387316),
+                Fp::new(12509737613613482069),
+            ],
+            [
+                Fp::new(7484585316719847),
+                Fp::new(5177493485322182850),
+                Fp::new(17484076486821576547),
+                Fp::new(17107045512635882193),
+                Fp::new(11402772341349089428),
+                Fp::new(9301564795056471120),
+                Fp::new(12904361465868685460),
+                Fp::new(14528263708818890021),
+                Fp::new(17804305439439782040),
+                Fp::new(2432917329414399029),
+                Fp::new(17740332018220103476),
+                Fp::new(7827526199865234960),
+            ],
+            [
+                Fp::new(11491125353708687858),
+                Fp::new(17391923978205557017),
+                Fp::new(5799729584068785056),
+                Fp::new(11086209417635308130),
+                Fp::new(1545825067195389367),
+                Fp::new(13518776577767891797),
+                Fp::new(6814490908354383711),
+                Fp::new(4011685430918132904),
+                Fp::new(17780927051604496716),
+                Fp::new(272877521905730486),
+                Fp::new(10756595248503403252),
+                Fp::new(7587196953639088872),
+            ],
+            [
+                Fp::new(1685869131445503428),
+                Fp::new(5132889593412466666),
+                Fp::new(12247093604737540851),
+                Fp::new(2222841059333865668),
+                Fp::new(5615293573263788511),
+                Fp::new(10428018497348573400),
+                Fp::new(1979922517619512703),
+                Fp::new(1457745522625034426),
+                Fp::new(13992880183898694768),
+                Fp::new(1573869745020181627),
+                Fp::new(8510172997553768109),
+                Fp::new(655096750158765885),
+            ],
+        ];
+
+        for i in input.iter_mut() {
+            apply_sbox(i);
+        }
+
+        assert_eq!(input, output);
+    }
+
+    #[test]
+    fn test_mds() {
+        let mut input = [
+            [Fp::zero(); 12],
+            [Fp::one(); 12],
+            [
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+            ],
+            [
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+            ],
+            [
+                Fp::new(17423983679330604156),
+                Fp::new(4677141341184559961),
+                Fp::new(9252460361591153058),
+                Fp::new(851270947708484361),
+                Fp::new(15749416559116541989),
+                Fp::new(10535457148059317747),
+                Fp::new(16325394655000736802),
+                Fp::new(15861696232111219618),
+                Fp::new(14952207971796381570),
+                Fp::new(5354804073368371571),
+                Fp::new(15179877018224218356),
+                Fp::new(13653188584983652491),
+            ],
+            [
+                Fp::new(4291839133129274381),
+                Fp::new(17137370297779833669),
+                Fp::new(7584705119956532426),
+                Fp::new(14666450837908662446),
+                Fp::new(10364955386008823349),
+                Fp::new(2721318136771011065),
+                Fp::new(16497507404896471324),
+                Fp::new(4508110359315115580),
+                Fp::new(2231576177088690723),
+                Fp::new(4045620135736294294),
+                Fp::new(11367967224738861189),
+                Fp::new(16595386199170881588),
+            ],
+            [
+                Fp::new(6313686134045469177),
+                Fp::new(11706204041685266408),
+                Fp::new(13914655879011897660),
+                Fp::new(14661331301840215547),
+                Fp::new(9517217765740616854),
+                Fp::new(9827707806680094201),
+                Fp::new(1258765777928525126),
+                Fp::new(8318975484353539222),
+                Fp::new(16066242592211961115),
+                Fp::new(12127866743086949057),
+                Fp::new(9338673378236916946),
+                Fp::new(4847264874520410971),
+            ],
+            [
+                Fp::new(7980948695017534993),
+                Fp::new(6141402559616558793),
+                Fp::new(10129652258982486089),
+                Fp::new(6343379184704146939),
+                Fp::new(4092068743128412131),
+                Fp::new(1762512308892867408),
+                Fp::new(3277695310327618942),
+                Fp::new(10504692281674248979),
+                Fp::new(893915709041658646),
+                Fp::new(629215277272866785),
+                Fp::new(2030632897795193594),
+                Fp::new(7537479964301897219),
+            ],
+            [
+                Fp::new(3782351136259002915),
+                Fp::new(8436287662062117153),
+                Fp::new(2432708193282115285),
+                Fp::new(13060249077668699249),
+                Fp::new(18401402791174097112),
+                Fp::new(6794016068022536505),
+                Fp::new(9070961130987747547),
+                Fp::new(10375397673695775830),
+                Fp::new(954837329913344553),
+                Fp::new(10915844169467323107),
+                Fp::new(9311587437195854371),
+                Fp::new(10120292931649366381),
+            ],
+            [
+                Fp::new(10974646260112104950),
+                Fp::new(3904360792099936662),
+                Fp::new(4559321416955878381),
+                Fp::new(15453143552822928478),
+                Fp::new(4766820171748024319),
+                Fp::new(4522413849026020583),
+                Fp::new(15228481494815102018),
+                Fp::new(6279341699506832522),
+                Fp::new(4183899199765683808),
+                Fp::new(2576394100107007278),
+                Fp::new(408668067900312651),
+                Fp::new(16317310416603821532),
+            ],
+        ];
+
+        let mut input2 = input;
+
+        let output = [
+            [
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+            ],
+            [
+                Fp::new(60),
+                Fp::new(60),
+                Fp::new(60),
+                Fp::new(60),
+                Fp::new(60),
+                Fp::new(60),
+                Fp::new(40),
+                Fp::new(40),
+                Fp::new(40),
+                Fp::new(40),
+                Fp::new(40),
+                Fp::new(40),
+            ],
+            [
+                Fp::new(20),
+                Fp::new(20),
+                Fp::new(20),
+                Fp::new(20),
+                Fp::new(20),
+                Fp::new(20),
+                Fp::new(20),
+                Fp::new(20),
+                Fp::new(20),
+                Fp::new(20),
+                Fp::new(20),
+                Fp::new(20),
+            ],
+            [
+                Fp::new(40),
+                Fp::new(40),
+                Fp::new(40),
+                Fp::new(40),
+                Fp::new(40),
+                Fp::new(40),
+                Fp::new(20),
+                Fp::new(20),
+                Fp::new(20),
+                Fp::new(20),
+                Fp::new(20),
+                Fp::new(20),
+            ],
+            [
+                Fp::new(180180013029591360),
+                Fp::new(14344578458740158188),
+                Fp::new(5747687796005923439),
+                Fp::new(14563525523341458823),
+                Fp::new(17540843494892964117),
+                Fp::new(3046014187416087061),
+                Fp::new(7870949196528841729),
+                Fp::new(17449982762482206632),
+                Fp::new(12432040606334020371),
+                Fp::new(2227503673692187665),
+                Fp::new(16479478446097055181),
+                Fp::new(9759894491012042514),
+            ],
+            [
+                Fp::new(16464952902116332368),
+                Fp::new(17314502006161065249),
+                Fp::new(9193630654260628668),
+                Fp::new(14420171721713604932),
+                Fp::new(12997372746673245275),
+                Fp::new(3231088024956317449),
+                Fp::new(11483835633764053010),
+                Fp::new(15349579603349391377),
+                Fp::new(5146359233105388780),
+                Fp::new(17329813040473468727),
+                Fp::new(13924798839126628088),
+                Fp::new(305941709269160820),
+            ],
+            [
+                Fp::new(7986402084916987410),
+                Fp::new(4686798273953933252),
+                Fp::new(3674917930053118081),
+                Fp::new(571253526629528289),
+                Fp::new(14773905585671956907),
+                Fp::new(11086035091009807444),
+                Fp::new(4385812228981059033),
+                Fp::new(12906602310660796591),
+                Fp::new(8344976068480260706),
+                Fp::new(996809853303830429),
+                Fp::new(11929511140662587280),
+                Fp::new(13565115307925671516),
+            ],
+            [
+                Fp::new(3901680734766527112),
+                Fp::new(1875628648718128337),
+                Fp::new(7780267274718224670),
+                Fp::new(15886856852277943247),
+                Fp::new(15336543036846738931),
+                Fp::new(10675563124920190060),
+                Fp::new(13661902811856628126),
+                Fp::new(4188372005410953042),
+                Fp::new(5908285949775363820),
+                Fp::new(5882359351621021729),
+                Fp::new(1392177744071397409),
+                Fp::new(14847185509841049690),
+            ],
+            [
+                Fp::new(5113278589397061121),
+                Fp::new(13577312043082980999),
+                Fp::new(18114988678618544018),
+                Fp::new(88820771740763080),
+                Fp::new(6595512766400134831),
+                Fp::new(7162838155455898884),
+                Fp::new(3498593300109521143),
+                Fp::new(17725211326515405561),
+                Fp::new(16732973733118833524),
+                Fp::new(5465237169693158316),
+                Fp::new(11803551042486510827),
+                Fp::new(7198041889446479800),
+            ],
+            [
+                Fp::new(14292193722275172031),
+                Fp::new(13712587687550504458),
+                Fp::new(6479863365246232412),
+                Fp::new(4098814526820322497),
+                Fp::new(4870447862857767607),
+                Fp::new(4218576262949958513),
+                Fp::new(2081272740909626518),
+                Fp::new(17382943951962332979),
+                Fp::new(4905094916861215789),
+                Fp::new(8662500238390024802),
+                Fp::new(15991804185776541644),
+                Fp::new(478461870402395734),
+            ],
+        ];
+
+        for i in input.iter_mut() {
+            apply_mds(i);
+        }
+        for i in input2.iter_mut() {
+            apply_naive_mds(i);
+        }
+
+        assert_eq!(input, output);
+        assert_eq!(input2, output);
+    }
+}
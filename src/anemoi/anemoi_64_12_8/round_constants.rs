@@ -0,0 +1,178 @@
+// Copyright (c) 2021-2022 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::{NUM_COLUMNS, NUM_HASH_ROUNDS};
+use cheetah::Fp;
+
+/// Additive constants C for Anemoi,
+pub(crate) const C: [[Fp; NUM_COLUMNS]; NUM_HASH_ROUNDS] = [
+    [
+        Fp::new(135),
+        Fp::new(11838461599927962280),
+        Fp::new(6695425721971157745),
+        Fp::new(5019557931754813304),
+        Fp::new(2904638783509415261),
+        Fp::new(17086431532787116882),
+    ],
+    [
+        Fp::new(2495927434367559449),
+        Fp::new(13492968121013574904),
+        Fp::new(10098914474676720416),
+        Fp::new(4843826562618834151),
+        Fp::new(17539093704093074863),
+        Fp::new(8553726820257998916),
+    ],
+    [
+        Fp::new(6426088485207136111),
+        Fp::new(5831914601985236665),
+        Fp::new(3131753839109558676),
+        Fp::new(3931787235364236402),
+        Fp::new(15904007449688076222),
+        Fp::new(9797250005833249614),
+    ],
+    [
+        Fp::new(15630856188639316257),
+        Fp::new(1985318265374982878),
+        Fp::new(2310933915074061319),
+        Fp::new(82460173033028635),
+        Fp::new(18020935870943136160),
+        Fp::new(6084573595352436610),
+    ],
+    [
+        Fp::new(35751876663066701),
+        Fp::new(12218846398244870934),
+        Fp::new(12158495329324236791),
+        Fp::new(14193345342118923896),
+        Fp::new(5719028597539960093),
+        Fp::new(4952589849070338459),
+    ],
+    [
+        Fp::new(10762311532495310736),
+        Fp::new(1602790612750887683),
+        Fp::new(10558955589715575707),
+        Fp::new(4268644503140521285),
+        Fp::new(11257154188468233463),
+        Fp::new(15424512470177117790),
+    ],
+    [
+        Fp::new(13776040199363511613),
+        Fp::new(2645507812358398727),
+        Fp::new(11189896514172536637),
+        Fp::new(2832927452123329016),
+        Fp::new(11496711359337291663),
+        Fp::new(2261696610080146603),
+    ],
+    [
+        Fp::new(7954822014402935159),
+        Fp::new(4221275062851635374),
+        Fp::new(4146348742796636803),
+        Fp::new(11984179805579125028),
+        Fp::new(8628002987794654845),
+        Fp::new(16956140286558224010),
+    ],
+    [
+        Fp::new(16207264356032897204),
+        Fp::new(14947325633951015074),
+        Fp::new(12682075457336921621),
+        Fp::new(3428661900037965523),
+        Fp::new(3223466965214343391),
+        Fp::new(15171824165606685438),
+    ],
+    [
+        Fp::new(11395204924706988113),
+        Fp::new(1445298492587981366),
+        Fp::new(1648821176782737788),
+        Fp::new(15250803325479081487),
+        Fp::new(5516900161126217551),
+        Fp::new(17018914763275392088),
+    ],
+];
+
+/// Additive constants D for Anemoi,
+pub(crate) const D: [[Fp; NUM_COLUMNS]; NUM_HASH_ROUNDS] = [
+    [
+        Fp::new(2635249152773512181),
+        Fp::new(6157005413239783064),
+        Fp::new(68462008871283793),
+        Fp::new(1116237429717582885),
+        Fp::new(9681424646190483573),
+        Fp::new(1572301186541881797),
+    ],
+    [
+        Fp::new(5762606100895777574),
+        Fp::new(8442941448080101767),
+        Fp::new(4103380275331552543),
+        Fp::new(1571935574336309811),
+        Fp::new(6500565011114264933),
+        Fp::new(12117770057182054231),
+    ],
+    [
+        Fp::new(13733991889676916174),
+        Fp::new(4823112666993325466),
+        Fp::new(1177444377705952741),
+        Fp::new(4701120985023274000),
+        Fp::new(8906703494650828230),
+        Fp::new(17402517980698866867),
+    ],
+    [
+        Fp::new(8470567618884092005),
+        Fp::new(4955068425572651685),
+        Fp::new(4335176548860035390),
+        Fp::new(4830346017881646239),
+        Fp::new(15002184011095468174),
+        Fp::new(17668393665407633869),
+    ],
+    [
+        Fp::new(1762538920950224372),
+        Fp::new(5628928103070337343),
+        Fp::new(4623069507738008464),
+        Fp::new(9381562731595339102),
+        Fp::new(11587352351734674030),
+        Fp::new(6976741463753333320),
+    ],
+    [
+        Fp::new(7562502807542755678),
+        Fp::new(8533020617751225684),
+        Fp::new(16543678068304218972),
+        Fp::new(12977010192791808083),
+        Fp::new(12198882173423234671),
+        Fp::new(12522068315620399922),
+    ],
+    [
+        Fp::new(12515775684509917073),
+        Fp::new(11515282027457697246),
+        Fp::new(667419133445556099),
+        Fp::new(13480837351873576332),
+        Fp::new(14377983554391253389),
+        Fp::new(1298796665622389253),
+    ],
+    [
+        Fp::new(4309793999236275086),
+        Fp::new(10706285777637868360),
+        Fp::new(9685851931171175053),
+        Fp::new(1800582135601722490),
+        Fp::new(9124511682535551038),
+        Fp::new(13608476841787401127),
+    ],
+    [
+        Fp::new(168687491574439534),
+        Fp::new(9038787499445450463),
+        Fp::new(5828029796419662274),
+        Fp::new(17745003519597934030),
+        Fp::new(9773170880078026308),
+        Fp::new(17877355940958649279),
+    ],
+    [
+        Fp::new(16776936635835678651),
+        Fp::new(16957068933669564963),
+        Fp::new(16215084091452626649),
+        Fp::new(14093965381797029560),
+        Fp::new(15040168582162464355),
+        Fp::new(4251266975385335495),
+    ],
+];
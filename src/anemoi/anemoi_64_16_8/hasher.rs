@@ -0,0 +1,551 @@
+// Copyright (c) 2021-2022 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Hasher trait implementation for Anemoi
+
+use super::digest::AnemoiDigest;
+use super::{
+    apply_permutation, AnemoiPermutation, CAPACITY_WIDTH, DIGEST_SIZE, RATE_WIDTH, STATE_WIDTH,
+};
+use crate::f64_utils::bytes_to_elements;
+use crate::jive::Jive;
+use crate::sponge::{Padding, Sponge, XofReader};
+use crate::traits::Hasher;
+
+use cheetah::Fp;
+use subtle::ConstantTimeEq;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// A Anemoi Hash over Fp
+pub struct AnemoiHash {
+    sponge: Sponge<AnemoiPermutation, RATE_WIDTH, CAPACITY_WIDTH>,
+}
+
+impl Default for AnemoiHash {
+    fn default() -> Self {
+        Self {
+            sponge: Sponge::new(Padding::Sigma),
+        }
+    }
+}
+
+impl AnemoiHash {
+    /// Returns a new hasher keyed with `key`, to compute a message
+    /// authentication code through `update` and `finalize`.
+    ///
+    /// The key is set as the initial capacity, and the message is
+    /// always padded with a one, even when its length is a multiple
+    /// of the rate, so that no two messages share the same padding.
+    pub fn new_keyed(key: &[Fp; CAPACITY_WIDTH]) -> Self {
+        Self {
+            sponge: Sponge::with_initial_capacity(Padding::AlwaysOne, *key),
+        }
+    }
+
+    /// Absorbs a new sequence of field elements into the hasher state.
+    ///
+    /// This can be called several times on chunks of a message, the
+    /// resulting digest being identical to the one returned by
+    /// `AnemoiHash::hash` on the concatenated input.
+    pub fn update(&mut self, bytes: &[Fp]) {
+        self.sponge.absorb(bytes);
+    }
+
+    /// Pads the absorbed sequence and returns its digest.
+    pub fn finalize(self) -> AnemoiDigest {
+        let mut digest = [Fp::zero(); DIGEST_SIZE];
+        self.finalize_xof().read(&mut digest);
+
+        AnemoiDigest::new(digest)
+    }
+
+    /// Pads the absorbed sequence and returns a reader over an arbitrarily
+    /// long output, whose first `DIGEST_SIZE` elements form its digest.
+    pub fn finalize_xof(self) -> XofReader<AnemoiPermutation, RATE_WIDTH, CAPACITY_WIDTH> {
+        self.sponge.into_reader()
+    }
+
+    /// Returns a reader over an arbitrarily long hash output
+    /// of the provided sequence of field elements.
+    pub fn hash_xof(bytes: &[Fp]) -> XofReader<AnemoiPermutation, RATE_WIDTH, CAPACITY_WIDTH> {
+        let mut hasher = Self::default();
+        hasher.update(bytes);
+        hasher.finalize_xof()
+    }
+
+    /// Returns a hash of the provided sequence of bytes.
+    ///
+    /// The bytes are first packed into field elements with
+    /// `f64_utils::bytes_to_elements`.
+    pub fn hash_bytes(bytes: &[u8]) -> AnemoiDigest {
+        Self::hash(&bytes_to_elements(bytes))
+    }
+
+    /// Returns a message authentication code of the provided
+    /// sequence of field elements under `key`, as computed by a
+    /// hasher returned by `new_keyed`.
+    pub fn mac(key: &[Fp; CAPACITY_WIDTH], bytes: &[Fp]) -> AnemoiDigest {
+        let mut hasher = Self::new_keyed(key);
+        hasher.update(bytes);
+        hasher.finalize()
+    }
+
+    /// Checks in constant time that `tag` is a valid message
+    /// authentication code of `bytes` under `key`.
+    pub fn verify(key: &[Fp; CAPACITY_WIDTH], bytes: &[Fp], tag: &AnemoiDigest) -> bool {
+        Self::mac(key, bytes).ct_eq(tag).into()
+    }
+
+    /// Returns a hash of four digests, with a single Anemoi permutation call.
+    /// This method is intended for use in construction of quaternary Merkle trees.
+    ///
+    /// The four digests fill the state, which is compressed with the Jive
+    /// mode for b = 4: the result is the sum of the four digests and of the
+    /// four quarters of the permuted state.
+    pub fn merge4(values: &[AnemoiDigest; 4]) -> AnemoiDigest {
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        for (chunk, value) in state.chunks_exact_mut(DIGEST_SIZE).zip(values) {
+            chunk.copy_from_slice(value.as_elements());
+        }
+
+        let mut result = [Fp::zero(); DIGEST_SIZE];
+        Jive::<AnemoiPermutation, 4>::compress(&state, &mut result);
+
+        AnemoiDigest::new(result)
+    }
+}
+
+impl Hasher<Fp> for AnemoiHash {
+    type Digest = AnemoiDigest;
+
+    fn hash(bytes: &[Fp]) -> Self::Digest {
+        let mut hasher = Self::default();
+        hasher.update(bytes);
+        hasher.finalize()
+    }
+
+    // The two digests fill the rate of the state, which allows
+    // to merge them with a single Anemoi permutation call.
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        state[..DIGEST_SIZE].copy_from_slice(values[0].as_elements());
+        state[DIGEST_SIZE..RATE_WIDTH].copy_from_slice(values[1].as_elements());
+        apply_permutation(&mut state);
+
+        AnemoiDigest::new(state[..DIGEST_SIZE].try_into().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+
+    #[test]
+    fn test_anemoi_hash() {
+        let input_data = [
+            vec![Fp::zero(); 16],
+            vec![Fp::one(); 16],
+            vec![
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+            ],
+            vec![
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+            ],
+            vec![Fp::new(17034304680378990593)],
+            vec![Fp::new(14717522243836148645), Fp::new(13083744320516212099)],
+            vec![
+                Fp::new(12636374470493764585),
+                Fp::new(4633685103624134705),
+                Fp::new(3387113220078373436),
+            ],
+            vec![
+                Fp::new(8456646190515084682),
+                Fp::new(13422660296625058046),
+                Fp::new(6412264356237719015),
+                Fp::new(15681324668660747245),
+            ],
+            vec![
+                Fp::new(5024098825481674854),
+                Fp::new(17074804226935063103),
+                Fp::new(12706716815026291874),
+                Fp::new(15111404609115389377),
+                Fp::new(14660803971611056841),
+            ],
+            vec![
+                Fp::new(12593193490016384806),
+                Fp::new(18163695353480745896),
+                Fp::new(12379842404544411425),
+                Fp::new(15341182951106044393),
+                Fp::new(1499922380158571885),
+                Fp::new(4387632416457430195),
+            ],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(7028618598829391203),
+                Fp::new(2935703074153706778),
+                Fp::new(17908204086648799426),
+                Fp::new(5923380653539844057),
+            ],
+            [
+                Fp::new(8635412154197208328),
+                Fp::new(5995745703092090552),
+                Fp::new(7850301413185111426),
+                Fp::new(11114382075565872297),
+            ],
+            [
+                Fp::new(10356156649399354927),
+                Fp::new(2102661427806594776),
+                Fp::new(4451638133213191282),
+                Fp::new(6313592683376775015),
+            ],
+            [
+                Fp::new(12647316272402721817),
+                Fp::new(4622112541179502453),
+                Fp::new(11175621281506435035),
+                Fp::new(10870561320183496187),
+            ],
+            [
+                Fp::new(15262043387074706990),
+                Fp::new(10102229914788319262),
+                Fp::new(18212263449927663674),
+                Fp::new(13200201813035724914),
+            ],
+            [
+                Fp::new(13659638755185451246),
+                Fp::new(16259289783577782353),
+                Fp::new(18042441488377238127),
+                Fp::new(15847937136310343358),
+            ],
+            [
+                Fp::new(3301750610795755347),
+                Fp::new(17767923427743696880),
+                Fp::new(4959571267475769917),
+                Fp::new(15858724909091757267),
+            ],
+            [
+                Fp::new(16091913069639455443),
+                Fp::new(10302220673863550772),
+                Fp::new(12274026444384915979),
+                Fp::new(157951623583166085),
+            ],
+            [
+                Fp::new(17486646591051541597),
+                Fp::new(1169374903299160944),
+                Fp::new(7511030623654523243),
+                Fp::new(528589955155164811),
+            ],
+            [
+                Fp::new(2768664709938266153),
+                Fp::new(10109728371191129382),
+                Fp::new(1795471379885702360),
+                Fp::new(11359487659720764164),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, AnemoiHash::hash(input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_anemoi_hash_update() {
+        let mut rng = OsRng;
+        let mut input = [Fp::zero(); 3 * RATE_WIDTH + 1];
+        for element in input.iter_mut() {
+            *element = Fp::random(&mut rng);
+        }
+
+        for len in 0..=input.len() {
+            let expected = AnemoiHash::hash(&input[..len]);
+
+            for chunk_size in 1..=RATE_WIDTH + 1 {
+                let mut hasher = AnemoiHash::default();
+                for chunk in input[..len].chunks(chunk_size) {
+                    hasher.update(chunk);
+                }
+
+                assert_eq!(expected, hasher.finalize());
+            }
+        }
+    }
+
+    #[test]
+    fn test_anemoi_hash_bytes() {
+        let inputs: [&[u8]; 5] = [b"", b"\0", b"\0\0", b"0123456", b"01234567"];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let digest = AnemoiHash::hash_bytes(input);
+            assert_eq!(digest, AnemoiHash::hash(&bytes_to_elements(input)));
+
+            for other in inputs.iter().skip(i + 1) {
+                assert_ne!(digest, AnemoiHash::hash_bytes(other));
+            }
+        }
+    }
+
+    #[test]
+    fn test_anemoi_hash_xof() {
+        let input = [Fp::zero(), Fp::one(), Fp::new(2)];
+
+        // Output spanning more than two rate blocks, to cover squeezing
+        // beyond the first permutation.
+        let expected = [
+            Fp::new(14764206912257802569),
+            Fp::new(4552993881821386984),
+            Fp::new(16828489573693452278),
+            Fp::new(16569787378708570145),
+            Fp::new(10081414049321641893),
+            Fp::new(6666610550628144513),
+            Fp::new(18270828550697614463),
+            Fp::new(16034634350282361073),
+            Fp::new(16291860998213131670),
+            Fp::new(9467378251598738899),
+            Fp::new(14952325530726939680),
+            Fp::new(15069050688961089258),
+            Fp::new(8860316609763515713),
+            Fp::new(4932708753262796149),
+            Fp::new(17630068143616089433),
+            Fp::new(3500743873622383143),
+            Fp::new(4098845403748284141),
+        ];
+
+        let mut output = [Fp::zero(); 17];
+        AnemoiHash::hash_xof(&input).read(&mut output);
+        assert_eq!(output, expected);
+        assert_eq!(
+            output[..DIGEST_SIZE],
+            AnemoiHash::hash(&input).to_elements()
+        );
+
+        let mut hasher = AnemoiHash::default();
+        hasher.update(&input);
+        let mut reader = hasher.finalize_xof();
+        let mut output = [Fp::zero(); 17];
+        for chunk in output.chunks_mut(3) {
+            reader.read(chunk);
+        }
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_anemoi_merge() {
+        let input_data = [
+            [AnemoiDigest::default(), AnemoiDigest::default()],
+            [
+                AnemoiDigest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+                AnemoiDigest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+            ],
+            [
+                AnemoiDigest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+                AnemoiDigest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+            ],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(15368800467845622057),
+                Fp::new(1900984373929563649),
+                Fp::new(9706991957637078803),
+                Fp::new(7792220563260990119),
+            ],
+            [
+                Fp::new(10502505667534841443),
+                Fp::new(10516252267659004245),
+                Fp::new(11617350517991962715),
+                Fp::new(832018764904315045),
+            ],
+            [
+                Fp::new(3123479102352932119),
+                Fp::new(17725179671711644549),
+                Fp::new(7963875556446108029),
+                Fp::new(3409470721254362920),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, AnemoiHash::merge(input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_anemoi_merge4() {
+        let digests = [
+            AnemoiDigest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+            AnemoiDigest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+            AnemoiDigest::new([Fp::new(8), Fp::new(9), Fp::new(10), Fp::new(11)]),
+            AnemoiDigest::new([Fp::new(12), Fp::new(13), Fp::new(14), Fp::new(15)]),
+        ];
+
+        let input_data = [
+            [AnemoiDigest::default(); 4],
+            digests,
+            [digests[3], digests[2], digests[1], digests[0]],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(18105598474182827234),
+                Fp::new(15706412646691645710),
+                Fp::new(12936438563116762146),
+                Fp::new(14641668274456902564),
+            ],
+            [
+                Fp::new(14307580712538032730),
+                Fp::new(11171612039346421204),
+                Fp::new(10877718271127732763),
+                Fp::new(1469745520545746813),
+            ],
+            [
+                Fp::new(11542296120271380891),
+                Fp::new(1687243300114470935),
+                Fp::new(3734764543831981484),
+                Fp::new(3593881991116846996),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, AnemoiHash::merge4(input).to_elements());
+        }
+
+        // The feed-forward sums the four digests and the four
+        // quarters of the permuted state.
+        let mut rng = OsRng;
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        for element in state.iter_mut() {
+            *element = Fp::random(&mut rng);
+        }
+        let digests = [
+            AnemoiDigest::new(state[0..4].try_into().unwrap()),
+            AnemoiDigest::new(state[4..8].try_into().unwrap()),
+            AnemoiDigest::new(state[8..12].try_into().unwrap()),
+            AnemoiDigest::new(state[12..16].try_into().unwrap()),
+        ];
+
+        let mut permuted = state;
+        apply_permutation(&mut permuted);
+        let mut expected = [Fp::zero(); DIGEST_SIZE];
+        for (i, e) in expected.iter_mut().enumerate() {
+            for j in 0..4 {
+                *e += state[4 * j + i] + permuted[4 * j + i];
+            }
+        }
+
+        assert_eq!(expected, AnemoiHash::merge4(&digests).to_elements());
+    }
+
+    #[test]
+    fn test_anemoi_mac() {
+        let key = [
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+            Fp::new(8),
+        ];
+        let message = [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+        ];
+
+        let output_data = [
+            [
+                Fp::new(12794128036453740915),
+                Fp::new(11250535161453289825),
+                Fp::new(14074898201169111638),
+                Fp::new(7743542656302036242),
+            ],
+            [
+                Fp::new(6280179647899305285),
+                Fp::new(17918869959296942939),
+                Fp::new(17222333609170840967),
+                Fp::new(15980403370595747368),
+            ],
+            [
+                Fp::new(16746257601858193378),
+                Fp::new(16889282572561842498),
+                Fp::new(6280474258535988670),
+                Fp::new(12400319072947061826),
+            ],
+        ];
+
+        for (len, expected) in [0, 3, 8].into_iter().zip(output_data) {
+            let input = &message[..len];
+            let tag = AnemoiHash::mac(&key, input);
+            assert_eq!(expected, tag.to_elements());
+
+            let mut hasher = AnemoiHash::new_keyed(&key);
+            hasher.update(input);
+            assert_eq!(tag, hasher.finalize());
+
+            assert!(AnemoiHash::verify(&key, input, &tag));
+            assert!(!AnemoiHash::verify(
+                &[Fp::zero(); CAPACITY_WIDTH],
+                input,
+                &tag
+            ));
+            assert!(!AnemoiHash::verify(&key, &message[1..], &tag));
+
+            let mut forged = tag.to_elements();
+            forged[DIGEST_SIZE - 1] += Fp::one();
+            assert!(!AnemoiHash::verify(&key, input, &AnemoiDigest::new(forged)));
+        }
+
+        // Appending a one or a zero to a message invalidates its tag,
+        // wherever the message ends within the rate.
+        for len in 0..message.len() {
+            let tag = AnemoiHash::mac(&key, &message[..len]);
+            let mut extended = message[..=len].to_vec();
+            extended[len] = Fp::one();
+            assert!(!AnemoiHash::verify(&key, &extended, &tag));
+            extended[len] = Fp::zero();
+            assert!(!AnemoiHash::verify(&key, &extended, &tag));
+        }
+    }
+}
@@ -0,0 +1,16 @@
+// Copyright (c) 2021-2022 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::NUM_COLUMNS;
+
+/// Maximum Diffusion Layer matrix for Anemoi, the circulant matrix
+/// of first row [1, 2, 3, 5, 7, 8, 8, 9].
+pub(crate) const MDS: [u32; NUM_COLUMNS * NUM_COLUMNS] = [
+    1, 2, 3, 5, 7, 8, 8, 9, 9, 1, 2, 3, 5, 7, 8, 8, 8, 9, 1, 2, 3, 5, 7, 8, 8, 8, 9, 1, 2, 3, 5, 7,
+    7, 8, 8, 9, 1, 2, 3, 5, 5, 7, 8, 8, 9, 1, 2, 3, 3, 5, 7, 8, 8, 9, 1, 2, 2, 3, 5, 7, 8, 8, 9, 1,
+];
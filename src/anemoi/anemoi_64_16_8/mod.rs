@@ -0,0 +1,881 @@
+// Copyright (c) 2021-2022 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use crate::duplex::Duplex;
+use crate::f64_utils::apply_rescue_inv_sbox;
+use cheetah::fp_arith_utils::reduce_u96;
+use cheetah::Fp;
+
+/// Digest for Anemoi
+mod digest;
+/// Hasher for Anemoi
+mod hasher;
+/// MDS matrix for Anemoi
+mod mds;
+/// Permutation for Anemoi
+mod permutation;
+/// Round constants for Anemoi
+mod round_constants;
+/// S-Box for Anemoi
+mod sbox;
+
+pub use digest::AnemoiDigest;
+pub use hasher::AnemoiHash;
+pub use permutation::AnemoiPermutation;
+
+/// A duplex sponge over the Anemoi permutation, with the rate and capacity of this instance.
+pub type AnemoiDuplex = Duplex<AnemoiPermutation, RATE_WIDTH, CAPACITY_WIDTH>;
+
+// ANEMOI CONSTANTS
+// ================================================================================================
+
+/// Function state is set to 16 field elements or 128 bytes.
+/// 8 elements of the state are reserved for capacity.
+pub const STATE_WIDTH: usize = 16;
+/// 8 elements of the state are reserved for rate.
+pub const RATE_WIDTH: usize = 8;
+/// 8 elements of the state are reserved for capacity.
+pub const CAPACITY_WIDTH: usize = STATE_WIDTH - RATE_WIDTH;
+
+/// The state is divided into two even-length rows.
+pub const NUM_COLUMNS: usize = 8;
+
+/// Four elements (32-bytes) are returned as digest.
+pub const DIGEST_SIZE: usize = 4;
+
+/// The number of rounds is set to 9 to provide 128-bit security level.
+pub const NUM_HASH_ROUNDS: usize = 9;
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+#[inline(always)]
+/// Applies application of the Anemoi S-Box
+/// to the current hash state.
+pub(crate) fn apply_sbox(state: &mut [Fp; STATE_WIDTH]) {
+    let mut x: [Fp; NUM_COLUMNS] = state[..NUM_COLUMNS].try_into().unwrap();
+    let mut y: [Fp; NUM_COLUMNS] = state[NUM_COLUMNS..].try_into().unwrap();
+
+    x.iter_mut()
+        .enumerate()
+        .for_each(|(i, t)| *t -= y[i].square().mul_by_u32(sbox::BETA));
+
+    let mut x_alpha_inv = x;
+    apply_rescue_inv_sbox(&mut x_alpha_inv);
+
+    y.iter_mut()
+        .enumerate()
+        .for_each(|(i, t)| *t -= x_alpha_inv[i]);
+
+    x.iter_mut()
+        .enumerate()
+        .for_each(|(i, t)| *t += y[i].square().mul_by_u32(sbox::BETA) + sbox::DELTA);
+
+    state[..NUM_COLUMNS].copy_from_slice(&x);
+    state[NUM_COLUMNS..].copy_from_slice(&y);
+}
+
+#[inline(always)]
+/// Applies matrix-vector multiplication of the current
+/// hash state with the Anemoi MDS matrix.
+pub(crate) fn apply_mds(state: &mut [Fp; STATE_WIDTH]) {
+    let x: [u128; NUM_COLUMNS] = [
+        state[0].output_unreduced_internal() as u128,
+        state[1].output_unreduced_internal() as u128,
+        state[2].output_unreduced_internal() as u128,
+        state[3].output_unreduced_internal() as u128,
+        state[4].output_unreduced_internal() as u128,
+        state[5].output_unreduced_internal() as u128,
+        state[6].output_unreduced_internal() as u128,
+        state[7].output_unreduced_internal() as u128,
+    ];
+    // The second vector is first permuted
+    let y: [u128; NUM_COLUMNS] = [
+        state[9].output_unreduced_internal() as u128,
+        state[10].output_unreduced_internal() as u128,
+        state[11].output_unreduced_internal() as u128,
+        state[12].output_unreduced_internal() as u128,
+        state[13].output_unreduced_internal() as u128,
+        state[14].output_unreduced_internal() as u128,
+        state[15].output_unreduced_internal() as u128,
+        state[8].output_unreduced_internal() as u128,
+    ];
+
+    // MDS layer over X and Y vectors. The matrix coefficients being
+    // small, the sums can be reduced once at the end.
+
+    let mut mds_x = [0u128; NUM_COLUMNS];
+    let mut mds_y = [0u128; NUM_COLUMNS];
+    for (i, (mx, my)) in mds_x.iter_mut().zip(mds_y.iter_mut()).enumerate() {
+        for (j, (&xj, &yj)) in x.iter().zip(y.iter()).enumerate() {
+            let m = mds::MDS[i * NUM_COLUMNS + j] as u128;
+            *mx += m * xj;
+            *my += m * yj;
+        }
+    }
+
+    // Before applying modular reduction, we perform a final
+    // Pseudo-Hadamard transform on each pair (x_i, y_i).
+
+    for (mx, my) in mds_x.iter_mut().zip(mds_y.iter_mut()) {
+        *my += *mx;
+        *mx += *my;
+    }
+
+    for (s, v) in state.iter_mut().zip(mds_x.iter().chain(mds_y.iter())) {
+        *s = Fp::from_raw_unchecked(reduce_u96(*v));
+    }
+}
+
+// ANEMOI PERMUTATION
+// ================================================================================================
+
+/// Applies Anemoi permutation to the provided state.
+pub(crate) fn apply_permutation(state: &mut [Fp; STATE_WIDTH]) {
+    for i in 0..NUM_HASH_ROUNDS {
+        apply_round(state, i);
+    }
+
+    apply_mds(state)
+}
+
+/// Anemoi round function
+#[inline(always)]
+pub(crate) fn apply_round(state: &mut [Fp; STATE_WIDTH], step: usize) {
+    // determine which round constants to use
+    let c = &round_constants::C[step % NUM_HASH_ROUNDS];
+    let d = &round_constants::D[step % NUM_HASH_ROUNDS];
+
+    for i in 0..NUM_COLUMNS {
+        state[i] += c[i];
+        state[NUM_COLUMNS + i] += d[i];
+    }
+
+    apply_mds(state);
+    apply_sbox(state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply_naive_mds(state: &mut [Fp; STATE_WIDTH]) {
+        let x: [Fp; NUM_COLUMNS] = state[..NUM_COLUMNS].try_into().unwrap();
+        let mut y: [Fp; NUM_COLUMNS] = [Fp::zero(); NUM_COLUMNS];
+        y[0..NUM_COLUMNS - 1].copy_from_slice(&state[NUM_COLUMNS + 1..]);
+        y[NUM_COLUMNS - 1] = state[NUM_COLUMNS];
+
+        let mut result = [Fp::zero(); STATE_WIDTH];
+        for (i, r) in result.iter_mut().enumerate().take(NUM_COLUMNS) {
+            for (j, s) in x.into_iter().enumerate().take(NUM_COLUMNS) {
+                *r += s.mul_by_u32(mds::MDS[i * NUM_COLUMNS + j]);
+            }
+        }
+        for (i, r) in result.iter_mut().enumerate().skip(NUM_COLUMNS) {
+            for (j, s) in y.into_iter().enumerate() {
+                *r += s.mul_by_u32(mds::MDS[(i - NUM_COLUMNS) * NUM_COLUMNS + j]);
+            }
+        }
+
+        // Pseudo-Hadamard transform
+        for i in 0..NUM_COLUMNS {
+            result[i + NUM_COLUMNS] += result[i];
+            result[i] += result[i + NUM_COLUMNS];
+        }
+
+        state.copy_from_slice(&result);
+    }
+
+    #[test]
+    fn test_sbox() {
+        let mut input = [
+            [Fp::zero(); 16],
+            [Fp::one(); 16],
+            [
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+            ],
+            [
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+            ],
+            [
+                Fp::new(6832892905590973747),
+                Fp::new(6924344268699531175),
+                Fp::new(3562373307524659409),
+                Fp::new(807570059310326234),
+                Fp::new(2524308075868763041),
+                Fp::new(14965180156557675220),
+                Fp::new(9340700596233495388),
+                Fp::new(7391799147228763464),
+                Fp::new(558820675835493740),
+                Fp::new(8990981361419012682),
+                Fp::new(7203047632107764916),
+                Fp::new(10572288831337738114),
+                Fp::new(16552987268813818432),
+                Fp::new(7428204422273764033),
+                Fp::new(8948506018764932564),
+                Fp::new(14001527037311019540),
+            ],
+            [
+                Fp::new(12934152458457720432),
+                Fp::new(9595587209680576322),
+                Fp::new(8749715365801273519),
+                Fp::new(16807902724865503104),
+                Fp::new(13392371907946026545),
+                Fp::new(12229493907798194255),
+                Fp::new(1792418990512122454),
+                Fp::new(15019940279813253005),
+                Fp::new(7127254080567022704),
+                Fp::new(15302169008804156353),
+                Fp::new(1987247709820584965),
+                Fp::new(1070912147764872355),
+                Fp::new(4324270824227072343),
+                Fp::new(1587432852012208902),
+                Fp::new(16664640571391963166),
+                Fp::new(9533482748729369513),
+            ],
+            [
+                Fp::new(3835379161812206185),
+                Fp::new(14912213319923242044),
+                Fp::new(11191944467653535630),
+                Fp::new(9956000196908992518),
+                Fp::new(691104106490654026),
+                Fp::new(12946289458228577535),
+                Fp::new(2733951133769820796),
+                Fp::new(15869965370362164733),
+                Fp::new(8416569940390631158),
+                Fp::new(10779656536362575962),
+                Fp::new(8211326876384662242),
+                Fp::new(10280740617820606962),
+                Fp::new(15772696182621502006),
+                Fp::new(7860525793154801470),
+                Fp::new(2920115043558081817),
+                Fp::new(11092246493995260758),
+            ],
+            [
+                Fp::new(9810125902040622327),
+                Fp::new(5788341669895059486),
+                Fp::new(6586050369648840029),
+                Fp::new(11877977922276110000),
+                Fp::new(9124348236573648827),
+                Fp::new(11510504886494909699),
+                Fp::new(5696194153761280474),
+                Fp::new(6562227820069707928),
+                Fp::new(5245414462472205554),
+                Fp::new(12120547245082912447),
+                Fp::new(14907220538509796004),
+                Fp::new(15803731640124536709),
+                Fp::new(16843075065055942783),
+                Fp::new(10052292966988910048),
+                Fp::new(6349414157947697418),
+                Fp::new(16747876345009099988),
+            ],
+            [
+                Fp::new(5454825378999496797),
+                Fp::new(4054434075037794636),
+                Fp::new(620799002221201145),
+                Fp::new(4699729216271552639),
+                Fp::new(5503643735495162321),
+                Fp::new(13012256845959905059),
+                Fp::new(7690809043819379325),
+                Fp::new(12127640785862344638),
+                Fp::new(2144659549791030748),
+                Fp::new(17202397520836878694),
+                Fp::new(15718634318186519085),
+                Fp::new(472851890314377741),
+                Fp::new(17950047277345780923),
+                Fp::new(2963939329190637655),
+                Fp::new(2148733888498744255),
+                Fp::new(14691317102135098084),
+            ],
+            [
+                Fp::new(7018114055439715176),
+                Fp::new(15428795681560674181),
+                Fp::new(10498517561133459734),
+                Fp::new(16696749652141405630),
+                Fp::new(2564805847118115840),
+                Fp::new(10403913734874226879),
+                Fp::new(7510683327602396582),
+                Fp::new(1478313034815342391),
+                Fp::new(1290819579725513214),
+                Fp::new(13223438298206905719),
+                Fp::new(627800133183553019),
+                Fp::new(12234117452854940717),
+                Fp::new(14098478042204937760),
+                Fp::new(9726673714339946214),
+                Fp::new(2834459949100413820),
+                Fp::new(17274673886084295249),
+            ],
+        ];
+
+        let output = [
+            [
+                Fp::new(2635249152773512046),
+                Fp::new(2635249152773512046),
+                Fp::new(2635249152773512046),
+                Fp::new(2635249152773512046),
+                Fp::new(2635249152773512046),
+                Fp::new(2635249152773512046),
+                Fp::new(2635249152773512046),
+                Fp::new(2635249152773512046),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+            ],
+            [
+                Fp::new(17136669903572771321),
+                Fp::new(17136669903572771321),
+                Fp::new(17136669903572771321),
+                Fp::new(17136669903572771321),
+                Fp::new(17136669903572771321),
+                Fp::new(17136669903572771321),
+                Fp::new(17136669903572771321),
+                Fp::new(17136669903572771321),
+                Fp::new(9739452640566982996),
+                Fp::new(9739452640566982996),
+                Fp::new(9739452640566982996),
+                Fp::new(9739452640566982996),
+                Fp::new(9739452640566982996),
+                Fp::new(9739452640566982996),
+                Fp::new(9739452640566982996),
+                Fp::new(9739452640566982996),
+            ],
+            [
+                Fp::new(6928912281476859504),
+                Fp::new(6928912281476859504),
+                Fp::new(6928912281476859504),
+                Fp::new(6928912281476859504),
+                Fp::new(6928912281476859504),
+                Fp::new(6928912281476859504),
+                Fp::new(6928912281476859504),
+                Fp::new(6928912281476859504),
+                Fp::new(5829874566404923654),
+                Fp::new(5829874566404923654),
+                Fp::new(5829874566404923654),
+                Fp::new(5829874566404923654),
+                Fp::new(5829874566404923654),
+                Fp::new(5829874566404923654),
+                Fp::new(5829874566404923654),
+                Fp::new(5829874566404923654),
+            ],
+            [
+                Fp::new(2635249152773512054),
+                Fp::new(2635249152773512054),
+                Fp::new(2635249152773512054),
+                Fp::new(2635249152773512054),
+                Fp::new(2635249152773512054),
+                Fp::new(2635249152773512054),
+                Fp::new(2635249152773512054),
+                Fp::new(2635249152773512054),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+            ],
+            [
+                Fp::new(7872602591650428874),
+                Fp::new(8126905672736169983),
+                Fp::new(4111403336515518954),
+                Fp::new(14574781112328335822),
+                Fp::new(15058933883890874178),
+                Fp::new(15403463684852314562),
+                Fp::new(10965320523671588543),
+                Fp::new(16233040327816805875),
+                Fp::new(8901907987651571607),
+                Fp::new(7015365100586363591),
+                Fp::new(15984569590708383292),
+                Fp::new(11938868478320899048),
+                Fp::new(1334796654108531765),
+                Fp::new(14616097280073264247),
+                Fp::new(763648244474137477),
+                Fp::new(16679612033635158207),
+            ],
+            [
+                Fp::new(13715257059222260976),
+                Fp::new(17772323511458427386),
+                Fp::new(11904917511183831776),
+                Fp::new(17523185571028614438),
+                Fp::new(6359032368232509101),
+                Fp::new(12801744213710588228),
+                Fp::new(5119948470816905621),
+                Fp::new(18213952496143317110),
+                Fp::new(9253404668427341254),
+                Fp::new(5273917631991943028),
+                Fp::new(3386913700762842415),
+                Fp::new(3992470476802107974),
+                Fp::new(13602266114800887645),
+                Fp::new(7326762779174510392),
+                Fp::new(9945133584276161141),
+                Fp::new(834362319667285757),
+            ],
+            [
+                Fp::new(4435221002196871752),
+                Fp::new(17970229504811625895),
+                Fp::new(11680645127991439622),
+                Fp::new(13729443902223653926),
+                Fp::new(17327276243591405133),
+                Fp::new(10199902337977640458),
+                Fp::new(7923906725285308559),
+                Fp::new(8073931522226866378),
+                Fp::new(10217338486721758662),
+                Fp::new(632943766191688084),
+                Fp::new(3310163311174140408),
+                Fp::new(372943200215681828),
+                Fp::new(13441959825108657185),
+                Fp::new(10528191892271258560),
+                Fp::new(10534037166436215803),
+                Fp::new(11191672137468304058),
+            ],
+            [
+                Fp::new(12765842898208297610),
+                Fp::new(1747512880036998417),
+                Fp::new(1044685939656678290),
+                Fp::new(12356942871534171640),
+                Fp::new(13887207030470263589),
+                Fp::new(14285267793422375311),
+                Fp::new(5918049708244246406),
+                Fp::new(10623623073354107108),
+                Fp::new(5487751449069670583),
+                Fp::new(331360626565356924),
+                Fp::new(8957303648141654088),
+                Fp::new(7912162102881607467),
+                Fp::new(12673978729734265347),
+                Fp::new(10898295849643127465),
+                Fp::new(12050882490334060547),
+                Fp::new(16445051761181842297),
+            ],
+            [
+                Fp::new(4955659655476490455),
+                Fp::new(11600330352289541814),
+                Fp::new(11576286312638486988),
+                Fp::new(11167166858914115229),
+                Fp::new(9254823517630404352),
+                Fp::new(13986465733342556132),
+                Fp::new(7791029125792135780),
+                Fp::new(6320219104819172072),
+                Fp::new(15316617742371065330),
+                Fp::new(2723479746071183945),
+                Fp::new(10289195067362743726),
+                Fp::new(6619148359879254598),
+                Fp::new(2116671291695735820),
+                Fp::new(15992807330924037578),
+                Fp::new(16267676198016615566),
+                Fp::new(16545939673535175720),
+            ],
+            [
+                Fp::new(15043770026128363319),
+                Fp::new(8457515928615788264),
+                Fp::new(7306369337459695095),
+                Fp::new(429575338606342945),
+                Fp::new(13497731487959361393),
+                Fp::new(7205383420313761258),
+                Fp::new(1464001643029459979),
+                Fp::new(8011676480009453235),
+                Fp::new(8290166332369413012),
+                Fp::new(1975545422798319486),
+                Fp::new(18443206641514258716),
+                Fp::new(12914272635888303011),
+                Fp::new(3933940074694228637),
+                Fp::new(18144706975949814478),
+                Fp::new(8073815731633917541),
+                Fp::new(5529526311721629195),
+            ],
+        ];
+
+        for i in input.iter_mut() {
+            apply_sbox(i);
+        }
+
+        assert_eq!(input, output);
+    }
+
+    #[test]
+    fn test_mds() {
+        let mut input = [
+            [Fp::zero(); 16],
+            [Fp::one(); 16],
+            [
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+            ],
+            [
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::one(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+            ],
+            [
+                Fp::new(6832892905590973747),
+                Fp::new(6924344268699531175),
+                Fp::new(3562373307524659409),
+                Fp::new(807570059310326234),
+                Fp::new(2524308075868763041),
+                Fp::new(14965180156557675220),
+                Fp::new(9340700596233495388),
+                Fp::new(7391799147228763464),
+                Fp::new(558820675835493740),
+                Fp::new(8990981361419012682),
+                Fp::new(7203047632107764916),
+                Fp::new(10572288831337738114),
+                Fp::new(16552987268813818432),
+                Fp::new(7428204422273764033),
+                Fp::new(8948506018764932564),
+                Fp::new(14001527037311019540),
+            ],
+            [
+                Fp::new(12934152458457720432),
+                Fp::new(9595587209680576322),
+                Fp::new(8749715365801273519),
+                Fp::new(16807902724865503104),
+                Fp::new(13392371907946026545),
+                Fp::new(12229493907798194255),
+                Fp::new(1792418990512122454),
+                Fp::new(15019940279813253005),
+                Fp::new(7127254080567022704),
+                Fp::new(15302169008804156353),
+                Fp::new(1987247709820584965),
+                Fp::new(1070912147764872355),
+                Fp::new(4324270824227072343),
+                Fp::new(1587432852012208902),
+                Fp::new(16664640571391963166),
+                Fp::new(9533482748729369513),
+            ],
+            [
+                Fp::new(3835379161812206185),
+                Fp::new(14912213319923242044),
+                Fp::new(11191944467653535630),
+                Fp::new(9956000196908992518),
+                Fp::new(691104106490654026),
+                Fp::new(12946289458228577535),
+                Fp::new(2733951133769820796),
+                Fp::new(15869965370362164733),
+                Fp::new(8416569940390631158),
+                Fp::new(10779656536362575962),
+                Fp::new(8211326876384662242),
+                Fp::new(10280740617820606962),
+                Fp::new(15772696182621502006),
+                Fp::new(7860525793154801470),
+                Fp::new(2920115043558081817),
+                Fp::new(11092246493995260758),
+            ],
+            [
+                Fp::new(9810125902040622327),
+                Fp::new(5788341669895059486),
+                Fp::new(6586050369648840029),
+                Fp::new(11877977922276110000),
+                Fp::new(9124348236573648827),
+                Fp::new(11510504886494909699),
+                Fp::new(5696194153761280474),
+                Fp::new(6562227820069707928),
+                Fp::new(5245414462472205554),
+                Fp::new(12120547245082912447),
+                Fp::new(14907220538509796004),
+                Fp::new(15803731640124536709),
+                Fp::new(16843075065055942783),
+                Fp::new(10052292966988910048),
+                Fp::new(6349414157947697418),
+                Fp::new(16747876345009099988),
+            ],
+            [
+                Fp::new(5454825378999496797),
+                Fp::new(4054434075037794636),
+                Fp::new(620799002221201145),
+                Fp::new(4699729216271552639),
+                Fp::new(5503643735495162321),
+                Fp::new(13012256845959905059),
+                Fp::new(7690809043819379325),
+                Fp::new(12127640785862344638),
+                Fp::new(2144659549791030748),
+                Fp::new(17202397520836878694),
+                Fp::new(15718634318186519085),
+                Fp::new(472851890314377741),
+                Fp::new(17950047277345780923),
+                Fp::new(2963939329190637655),
+                Fp::new(2148733888498744255),
+                Fp::new(14691317102135098084),
+            ],
+            [
+                Fp::new(7018114055439715176),
+                Fp::new(15428795681560674181),
+                Fp::new(10498517561133459734),
+                Fp::new(16696749652141405630),
+                Fp::new(2564805847118115840),
+                Fp::new(10403913734874226879),
+                Fp::new(7510683327602396582),
+                Fp::new(1478313034815342391),
+                Fp::new(1290819579725513214),
+                Fp::new(13223438298206905719),
+                Fp::new(627800133183553019),
+                Fp::new(12234117452854940717),
+                Fp::new(14098478042204937760),
+                Fp::new(9726673714339946214),
+                Fp::new(2834459949100413820),
+                Fp::new(17274673886084295249),
+            ],
+        ];
+
+        let mut input2 = input;
+
+        let output = [
+            [
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+                Fp::zero(),
+            ],
+            [
+                Fp::new(129),
+                Fp::new(129),
+                Fp::new(129),
+                Fp::new(129),
+                Fp::new(129),
+                Fp::new(129),
+                Fp::new(129),
+                Fp::new(129),
+                Fp::new(86),
+                Fp::new(86),
+                Fp::new(86),
+                Fp::new(86),
+                Fp::new(86),
+                Fp::new(86),
+                Fp::new(86),
+                Fp::new(86),
+            ],
+            [
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+            ],
+            [
+                Fp::new(86),
+                Fp::new(86),
+                Fp::new(86),
+                Fp::new(86),
+                Fp::new(86),
+                Fp::new(86),
+                Fp::new(86),
+                Fp::new(86),
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+                Fp::new(43),
+            ],
+            [
+                Fp::new(10481674283543150047),
+                Fp::new(582370684562952913),
+                Fp::new(11973516131463095121),
+                Fp::new(17174083755655692272),
+                Fp::new(11652407068938975919),
+                Fp::new(16920661603714665270),
+                Fp::new(14761429798755301819),
+                Fp::new(11447412195909959598),
+                Fp::new(10026376923005860683),
+                Fp::new(3418127299081265712),
+                Fp::new(14937031929897489820),
+                Fp::new(2558163942338783866),
+                Fp::new(15032192574776878120),
+                Fp::new(5252929480799212619),
+                Fp::new(7492888176473475838),
+                Fp::new(17317631363725458988),
+            ],
+            [
+                Fp::new(4711684703962142045),
+                Fp::new(9624465912604864019),
+                Fp::new(3047995305133165257),
+                Fp::new(10702750742423902946),
+                Fp::new(10628852056556277522),
+                Fp::new(4464088886911507981),
+                Fp::new(846884539309321291),
+                Fp::new(16468559688697041065),
+                Fp::new(811674848767128706),
+                Fp::new(8246522418464443623),
+                Fp::new(16433275304673195394),
+                Fp::new(56447595650568683),
+                Fp::new(1790011499756637059),
+                Fp::new(3266605445356582488),
+                Fp::new(4274119785132695110),
+                Fp::new(7004817185248884125),
+            ],
+            [
+                Fp::new(16331547075714049132),
+                Fp::new(8197403357628077881),
+                Fp::new(3885477318183409510),
+                Fp::new(14443655381353684652),
+                Fp::new(5083736381230920472),
+                Fp::new(8739181158626385016),
+                Fp::new(9197639014983775749),
+                Fp::new(6300965376890762807),
+                Fp::new(13588190529574812693),
+                Fp::new(14092146601128836350),
+                Fp::new(814808300125002841),
+                Fp::new(13073938869163427247),
+                Fp::new(8385312565632251678),
+                Fp::new(12684091386623521459),
+                Fp::new(14447871757728532197),
+                Fp::new(10708562534284111922),
+            ],
+            [
+                Fp::new(17560903721749523345),
+                Fp::new(12040481054888184893),
+                Fp::new(16006446487243026720),
+                Fp::new(591189955611914160),
+                Fp::new(3294658506043047420),
+                Fp::new(4887612352203896503),
+                Fp::new(1167897300659595741),
+                Fp::new(13737162082561745954),
+                Fp::new(6930110719776379533),
+                Fp::new(13827201969812681582),
+                Fp::new(9833000498041848761),
+                Fp::new(9495634738546922556),
+                Fp::new(15616641392018342144),
+                Fp::new(11832535422238020780),
+                Fp::new(12088025141488451952),
+                Fp::new(6703869164440338715),
+            ],
+            [
+                Fp::new(4454527487720227596),
+                Fp::new(11254075425070593156),
+                Fp::new(11889463267298447097),
+                Fp::new(2031961479266031462),
+                Fp::new(17464537912191214244),
+                Fp::new(3020902911665243936),
+                Fp::new(15475740915761229902),
+                Fp::new(2706854670182352507),
+                Fp::new(2719127850039363935),
+                Fp::new(16101949368008430517),
+                Fp::new(2906340275326523394),
+                Fp::new(533793853506168638),
+                Fp::new(5703473039468974914),
+                Fp::new(11852849665794020567),
+                Fp::new(2064532516512604998),
+                Fp::new(9307580440819152340),
+            ],
+            [
+                Fp::new(2540502427041001983),
+                Fp::new(3315031049972009583),
+                Fp::new(1028692354746927356),
+                Fp::new(1579924926477117600),
+                Fp::new(6525194741894867683),
+                Fp::new(1232533368346953593),
+                Fp::new(6696536803426679146),
+                Fp::new(14543652097692128332),
+                Fp::new(7151654570857168784),
+                Fp::new(9667177691758616780),
+                Fp::new(6952209512593556628),
+                Fp::new(13959904005259438285),
+                Fp::new(7581496804605276187),
+                Fp::new(11909897091972085141),
+                Fp::new(1088729892728088923),
+                Fp::new(17855351459418739038),
+            ],
+        ];
+
+        for i in input.iter_mut() {
+            apply_mds(i);
+        }
+        for i in input2.iter_mut() {
+            apply_naive_mds(i);
+        }
+
+        assert_eq!(input, output);
+        assert_eq!(input2, output);
+    }
+}
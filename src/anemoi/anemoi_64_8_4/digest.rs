@@ -12,9 +12,11 @@
 use alloc::vec::Vec;
 
 use super::DIGEST_SIZE;
-use crate::traits::Digest;
+use crate::f64_utils::fp_from_canonical_bytes;
+use crate::traits::{Digest, DigestError};
 
 use cheetah::Fp;
+use subtle::{Choice, ConstantTimeEq};
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 /// A Anemoi Digest for the Anemoi Hash over Fp
@@ -55,6 +57,12 @@ impl Default for AnemoiDigest {
     }
 }
 
+impl ConstantTimeEq for AnemoiDigest {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0[..].ct_eq(&other.0[..])
+    }
+}
+
 impl Digest for AnemoiDigest {
     fn to_bytes(&self) -> [u8; 32] {
         let mut digest = [0u8; 32];
@@ -65,11 +73,22 @@ impl Digest for AnemoiDigest {
 
         digest
     }
+
+    fn from_bytes(bytes: &[u8; 32]) -> Result<Self, DigestError> {
+        let mut digest = [Fp::zero(); DIGEST_SIZE];
+        for (i, (element, chunk)) in digest.iter_mut().zip(bytes.chunks_exact(8)).enumerate() {
+            *element = fp_from_canonical_bytes(chunk.try_into().unwrap())
+                .ok_or(DigestError::NonCanonicalElement(i))?;
+        }
+
+        Ok(Self(digest))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::f64_utils::MODULUS;
     use rand_core::OsRng;
 
     #[test]
@@ -96,4 +115,39 @@ mod tests {
         assert_eq!(digest.as_elements(), &vec![Fp::zero(); DIGEST_SIZE][..]);
         assert_eq!(digest.to_bytes(), [0u8; 32]);
     }
+
+    #[test]
+    fn digest_bytes() {
+        let mut rng = OsRng;
+
+        for _ in 0..100 {
+            let mut array = [Fp::zero(); DIGEST_SIZE];
+            for item in array.iter_mut() {
+                *item = Fp::random(&mut rng);
+            }
+
+            let digest = AnemoiDigest::new(array);
+            assert_eq!(AnemoiDigest::from_bytes(&digest.to_bytes()), Ok(digest));
+        }
+
+        let mut bytes = [0u8; 32];
+        bytes[0..8].copy_from_slice(&(MODULUS - 1).to_le_bytes());
+        assert_eq!(
+            AnemoiDigest::from_bytes(&bytes).unwrap().to_elements()[0],
+            -Fp::one()
+        );
+
+        bytes[8..16].copy_from_slice(&MODULUS.to_le_bytes());
+        assert_eq!(
+            AnemoiDigest::from_bytes(&bytes),
+            Err(DigestError::NonCanonicalElement(1))
+        );
+
+        let mut bytes = [0u8; 32];
+        bytes[24..32].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(
+            AnemoiDigest::from_bytes(&bytes),
+            Err(DigestError::NonCanonicalElement(3))
+        );
+    }
 }
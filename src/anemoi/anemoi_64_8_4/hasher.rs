@@ -8,69 +8,108 @@
 
 //! Hasher trait implementation for Anemoi
 
-use core::convert::TryInto;
-
 use super::digest::AnemoiDigest;
-use super::{apply_permutation, DIGEST_SIZE, NUM_COLUMNS, RATE_WIDTH, STATE_WIDTH};
+use super::{AnemoiPermutation, CAPACITY_WIDTH, DIGEST_SIZE, RATE_WIDTH, STATE_WIDTH};
+use crate::f64_utils::bytes_to_elements;
+use crate::jive::Jive;
+use crate::sponge::{Padding, Sponge, XofReader};
 use crate::traits::Hasher;
 
 #[cfg(not(feature = "std"))]
 use alloc::vec::Vec;
 
 use cheetah::Fp;
+use subtle::ConstantTimeEq;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 /// A Anemoi Hash over Fp
 pub struct AnemoiHash {
-    state: [Fp; STATE_WIDTH],
-    idx: usize,
+    sponge: Sponge<AnemoiPermutation, RATE_WIDTH, CAPACITY_WIDTH>,
 }
 
 impl Default for AnemoiHash {
     fn default() -> Self {
         Self {
-            state: [Fp::zero(); STATE_WIDTH],
-            idx: 0,
+            sponge: Sponge::new(Padding::Sigma),
         }
     }
 }
 
-impl Hasher<Fp> for AnemoiHash {
-    type Digest = AnemoiDigest;
+impl AnemoiHash {
+    /// Returns a new hasher keyed with `key`, to compute a message
+    /// authentication code through `update` and `finalize`.
+    ///
+    /// The key is set as the initial capacity, and the message is
+    /// always padded with a one, even when its length is a multiple
+    /// of the rate, so that no two messages share the same padding.
+    pub fn new_keyed(key: &[Fp; CAPACITY_WIDTH]) -> Self {
+        Self {
+            sponge: Sponge::with_initial_capacity(Padding::AlwaysOne, *key),
+        }
+    }
 
-    fn hash(bytes: &[Fp]) -> Self::Digest {
-        // initialize state to all zeros.
-        let mut state = [Fp::zero(); STATE_WIDTH];
+    /// Absorbs a new sequence of field elements into the hasher state.
+    ///
+    /// This can be called several times on chunks of a message, the
+    /// resulting digest being identical to the one returned by
+    /// `AnemoiHash::hash` on the concatenated input.
+    pub fn update(&mut self, bytes: &[Fp]) {
+        self.sponge.absorb(bytes);
+    }
 
-        let sigma = if bytes.len() % RATE_WIDTH == 0 {
-            Fp::one()
-        } else {
-            Fp::zero()
-        };
-
-        let mut i = 0;
-        for &element in bytes.iter() {
-            state[i] += element;
-            i += 1;
-            if i % RATE_WIDTH == 0 {
-                apply_permutation(&mut state);
-                i = 0;
-            }
-        }
+    /// Pads the absorbed sequence and returns its digest.
+    pub fn finalize(self) -> AnemoiDigest {
+        let mut digest = [Fp::zero(); DIGEST_SIZE];
+        self.finalize_xof().read(&mut digest);
 
-        // If the message length is not a multiple of RATE_WIDTH, we append 1 to the rate cell
-        // next to the one where we previously appended the last message element. This is
-        // guaranted to be in the rate registers (i.e. to not require an extra permutation before
-        // adding this constant) if sigma is equal to zero.
-        if sigma.is_zero().into() {
-            state[i] += Fp::one();
-            apply_permutation(&mut state);
-        }
+        AnemoiDigest::new(digest)
+    }
+
+    /// Pads the absorbed sequence and returns a reader over an arbitrarily
+    /// long output, whose first `DIGEST_SIZE` elements form its digest.
+    pub fn finalize_xof(self) -> XofReader<AnemoiPermutation, RATE_WIDTH, CAPACITY_WIDTH> {
+        self.sponge.into_reader()
+    }
+
+    /// Returns a reader over an arbitrarily long hash output
+    /// of the provided sequence of field elements.
+    pub fn hash_xof(bytes: &[Fp]) -> XofReader<AnemoiPermutation, RATE_WIDTH, CAPACITY_WIDTH> {
+        let mut hasher = Self::default();
+        hasher.update(bytes);
+        hasher.finalize_xof()
+    }
+
+    /// Returns a hash of the provided sequence of bytes.
+    ///
+    /// The bytes are first packed into field elements with
+    /// `f64_utils::bytes_to_elements`.
+    pub fn hash_bytes(bytes: &[u8]) -> AnemoiDigest {
+        Self::hash(&bytes_to_elements(bytes))
+    }
 
-        // We then add sigma to the last capacity register of the capacity.
-        state[STATE_WIDTH - 1] += sigma;
+    /// Returns a message authentication code of the provided
+    /// sequence of field elements under `key`, as computed by a
+    /// hasher returned by `new_keyed`.
+    pub fn mac(key: &[Fp; CAPACITY_WIDTH], bytes: &[Fp]) -> AnemoiDigest {
+        let mut hasher = Self::new_keyed(key);
+        hasher.update(bytes);
+        hasher.finalize()
+    }
 
-        AnemoiDigest::new(state[..DIGEST_SIZE].try_into().unwrap())
+    /// Checks in constant time that `tag` is a valid message
+    /// authentication code of `bytes` under `key`.
+    pub fn verify(key: &[Fp; CAPACITY_WIDTH], bytes: &[Fp], tag: &AnemoiDigest) -> bool {
+        Self::mac(key, bytes).ct_eq(tag).into()
+    }
+}
+
+impl Hasher<Fp> for AnemoiHash {
+    type Digest = AnemoiDigest;
+
+    fn hash(bytes: &[Fp]) -> Self::Digest {
+        let mut hasher = Self::default();
+        hasher.update(bytes);
+        hasher.finalize()
     }
 
     // This merge function uses the compression approach of Anemoi-Jive
@@ -79,16 +118,11 @@ impl Hasher<Fp> for AnemoiHash {
     // size RATE_WIDTH.
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
         let mut state = [Fp::zero(); STATE_WIDTH];
-        let digest1 = values[0].as_elements();
-        let digest2 = values[1].as_elements();
-        state[..RATE_WIDTH].copy_from_slice(digest1);
-        state[RATE_WIDTH..STATE_WIDTH].copy_from_slice(digest2);
-        apply_permutation(&mut state);
+        state[..RATE_WIDTH].copy_from_slice(values[0].as_elements());
+        state[RATE_WIDTH..STATE_WIDTH].copy_from_slice(values[1].as_elements());
 
         let mut result = [Fp::zero(); DIGEST_SIZE];
-        for (i, r) in result.iter_mut().enumerate() {
-            *r = digest1[i] + digest2[i] + state[i] + state[i + NUM_COLUMNS];
-        }
+        Jive::<AnemoiPermutation, 2>::compress(&state, &mut result);
 
         AnemoiDigest::new(result)
     }
@@ -97,6 +131,7 @@ impl Hasher<Fp> for AnemoiHash {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand_core::OsRng;
 
     #[test]
     fn test_anemoi_hash() {
@@ -223,4 +258,185 @@ mod tests {
             assert_eq!(expected, AnemoiHash::hash(input).to_elements());
         }
     }
+
+    #[test]
+    fn test_anemoi_hash_update() {
+        let mut rng = OsRng;
+        let mut input = [Fp::zero(); 3 * RATE_WIDTH + 1];
+        for element in input.iter_mut() {
+            *element = Fp::random(&mut rng);
+        }
+
+        for len in 0..=input.len() {
+            let expected = AnemoiHash::hash(&input[..len]);
+
+            for chunk_size in 1..=RATE_WIDTH + 1 {
+                let mut hasher = AnemoiHash::default();
+                for chunk in input[..len].chunks(chunk_size) {
+                    hasher.update(chunk);
+                }
+
+                assert_eq!(expected, hasher.finalize());
+            }
+        }
+    }
+
+    #[test]
+    fn test_anemoi_hash_bytes() {
+        let inputs: [&[u8]; 5] = [b"", b"\0", b"\0\0", b"0123456", b"01234567"];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let digest = AnemoiHash::hash_bytes(input);
+            assert_eq!(digest, AnemoiHash::hash(&bytes_to_elements(input)));
+
+            for other in inputs.iter().skip(i + 1) {
+                assert_ne!(digest, AnemoiHash::hash_bytes(other));
+            }
+        }
+    }
+
+    #[test]
+    fn test_anemoi_hash_xof() {
+        let input = [Fp::zero(), Fp::one(), Fp::new(2)];
+
+        // Output spanning more than two rate blocks, to cover squeezing
+        // beyond the first permutation.
+        let expected = [
+            Fp::new(8967890714416841621),
+            Fp::new(13703999274251368521),
+            Fp::new(14345596218159515729),
+            Fp::new(2249520363038399658),
+            Fp::new(18385591171177887846),
+            Fp::new(12023164174299126462),
+            Fp::new(15717895684287754348),
+            Fp::new(11293131660483752561),
+            Fp::new(4430439928561389556),
+        ];
+
+        let mut output = [Fp::zero(); 9];
+        AnemoiHash::hash_xof(&input).read(&mut output);
+        assert_eq!(output, expected);
+        assert_eq!(
+            output[..DIGEST_SIZE],
+            AnemoiHash::hash(&input).to_elements()
+        );
+
+        let mut hasher = AnemoiHash::default();
+        hasher.update(&input);
+        let mut reader = hasher.finalize_xof();
+        let mut output = [Fp::zero(); 9];
+        for chunk in output.chunks_mut(3) {
+            reader.read(chunk);
+        }
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_anemoi_merge() {
+        let input_data = [
+            [AnemoiDigest::default(), AnemoiDigest::default()],
+            [
+                AnemoiDigest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+                AnemoiDigest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+            ],
+            [
+                AnemoiDigest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+                AnemoiDigest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+            ],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(15247758756114382024),
+                Fp::new(9717937980809258838),
+                Fp::new(10329448772894401836),
+                Fp::new(7951558283109177533),
+            ],
+            [
+                Fp::new(6632344298631683338),
+                Fp::new(4581126218370986982),
+                Fp::new(15417755075723897623),
+                Fp::new(2205908887360559084),
+            ],
+            [
+                Fp::new(7090932930814176306),
+                Fp::new(6954831441717324340),
+                Fp::new(9001195242862073986),
+                Fp::new(10703733515261247824),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, AnemoiHash::merge(input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_anemoi_mac() {
+        let key = [Fp::new(1), Fp::new(2), Fp::new(3), Fp::new(4)];
+        let message = [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+        ];
+
+        let output_data = [
+            [
+                Fp::new(14832535828132862232),
+                Fp::new(14079122246970676337),
+                Fp::new(18431268628670299351),
+                Fp::new(5075938282539483481),
+            ],
+            [
+                Fp::new(13058806848852218265),
+                Fp::new(10609191563487688729),
+                Fp::new(12583411069620683329),
+                Fp::new(14402407652891872967),
+            ],
+            [
+                Fp::new(10761185247334614196),
+                Fp::new(11410705976133259481),
+                Fp::new(12233522895893791240),
+                Fp::new(11305369019250166494),
+            ],
+        ];
+
+        for (len, expected) in [0, 3, 8].into_iter().zip(output_data) {
+            let input = &message[..len];
+            let tag = AnemoiHash::mac(&key, input);
+            assert_eq!(expected, tag.to_elements());
+
+            let mut hasher = AnemoiHash::new_keyed(&key);
+            hasher.update(input);
+            assert_eq!(tag, hasher.finalize());
+
+            assert!(AnemoiHash::verify(&key, input, &tag));
+            assert!(!AnemoiHash::verify(
+                &[Fp::zero(); CAPACITY_WIDTH],
+                input,
+                &tag
+            ));
+            assert!(!AnemoiHash::verify(&key, &message[1..], &tag));
+
+            let mut forged = tag.to_elements();
+            forged[DIGEST_SIZE - 1] += Fp::one();
+            assert!(!AnemoiHash::verify(&key, input, &AnemoiDigest::new(forged)));
+        }
+
+        // Appending a one or a zero to a message invalidates its tag,
+        // wherever the message ends within the rate.
+        for len in 0..message.len() {
+            let tag = AnemoiHash::mac(&key, &message[..len]);
+            let mut extended = message[..=len].to_vec();
+            extended[len] = Fp::one();
+            assert!(!AnemoiHash::verify(&key, &extended, &tag));
+            extended[len] = Fp::zero();
+            assert!(!AnemoiHash::verify(&key, &extended, &tag));
+        }
+    }
 }
@@ -6,6 +6,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use crate::duplex::Duplex;
 use crate::f64_utils::apply_rescue_inv_sbox;
 use cheetah::fp_arith_utils::reduce_u96;
 use cheetah::Fp;
@@ -16,6 +17,8 @@ mod digest;
 mod hasher;
 /// MDS matrix for Anemoi
 mod mds;
+/// Permutation for Anemoi
+mod permutation;
 /// Round constants for Anemoi
 mod round_constants;
 /// S-Box for Anemoi
@@ -23,6 +26,10 @@ mod sbox;
 
 pub use digest::AnemoiDigest;
 pub use hasher::AnemoiHash;
+pub use permutation::AnemoiPermutation;
+
+/// A duplex sponge over the Anemoi permutation, with the rate and capacity of this instance.
+pub type AnemoiDuplex = Duplex<AnemoiPermutation, RATE_WIDTH, CAPACITY_WIDTH>;
 
 // ANEMOI CONSTANTS
 // ================================================================================================
@@ -32,6 +39,8 @@ pub use hasher::AnemoiHash;
 pub const STATE_WIDTH: usize = 8;
 /// 4 elements of the state are reserved for rate.
 pub const RATE_WIDTH: usize = 4;
+/// 4 elements of the state are reserved for capacity.
+pub const CAPACITY_WIDTH: usize = STATE_WIDTH - RATE_WIDTH;
 
 /// The state is divided into two even-length rows.
 pub const NUM_COLUMNS: usize = 4;
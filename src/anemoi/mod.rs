@@ -6,6 +6,26 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+/// The Anemoi hash function over Fp's small
+/// primefield with state width 16 and rate 8.
+///
+/// The test vectors of this instance were generated from a Python port of
+/// the [Sagemath reference](https://github.com/anemoi/anemoi-hash/), which
+/// reproduces the vectors of `anemoi_64_8_4`. They are not outputs of the
+/// Sagemath code itself.
+#[cfg(feature = "f64")]
+pub mod anemoi_64_16_8;
+
+/// The Anemoi hash function over Fp's small
+/// primefield with state width 12 and rate 8.
+///
+/// The test vectors of this instance were generated from a Python port of
+/// the [Sagemath reference](https://github.com/anemoi/anemoi-hash/), which
+/// reproduces the vectors of `anemoi_64_8_4`. They are not outputs of the
+/// Sagemath code itself.
+#[cfg(feature = "f64")]
+pub mod anemoi_64_12_8;
+
 /// The Anemoi hash function over Fp's small
 /// primefield with state width 8 and rate 4.
 #[cfg(feature = "f64")]
@@ -0,0 +1,220 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A `Duplex<P, RATE, CAPACITY>` keeps a single running state of the
+//! permutation `P`, on which absorbing and squeezing can be interleaved.
+//!
+//! Absorbed elements are added to the rate, and the permutation is only
+//! applied when the rate is exhausted or when switching from absorbing
+//! to squeezing. In the latter case, 1 is first added to the rate element
+//! following the last absorbed one, so that absorbing trailing zeros
+//! always yields a different output. Absorbing after squeezing starts a
+//! new absorbing phase from the beginning of the rate.
+
+use crate::traits::Permutation;
+
+use cheetah::Fp;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// A duplex sponge over the permutation `P`, with `RATE`
+/// elements of rate and `CAPACITY` elements of capacity.
+pub struct Duplex<P: Permutation<Fp>, const RATE: usize, const CAPACITY: usize> {
+    state: P::State,
+    idx: usize,
+    squeezing: bool,
+}
+
+impl<P: Permutation<Fp>, const RATE: usize, const CAPACITY: usize> Default
+    for Duplex<P, RATE, CAPACITY>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P: Permutation<Fp>, const RATE: usize, const CAPACITY: usize> Duplex<P, RATE, CAPACITY> {
+    /// Returns a new duplex sponge with an all-zero state.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `RATE` is zero or if `RATE + CAPACITY` differs from
+    /// the width of the permutation.
+    pub fn new() -> Self {
+        assert!(RATE > 0, "duplex rate must be non-zero");
+        assert_eq!(
+            RATE + CAPACITY,
+            P::WIDTH,
+            "duplex rate and capacity must match the permutation width"
+        );
+
+        Self {
+            state: P::State::default(),
+            idx: 0,
+            squeezing: false,
+        }
+    }
+
+    /// Returns whether the duplex sponge is currently in a squeezing phase.
+    pub fn is_squeezing(&self) -> bool {
+        self.squeezing
+    }
+
+    /// Absorbs a sequence of field elements into the running state.
+    ///
+    /// If called after squeezing, this starts a new absorbing phase.
+    pub fn absorb(&mut self, input: &[Fp]) {
+        if self.squeezing {
+            self.squeezing = false;
+            self.idx = 0;
+        }
+
+        for &element in input.iter() {
+            if self.idx == RATE {
+                P::permute(&mut self.state);
+                self.idx = 0;
+            }
+            self.state.as_mut()[self.idx] += element;
+            self.idx += 1;
+        }
+    }
+
+    /// Fills `output` with elements squeezed from the running state.
+    ///
+    /// If called after absorbing, the current absorbing phase is
+    /// padded and the permutation applied beforehand.
+    pub fn squeeze(&mut self, output: &mut [Fp]) {
+        if !self.squeezing {
+            if self.idx == RATE {
+                P::permute(&mut self.state);
+                self.idx = 0;
+            }
+            self.state.as_mut()[self.idx] += Fp::one();
+            P::permute(&mut self.state);
+
+            self.squeezing = true;
+            self.idx = 0;
+        }
+
+        for element in output.iter_mut() {
+            if self.idx == RATE {
+                P::permute(&mut self.state);
+                self.idx = 0;
+            }
+            *element = self.state.as_ref()[self.idx];
+            self.idx += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{anemoi_64_12_8, anemoi_64_16_8, anemoi_64_8_4};
+    use crate::{griffin_64_12_8, griffin_64_8_4};
+    use crate::{poseidon_64_12_8, poseidon_64_8_4, rescue_64_12_8, rescue_64_8_4};
+    use rand_core::OsRng;
+
+    fn check_duplex<P: Permutation<Fp>, const RATE: usize, const CAPACITY: usize>() {
+        let mut rng = OsRng;
+        let mut input = [Fp::zero(); 20];
+        for element in input.iter_mut() {
+            *element = Fp::random(&mut rng);
+        }
+
+        // Interleaved phases, with all absorbing and squeezing done at once.
+        let mut duplex = Duplex::<P, RATE, CAPACITY>::new();
+        let mut expected = [Fp::zero(); 30];
+        duplex.absorb(&input[..7]);
+        duplex.squeeze(&mut expected[..11]);
+        duplex.absorb(&input[7..]);
+        duplex.squeeze(&mut expected[11..]);
+
+        // Splitting a phase into several calls does not change the output.
+        for chunk_size in 1..=RATE + 1 {
+            let mut duplex = Duplex::<P, RATE, CAPACITY>::new();
+            let mut output = [Fp::zero(); 30];
+            for chunk in input[..7].chunks(chunk_size) {
+                duplex.absorb(chunk);
+            }
+            assert!(!duplex.is_squeezing());
+            for chunk in output[..11].chunks_mut(chunk_size) {
+                duplex.squeeze(chunk);
+            }
+            assert!(duplex.is_squeezing());
+            for chunk in input[7..].chunks(chunk_size) {
+                duplex.absorb(chunk);
+            }
+            for chunk in output[11..].chunks_mut(chunk_size) {
+                duplex.squeeze(chunk);
+            }
+
+            assert_eq!(output, expected);
+        }
+
+        // Absorbing in between starts a new phase, even with no elements.
+        let mut duplex = Duplex::<P, RATE, CAPACITY>::new();
+        let mut output = [Fp::zero(); 30];
+        duplex.absorb(&input[..7]);
+        duplex.squeeze(&mut output[..11]);
+        duplex.absorb(&[]);
+        duplex.squeeze(&mut output[11..]);
+        assert_eq!(output[..11], expected[..11]);
+        assert_ne!(output[11..], expected[11..]);
+
+        // Trailing zeros are not ignored.
+        let mut output1 = [Fp::zero(); 4];
+        let mut duplex = Duplex::<P, RATE, CAPACITY>::new();
+        duplex.absorb(&input[..RATE - 1]);
+        duplex.squeeze(&mut output1);
+
+        let mut output2 = [Fp::zero(); 4];
+        let mut duplex = Duplex::<P, RATE, CAPACITY>::new();
+        duplex.absorb(&input[..RATE - 1]);
+        duplex.absorb(&[Fp::zero()]);
+        duplex.squeeze(&mut output2);
+        assert_ne!(output1, output2);
+    }
+
+    #[test]
+    fn test_duplex() {
+        check_duplex::<anemoi_64_8_4::AnemoiPermutation, 4, 4>();
+        check_duplex::<anemoi_64_12_8::AnemoiPermutation, 8, 4>();
+        check_duplex::<anemoi_64_16_8::AnemoiPermutation, 8, 8>();
+        check_duplex::<griffin_64_8_4::GriffinPermutation, 4, 4>();
+        check_duplex::<griffin_64_12_8::GriffinPermutation, 8, 4>();
+        check_duplex::<rescue_64_8_4::RescuePermutation, 4, 4>();
+        check_duplex::<rescue_64_12_8::RescuePermutation, 8, 4>();
+        check_duplex::<poseidon_64_8_4::PoseidonPermutation, 4, 4>();
+        check_duplex::<poseidon_64_12_8::PoseidonPermutation, 8, 4>();
+    }
+
+    #[test]
+    fn test_duplex_permutation_calls() {
+        let mut rng = OsRng;
+        let mut input = [Fp::zero(); 4];
+        for element in input.iter_mut() {
+            *element = Fp::random(&mut rng);
+        }
+
+        // Absorbing a full rate defers the permutation
+        // until the next element is absorbed or squeezed.
+        let mut duplex = anemoi_64_8_4::AnemoiDuplex::new();
+        duplex.absorb(&input);
+        let mut output = [Fp::zero(); 5];
+        duplex.squeeze(&mut output);
+
+        let mut state = [Fp::zero(); 8];
+        state[..4].copy_from_slice(&input);
+        anemoi_64_8_4::AnemoiPermutation::permute(&mut state);
+        state[0] += Fp::one();
+        anemoi_64_8_4::AnemoiPermutation::permute(&mut state);
+        assert_eq!(output[..4], state[..4]);
+        anemoi_64_8_4::AnemoiPermutation::permute(&mut state);
+        assert_eq!(output[4], state[0]);
+    }
+}
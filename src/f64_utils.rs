@@ -1,5 +1,101 @@
+//! Byte strings are packed into field elements as follows: the input is
+//! split into chunks of [`BYTES_PER_ELEMENT`] bytes, the last one being
+//! padded with zeros, and each chunk is interpreted as a little-endian
+//! integer lower than 2**56, hence a canonical field element. A final
+//! element holding the length in bytes of the input is then appended.
+//! As Rust slices never exceed `isize::MAX` bytes, this length is always
+//! lower than p, which makes the encoding injective.
+//!
+//! [`BYTES_PER_ELEMENT`]: crate::f64_utils::BYTES_PER_ELEMENT
+
+use crate::traits::Digest;
+
 use cheetah::Fp;
 
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Number of bytes packed into each field element.
+pub const BYTES_PER_ELEMENT: usize = 7;
+
+/// The Goldilocks field modulus p = 2**64 - 2**32 + 1.
+pub(crate) const MODULUS: u64 = 0xffff_ffff_0000_0001;
+
+/// Returns the field element canonically encoded in little-endian
+/// by `bytes`, or `None` if the encoded value is not lower than p.
+pub(crate) fn fp_from_canonical_bytes(bytes: &[u8; 8]) -> Option<Fp> {
+    let value = u64::from_le_bytes(*bytes);
+    if value < MODULUS {
+        Some(Fp::new(value))
+    } else {
+        None
+    }
+}
+
+/// Returns the four field elements of a digest over Fp.
+pub(crate) fn digest_to_elements<D: Digest>(digest: &D) -> [Fp; 4] {
+    let bytes = digest.to_bytes();
+    let mut elements = [Fp::zero(); 4];
+    for (element, chunk) in elements.iter_mut().zip(bytes.chunks_exact(8)) {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(chunk);
+        // Digests always hold canonically encoded elements.
+        *element = Fp::new(u64::from_le_bytes(buf));
+    }
+
+    elements
+}
+
+/// Packs a sequence of bytes into field elements, appending
+/// the length of the sequence as a final element.
+pub fn bytes_to_elements(bytes: &[u8]) -> Vec<Fp> {
+    let num_elements = bytes.len().div_ceil(BYTES_PER_ELEMENT) + 1;
+    let mut elements = Vec::with_capacity(num_elements);
+
+    for chunk in bytes.chunks(BYTES_PER_ELEMENT) {
+        let mut buf = [0u8; 8];
+        buf[..chunk.len()].copy_from_slice(chunk);
+        elements.push(Fp::new(u64::from_le_bytes(buf)));
+    }
+    elements.push(Fp::new(bytes.len() as u64));
+
+    elements
+}
+
+/// Unpacks a sequence of bytes from field elements produced by
+/// `bytes_to_elements`.
+///
+/// Returns `None` if `elements` is not a valid encoding.
+pub fn elements_to_bytes(elements: &[Fp]) -> Option<Vec<u8>> {
+    let (length, limbs) = elements.split_last()?;
+    let length = u64::from_le_bytes(length.to_bytes());
+    if length > isize::MAX as u64 {
+        return None;
+    }
+
+    let length = length as usize;
+    if limbs.len() != length.div_ceil(BYTES_PER_ELEMENT) {
+        return None;
+    }
+
+    let mut bytes = Vec::with_capacity(limbs.len() * BYTES_PER_ELEMENT);
+    for limb in limbs {
+        let buf = limb.to_bytes();
+        if buf[BYTES_PER_ELEMENT..].iter().any(|&b| b != 0) {
+            return None;
+        }
+        bytes.extend_from_slice(&buf[..BYTES_PER_ELEMENT]);
+    }
+
+    // The padding bytes of the last limb must be zero.
+    if bytes[length..].iter().any(|&b| b != 0) {
+        return None;
+    }
+    bytes.truncate(length);
+
+    Some(bytes)
+}
+
 #[inline(always)]
 /// Squares each element of `base` M times, then performs
 /// a product term by term with `tail`.
@@ -53,7 +149,7 @@ pub(crate) fn apply_rescue_inv_sbox<const STATE_WIDTH: usize>(state: &mut [Fp; S
 #[cfg(test)]
 mod tests {
     use super::*;
-    use rand_core::OsRng;
+    use rand_core::{OsRng, RngCore};
 
     /// Base power map of the Rescue-Prime S-Box
     const ALPHA: u64 = 7;
@@ -61,6 +157,46 @@ mod tests {
     /// Inverse power map of the Rescue-Prime S-Box
     const INV_ALPHA: u64 = 10540996611094048183;
 
+    #[test]
+    fn test_bytes_to_elements() {
+        assert_eq!(bytes_to_elements(&[]), [Fp::zero()]);
+        assert_eq!(bytes_to_elements(&[0]), [Fp::zero(), Fp::one()]);
+        assert_eq!(bytes_to_elements(&[0, 0]), [Fp::zero(), Fp::new(2)]);
+        assert_eq!(
+            bytes_to_elements(&[1, 2, 3, 4, 5, 6, 7, 8]),
+            [Fp::new(0x07060504030201), Fp::new(8), Fp::new(8)]
+        );
+        assert_eq!(
+            bytes_to_elements(&[0xff; 7]),
+            [Fp::new((1 << 56) - 1), Fp::new(7)]
+        );
+
+        let mut rng = OsRng;
+        let mut bytes = [0u8; 50];
+        rng.fill_bytes(&mut bytes);
+
+        for len in 0..=bytes.len() {
+            let elements = bytes_to_elements(&bytes[..len]);
+            assert_eq!(elements.len(), len.div_ceil(7) + 1);
+            assert_eq!(elements_to_bytes(&elements).unwrap(), &bytes[..len]);
+        }
+    }
+
+    #[test]
+    fn test_elements_to_bytes_invalid() {
+        // Empty encoding
+        assert_eq!(elements_to_bytes(&[]), None);
+        // Wrong number of limbs
+        assert_eq!(elements_to_bytes(&[Fp::one()]), None);
+        assert_eq!(elements_to_bytes(&[Fp::zero(), Fp::zero()]), None);
+        // Limb exceeding 7 bytes
+        assert_eq!(elements_to_bytes(&[Fp::new(1 << 56), Fp::new(7)]), None);
+        // Non-zero padding bytes in the last limb
+        assert_eq!(elements_to_bytes(&[Fp::new(1 << 8), Fp::one()]), None);
+        // Length exceeding any possible slice
+        assert_eq!(elements_to_bytes(&[-Fp::one()]), None);
+    }
+
     #[test]
     fn test_square_assign_multi_and_multiply() {
         let mut state = [Fp::zero(); 10];
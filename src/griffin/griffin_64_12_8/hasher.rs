@@ -11,55 +11,137 @@
 use core::convert::TryInto;
 
 use super::digest::GriffinDigest;
-use super::{apply_permutation, DIGEST_SIZE, RATE_WIDTH, STATE_WIDTH};
+use super::{
+    apply_permutation, GriffinPermutation, CAPACITY_WIDTH, DIGEST_SIZE, RATE_WIDTH, STATE_WIDTH,
+};
+use crate::f64_utils::bytes_to_elements;
+use crate::sponge::{Padding, Sponge, XofReader};
 use crate::traits::Hasher;
 
 use cheetah::Fp;
+use subtle::ConstantTimeEq;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 /// A Griffin Hash over Fp
 pub struct GriffinHash {
-    state: [Fp; STATE_WIDTH],
-    idx: usize,
+    sponge: Sponge<GriffinPermutation, RATE_WIDTH, CAPACITY_WIDTH>,
+    // The capacity flag depends on the total input length, which is
+    // unknown until finalization. We hence absorb the input in a second
+    // sponge initialized with this flag set.
+    flagged_sponge: Sponge<GriffinPermutation, RATE_WIDTH, CAPACITY_WIDTH>,
 }
 
 impl Default for GriffinHash {
     fn default() -> Self {
         Self {
-            state: [Fp::zero(); STATE_WIDTH],
-            idx: 0,
+            sponge: init_sponge(false),
+            flagged_sponge: init_sponge(true),
         }
     }
 }
 
-impl Hasher<Fp> for GriffinHash {
-    type Digest = GriffinDigest;
+/// Returns a sponge whose first capacity element
+/// is set to 1 if `flag` is set, and 0 otherwise.
+fn init_sponge(flag: bool) -> Sponge<GriffinPermutation, RATE_WIDTH, CAPACITY_WIDTH> {
+    let mut capacity = [Fp::zero(); CAPACITY_WIDTH];
+    if flag {
+        capacity[0] = Fp::one();
+    }
 
-    fn hash(bytes: &[Fp]) -> Self::Digest {
+    Sponge::with_initial_capacity(Padding::One, capacity)
+}
+
+impl GriffinHash {
+    /// Returns a new hasher with `key` already absorbed, to compute
+    /// a message authentication code through `update` and `finalize`.
+    pub fn new_keyed(key: &[Fp; CAPACITY_WIDTH]) -> Self {
+        let mut hasher = Self::default();
+        hasher.update(key);
+        hasher
+    }
+
+    /// Absorbs a new sequence of field elements into the hasher state.
+    ///
+    /// This can be called several times on chunks of a message, the
+    /// resulting digest being identical to the one returned by
+    /// `GriffinHash::hash` on the concatenated input.
+    ///
+    /// Because the padding rule of Griffin alters the initial capacity,
+    /// each full block costs two permutation calls in this mode.
+    pub fn update(&mut self, bytes: &[Fp]) {
+        self.sponge.absorb(bytes);
+        self.flagged_sponge.absorb(bytes);
+    }
+
+    /// Pads the absorbed sequence and returns its digest.
+    pub fn finalize(self) -> GriffinDigest {
+        let mut digest = [Fp::zero(); DIGEST_SIZE];
+        self.finalize_xof().read(&mut digest);
+
+        GriffinDigest::new(digest)
+    }
+
+    /// Pads the absorbed sequence and returns a reader over an arbitrarily
+    /// long output, whose first `DIGEST_SIZE` elements form its digest.
+    pub fn finalize_xof(self) -> XofReader<GriffinPermutation, RATE_WIDTH, CAPACITY_WIDTH> {
+        if self.sponge.position() == 0 {
+            self.sponge.into_reader()
+        } else {
+            self.flagged_sponge.into_reader()
+        }
+    }
+
+    /// Returns a reader over an arbitrarily long hash output
+    /// of the provided sequence of field elements.
+    pub fn hash_xof(bytes: &[Fp]) -> XofReader<GriffinPermutation, RATE_WIDTH, CAPACITY_WIDTH> {
         // initialize state to all zeros, except for the first element of the capacity part, which
         // is set to 1 conditionally on the input length. this is done so that adding zero elements
         // at the end of the list always results in a different hash.
-        let mut state = [Fp::zero(); STATE_WIDTH];
-        if bytes.len() % RATE_WIDTH != 0 {
-            state[RATE_WIDTH] = Fp::one();
-        }
+        let mut sponge = init_sponge(bytes.len() % RATE_WIDTH != 0);
+        sponge.absorb(bytes);
 
-        let mut i = 0;
-        for &element in bytes.iter() {
-            state[i] += element;
-            i += 1;
-            if i % RATE_WIDTH == 0 {
-                apply_permutation(&mut state);
-                i = 0;
-            }
-        }
+        sponge.into_reader()
+    }
 
-        if i > 0 {
-            state[i] += Fp::one();
-            apply_permutation(&mut state);
-        }
+    /// Returns a hash of the provided sequence of bytes.
+    ///
+    /// The bytes are first packed into field elements with
+    /// `f64_utils::bytes_to_elements`.
+    pub fn hash_bytes(bytes: &[u8]) -> GriffinDigest {
+        Self::hash(&bytes_to_elements(bytes))
+    }
 
-        GriffinDigest::new(state[..DIGEST_SIZE].try_into().unwrap())
+    /// Returns a message authentication code of the provided
+    /// sequence of field elements under `key`.
+    ///
+    /// The key is absorbed as a prefix of the message, the code being
+    /// hence equal to the hash of the concatenation of `key` and `bytes`.
+    pub fn mac(key: &[Fp; CAPACITY_WIDTH], bytes: &[Fp]) -> GriffinDigest {
+        let mut sponge = init_sponge((CAPACITY_WIDTH + bytes.len()) % RATE_WIDTH != 0);
+        sponge.absorb(key);
+        sponge.absorb(bytes);
+
+        let mut digest = [Fp::zero(); DIGEST_SIZE];
+        sponge.into_reader().read(&mut digest);
+
+        GriffinDigest::new(digest)
+    }
+
+    /// Checks in constant time that `tag` is a valid message
+    /// authentication code of `bytes` under `key`.
+    pub fn verify(key: &[Fp; CAPACITY_WIDTH], bytes: &[Fp], tag: &GriffinDigest) -> bool {
+        Self::mac(key, bytes).ct_eq(tag).into()
+    }
+}
+
+impl Hasher<Fp> for GriffinHash {
+    type Digest = GriffinDigest;
+
+    fn hash(bytes: &[Fp]) -> Self::Digest {
+        let mut digest = [Fp::zero(); DIGEST_SIZE];
+        Self::hash_xof(bytes).read(&mut digest);
+
+        GriffinDigest::new(digest)
     }
 
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
@@ -75,6 +157,7 @@ impl Hasher<Fp> for GriffinHash {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand_core::OsRng;
 
     #[test]
     fn test_griffin_hash() {
@@ -266,4 +349,124 @@ mod tests {
             assert_eq!(expected, GriffinHash::hash(input).to_elements());
         }
     }
+
+    #[test]
+    fn test_griffin_hash_update() {
+        let mut rng = OsRng;
+        let mut input = [Fp::zero(); 3 * RATE_WIDTH + 1];
+        for element in input.iter_mut() {
+            *element = Fp::random(&mut rng);
+        }
+
+        for len in 0..=input.len() {
+            let expected = GriffinHash::hash(&input[..len]);
+
+            for chunk_size in 1..=RATE_WIDTH + 1 {
+                let mut hasher = GriffinHash::default();
+                for chunk in input[..len].chunks(chunk_size) {
+                    hasher.update(chunk);
+                }
+
+                assert_eq!(expected, hasher.finalize());
+            }
+        }
+    }
+
+    #[test]
+    fn test_griffin_hash_bytes() {
+        let inputs: [&[u8]; 5] = [b"", b"\0", b"\0\0", b"0123456", b"01234567"];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let digest = GriffinHash::hash_bytes(input);
+            assert_eq!(digest, GriffinHash::hash(&bytes_to_elements(input)));
+
+            for other in inputs.iter().skip(i + 1) {
+                assert_ne!(digest, GriffinHash::hash_bytes(other));
+            }
+        }
+    }
+
+    #[test]
+    fn test_griffin_hash_xof() {
+        let input = [Fp::zero(), Fp::one(), Fp::new(2)];
+
+        // Output spanning more than two rate blocks, to cover squeezing
+        // beyond the first permutation.
+        let expected = [
+            Fp::new(7503055782173731996),
+            Fp::new(1157682174818214561),
+            Fp::new(4973852742724463871),
+            Fp::new(4280420363717088683),
+            Fp::new(8270504551909462349),
+            Fp::new(3295660352817337771),
+            Fp::new(16286368885287739044),
+            Fp::new(12934974036031980905),
+            Fp::new(18198710712219102722),
+            Fp::new(280735781996057624),
+            Fp::new(17251110529657462641),
+            Fp::new(7786903370506315386),
+            Fp::new(8746570521706219380),
+            Fp::new(222693067814020311),
+            Fp::new(16072527879370903220),
+            Fp::new(10261061562362671109),
+            Fp::new(20740091368309940),
+        ];
+
+        let mut output = [Fp::zero(); 17];
+        GriffinHash::hash_xof(&input).read(&mut output);
+        assert_eq!(output, expected);
+        assert_eq!(
+            output[..DIGEST_SIZE],
+            GriffinHash::hash(&input).to_elements()
+        );
+
+        let mut hasher = GriffinHash::default();
+        hasher.update(&input);
+        let mut reader = hasher.finalize_xof();
+        let mut output = [Fp::zero(); 17];
+        for chunk in output.chunks_mut(3) {
+            reader.read(chunk);
+        }
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_griffin_merge() {
+        let input_data = [
+            [GriffinDigest::default(), GriffinDigest::default()],
+            [
+                GriffinDigest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+                GriffinDigest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+            ],
+            [
+                GriffinDigest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+                GriffinDigest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+            ],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(13452764624003418152),
+                Fp::new(12004385611710940293),
+                Fp::new(2466541256145861705),
+                Fp::new(9251577821251453908),
+            ],
+            [
+                Fp::new(5463825997364126891),
+                Fp::new(7306696331304862668),
+                Fp::new(11669917010806303034),
+                Fp::new(1488942981937645973),
+            ],
+            [
+                Fp::new(6216024996879709515),
+                Fp::new(4063072349759898628),
+                Fp::new(5759838185839042108),
+                Fp::new(5780168656335455738),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, GriffinHash::merge(input).to_elements());
+        }
+    }
 }
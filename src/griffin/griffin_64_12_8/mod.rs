@@ -6,6 +6,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use crate::duplex::Duplex;
 use cheetah::fp_arith_utils::reduce_u96;
 use cheetah::Fp;
 
@@ -17,11 +18,17 @@ mod hasher;
 mod mds;
 /// Non-linear layer for Griffin
 mod non_linear;
+/// Permutation for Griffin
+mod permutation;
 /// Round constants for Griffin
 mod round_constants;
 
 pub use digest::GriffinDigest;
 pub use hasher::GriffinHash;
+pub use permutation::GriffinPermutation;
+
+/// A duplex sponge over the Griffin permutation, with the rate and capacity of this instance.
+pub type GriffinDuplex = Duplex<GriffinPermutation, RATE_WIDTH, CAPACITY_WIDTH>;
 
 // ANEMOI CONSTANTS
 // ================================================================================================
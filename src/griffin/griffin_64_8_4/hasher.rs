@@ -8,81 +8,156 @@
 
 //! Hasher trait implementation for Griffin
 
-use core::convert::TryInto;
-
 use super::digest::GriffinDigest;
-use super::{apply_permutation, DIGEST_SIZE, RATE_WIDTH, STATE_WIDTH};
+use super::{GriffinPermutation, CAPACITY_WIDTH, DIGEST_SIZE, RATE_WIDTH, STATE_WIDTH};
+use crate::f64_utils::bytes_to_elements;
+use crate::jive::Jive;
+use crate::sponge::{Padding, Sponge, XofReader};
 use crate::traits::Hasher;
 
 use cheetah::Fp;
+use subtle::ConstantTimeEq;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 /// A Griffin Hash over Fp
 pub struct GriffinHash {
-    state: [Fp; STATE_WIDTH],
-    idx: usize,
+    sponge: Sponge<GriffinPermutation, RATE_WIDTH, CAPACITY_WIDTH>,
+    // The capacity flag depends on the total input length, which is
+    // unknown until finalization. We hence absorb the input in a second
+    // sponge initialized with this flag set.
+    flagged_sponge: Sponge<GriffinPermutation, RATE_WIDTH, CAPACITY_WIDTH>,
 }
 
 impl Default for GriffinHash {
     fn default() -> Self {
         Self {
-            state: [Fp::zero(); STATE_WIDTH],
-            idx: 0,
+            sponge: init_sponge(false),
+            flagged_sponge: init_sponge(true),
         }
     }
 }
 
-impl Hasher<Fp> for GriffinHash {
-    type Digest = GriffinDigest;
+/// Returns a sponge whose first capacity element
+/// is set to 1 if `flag` is set, and 0 otherwise.
+fn init_sponge(flag: bool) -> Sponge<GriffinPermutation, RATE_WIDTH, CAPACITY_WIDTH> {
+    let mut capacity = [Fp::zero(); CAPACITY_WIDTH];
+    if flag {
+        capacity[0] = Fp::one();
+    }
 
-    fn hash(bytes: &[Fp]) -> Self::Digest {
+    Sponge::with_initial_capacity(Padding::One, capacity)
+}
+
+impl GriffinHash {
+    /// Returns a new hasher with `key` already absorbed, to compute
+    /// a message authentication code through `update` and `finalize`.
+    pub fn new_keyed(key: &[Fp; CAPACITY_WIDTH]) -> Self {
+        let mut hasher = Self::default();
+        hasher.update(key);
+        hasher
+    }
+
+    /// Absorbs a new sequence of field elements into the hasher state.
+    ///
+    /// This can be called several times on chunks of a message, the
+    /// resulting digest being identical to the one returned by
+    /// `GriffinHash::hash` on the concatenated input.
+    ///
+    /// Because the padding rule of Griffin alters the initial capacity,
+    /// each full block costs two permutation calls in this mode.
+    pub fn update(&mut self, bytes: &[Fp]) {
+        self.sponge.absorb(bytes);
+        self.flagged_sponge.absorb(bytes);
+    }
+
+    /// Pads the absorbed sequence and returns its digest.
+    pub fn finalize(self) -> GriffinDigest {
+        let mut digest = [Fp::zero(); DIGEST_SIZE];
+        self.finalize_xof().read(&mut digest);
+
+        GriffinDigest::new(digest)
+    }
+
+    /// Pads the absorbed sequence and returns a reader over an arbitrarily
+    /// long output, whose first `DIGEST_SIZE` elements form its digest.
+    pub fn finalize_xof(self) -> XofReader<GriffinPermutation, RATE_WIDTH, CAPACITY_WIDTH> {
+        if self.sponge.position() == 0 {
+            self.sponge.into_reader()
+        } else {
+            self.flagged_sponge.into_reader()
+        }
+    }
+
+    /// Returns a reader over an arbitrarily long hash output
+    /// of the provided sequence of field elements.
+    pub fn hash_xof(bytes: &[Fp]) -> XofReader<GriffinPermutation, RATE_WIDTH, CAPACITY_WIDTH> {
         // initialize state to all zeros, except for the first element of the capacity part, which
         // is set to 1 conditionally on the input length. this is done so that adding zero elements
         // at the end of the list always results in a different hash.
-        let mut state = [Fp::zero(); STATE_WIDTH];
-        if bytes.len() % RATE_WIDTH != 0 {
-            state[RATE_WIDTH] = Fp::one();
-        }
+        let mut sponge = init_sponge(bytes.len() % RATE_WIDTH != 0);
+        sponge.absorb(bytes);
 
-        let mut i = 0;
-        for &element in bytes.iter() {
-            state[i] += element;
-            i += 1;
-            if i % RATE_WIDTH == 0 {
-                apply_permutation(&mut state);
-                i = 0;
-            }
-        }
+        sponge.into_reader()
+    }
 
-        if i > 0 {
-            state[i] += Fp::one();
-            apply_permutation(&mut state);
-        }
+    /// Returns a hash of the provided sequence of bytes.
+    ///
+    /// The bytes are first packed into field elements with
+    /// `f64_utils::bytes_to_elements`.
+    pub fn hash_bytes(bytes: &[u8]) -> GriffinDigest {
+        Self::hash(&bytes_to_elements(bytes))
+    }
+
+    /// Returns a message authentication code of the provided
+    /// sequence of field elements under `key`.
+    ///
+    /// The key is absorbed as a prefix of the message, the code being
+    /// hence equal to the hash of the concatenation of `key` and `bytes`.
+    pub fn mac(key: &[Fp; CAPACITY_WIDTH], bytes: &[Fp]) -> GriffinDigest {
+        let mut sponge = init_sponge((CAPACITY_WIDTH + bytes.len()) % RATE_WIDTH != 0);
+        sponge.absorb(key);
+        sponge.absorb(bytes);
+
+        let mut digest = [Fp::zero(); DIGEST_SIZE];
+        sponge.into_reader().read(&mut digest);
 
-        GriffinDigest::new(state[..DIGEST_SIZE].try_into().unwrap())
+        GriffinDigest::new(digest)
     }
 
+    /// Checks in constant time that `tag` is a valid message
+    /// authentication code of `bytes` under `key`.
+    pub fn verify(key: &[Fp; CAPACITY_WIDTH], bytes: &[Fp], tag: &GriffinDigest) -> bool {
+        Self::mac(key, bytes).ct_eq(tag).into()
+    }
+}
+
+impl Hasher<Fp> for GriffinHash {
+    type Digest = GriffinDigest;
+
+    fn hash(bytes: &[Fp]) -> Self::Digest {
+        let mut digest = [Fp::zero(); DIGEST_SIZE];
+        Self::hash_xof(bytes).read(&mut digest);
+
+        GriffinDigest::new(digest)
+    }
+
+    // Uses Jive compression to fill the whole state and perform a single permutation
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
         let mut state = [Fp::zero(); STATE_WIDTH];
-        let digest1 = values[0].as_elements();
-        let digest2 = values[1].as_elements();
-        // Uses Jive compression to fill the whole state and perform a single permutation
-        state[..RATE_WIDTH].copy_from_slice(digest1);
-        state[RATE_WIDTH..STATE_WIDTH].copy_from_slice(digest2);
-        apply_permutation(&mut state);
+        state[..RATE_WIDTH].copy_from_slice(values[0].as_elements());
+        state[RATE_WIDTH..STATE_WIDTH].copy_from_slice(values[1].as_elements());
 
         let mut result = [Fp::zero(); DIGEST_SIZE];
-        for (i, r) in result.iter_mut().enumerate() {
-            *r = digest1[i] + digest2[i] + state[i] + state[i + STATE_WIDTH / 2];
-        }
+        Jive::<GriffinPermutation, 2>::compress(&state, &mut result);
 
-        GriffinDigest::new(state[..DIGEST_SIZE].try_into().unwrap())
+        GriffinDigest::new(result)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand_core::OsRng;
 
     #[test]
     fn test_griffin_hash() {
@@ -274,4 +349,150 @@ mod tests {
             assert_eq!(expected, GriffinHash::hash(input).to_elements());
         }
     }
+
+    #[test]
+    fn test_griffin_hash_update() {
+        let mut rng = OsRng;
+        let mut input = [Fp::zero(); 3 * RATE_WIDTH + 1];
+        for element in input.iter_mut() {
+            *element = Fp::random(&mut rng);
+        }
+
+        for len in 0..=input.len() {
+            let expected = GriffinHash::hash(&input[..len]);
+
+            for chunk_size in 1..=RATE_WIDTH + 1 {
+                let mut hasher = GriffinHash::default();
+                for chunk in input[..len].chunks(chunk_size) {
+                    hasher.update(chunk);
+                }
+
+                assert_eq!(expected, hasher.finalize());
+            }
+        }
+    }
+
+    #[test]
+    fn test_griffin_hash_bytes() {
+        let inputs: [&[u8]; 5] = [b"", b"\0", b"\0\0", b"0123456", b"01234567"];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let digest = GriffinHash::hash_bytes(input);
+            assert_eq!(digest, GriffinHash::hash(&bytes_to_elements(input)));
+
+            for other in inputs.iter().skip(i + 1) {
+                assert_ne!(digest, GriffinHash::hash_bytes(other));
+            }
+        }
+    }
+
+    #[test]
+    fn test_griffin_hash_xof() {
+        let input = [Fp::zero(), Fp::one(), Fp::new(2)];
+
+        // Output spanning more than two rate blocks, to cover squeezing
+        // beyond the first permutation.
+        let expected = [
+            Fp::new(6485957317733941246),
+            Fp::new(9571796387471669712),
+            Fp::new(15832534675262834196),
+            Fp::new(13420886242683546482),
+            Fp::new(12172415568128085122),
+            Fp::new(18284957922403995977),
+            Fp::new(1973651073164498882),
+            Fp::new(6786469015211461049),
+            Fp::new(13225938141746141519),
+        ];
+
+        let mut output = [Fp::zero(); 9];
+        GriffinHash::hash_xof(&input).read(&mut output);
+        assert_eq!(output, expected);
+        assert_eq!(
+            output[..DIGEST_SIZE],
+            GriffinHash::hash(&input).to_elements()
+        );
+
+        let mut hasher = GriffinHash::default();
+        hasher.update(&input);
+        let mut reader = hasher.finalize_xof();
+        let mut output = [Fp::zero(); 9];
+        for chunk in output.chunks_mut(3) {
+            reader.read(chunk);
+        }
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_griffin_merge() {
+        let input_data = [
+            [GriffinDigest::default(), GriffinDigest::default()],
+            [
+                GriffinDigest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+                GriffinDigest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+            ],
+            [
+                GriffinDigest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+                GriffinDigest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+            ],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(1739544278262378546),
+                Fp::new(12340531274907558987),
+                Fp::new(2188970182285040194),
+                Fp::new(1793520894614186311),
+            ],
+            [
+                Fp::new(8725326649923004003),
+                Fp::new(3246467461770090951),
+                Fp::new(9777131366298915955),
+                Fp::new(2546611222669615133),
+            ],
+            [
+                Fp::new(8131222477848976015),
+                Fp::new(10411128392295113122),
+                Fp::new(14526776404573851773),
+                Fp::new(656418377895127005),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, GriffinHash::merge(input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_griffin_mac() {
+        let mut rng = OsRng;
+        let mut key = [Fp::zero(); CAPACITY_WIDTH];
+        let mut input = [Fp::zero(); 2 * RATE_WIDTH + 1];
+        for element in key.iter_mut().chain(input.iter_mut()) {
+            *element = Fp::random(&mut rng);
+        }
+
+        for len in 0..=input.len() {
+            let tag = GriffinHash::mac(&key, &input[..len]);
+
+            let mut hasher = GriffinHash::new_keyed(&key);
+            hasher.update(&input[..len]);
+            assert_eq!(tag, hasher.finalize());
+
+            assert!(GriffinHash::verify(&key, &input[..len], &tag));
+            assert!(!GriffinHash::verify(
+                &key,
+                &input[..len],
+                &GriffinHash::hash(&input[..len])
+            ));
+
+            // Appending a one or a zero invalidates the tag
+            if len < input.len() {
+                let mut extended = input[..=len].to_vec();
+                extended[len] = Fp::one();
+                assert!(!GriffinHash::verify(&key, &extended, &tag));
+                extended[len] = Fp::zero();
+                assert!(!GriffinHash::verify(&key, &extended, &tag));
+            }
+        }
+    }
 }
@@ -0,0 +1,146 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! The Jive compression mode, as defined in <https://eprint.iacr.org/2022/840.pdf>.
+//!
+//! `Jive<P, B>` compresses a full state of the permutation `P`, seen as `B`
+//! consecutive chunks of `P::WIDTH / B` elements, into a single chunk by
+//! summing all input chunks and all chunks of the permuted state.
+
+use core::marker::PhantomData;
+
+use crate::traits::Permutation;
+
+use cheetah::Fp;
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+/// The Jive compression mode over the permutation `P`,
+/// compressing `B` chunks of field elements into one.
+pub struct Jive<P: Permutation<Fp>, const B: usize> {
+    _permutation: PhantomData<P>,
+}
+
+impl<P: Permutation<Fp>, const B: usize> Jive<P, B> {
+    /// Number of field elements returned by this compression function.
+    pub const OUTPUT_SIZE: usize = P::WIDTH / B;
+
+    /// Compresses the `B` chunks of `input` into `output`, by computing
+    /// `sum_{i < B} (input_i + P(input)_i)`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `B` does not divide the permutation width, or if the
+    /// length of `output` differs from `OUTPUT_SIZE`.
+    pub fn compress(input: &P::State, output: &mut [Fp]) {
+        assert!(
+            B > 0 && P::WIDTH % B == 0,
+            "Jive chunk count must divide the permutation width"
+        );
+        assert_eq!(output.len(), Self::OUTPUT_SIZE, "invalid Jive output size");
+
+        let mut state = *input;
+        P::permute(&mut state);
+
+        output.iter_mut().for_each(|o| *o = Fp::zero());
+        for (i, (x, s)) in input.as_ref().iter().zip(state.as_ref()).enumerate() {
+            output[i % Self::OUTPUT_SIZE] += *x + *s;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::f64_utils::digest_to_elements;
+    use crate::traits::Hasher;
+    use crate::{anemoi_64_8_4, griffin_64_8_4, poseidon_64_8_4, rescue_64_8_4};
+    use crate::{poseidon_64_12_8, rescue_64_12_8};
+    use rand_core::OsRng;
+
+    // Checks that `H::merge` follows the Jive_2 definition over `P`,
+    // with the two digests filling the whole state.
+    fn check_merge<H: Hasher<Fp>, P: Permutation<Fp>>() {
+        assert_eq!(P::WIDTH, 8);
+        assert_eq!(Jive::<P, 2>::OUTPUT_SIZE, 4);
+
+        let mut rng = OsRng;
+        for _ in 0..20 {
+            let digests = [
+                H::hash(&[Fp::random(&mut rng)]),
+                H::hash(&[Fp::random(&mut rng)]),
+            ];
+
+            let mut state = P::State::default();
+            state.as_mut()[..4].copy_from_slice(&digest_to_elements(&digests[0]));
+            state.as_mut()[4..].copy_from_slice(&digest_to_elements(&digests[1]));
+
+            // Direct evaluation of the Jive definition.
+            let mut permuted = state;
+            P::permute(&mut permuted);
+            let mut expected = [Fp::zero(); 4];
+            for (i, e) in expected.iter_mut().enumerate() {
+                *e = state.as_ref()[i]
+                    + state.as_ref()[i + 4]
+                    + permuted.as_ref()[i]
+                    + permuted.as_ref()[i + 4];
+            }
+
+            let mut output = [Fp::zero(); 4];
+            Jive::<P, 2>::compress(&state, &mut output);
+            assert_eq!(output, expected);
+
+            assert_eq!(digest_to_elements(&H::merge(&digests)), expected);
+        }
+    }
+
+    #[test]
+    fn test_merge_is_jive() {
+        check_merge::<anemoi_64_8_4::AnemoiHash, anemoi_64_8_4::AnemoiPermutation>();
+        check_merge::<griffin_64_8_4::GriffinHash, griffin_64_8_4::GriffinPermutation>();
+        check_merge::<rescue_64_8_4::RescueHash, rescue_64_8_4::RescuePermutation>();
+        check_merge::<poseidon_64_8_4::PoseidonHash, poseidon_64_8_4::PoseidonPermutation>();
+    }
+
+    #[test]
+    fn test_jive_chunks() {
+        let mut rng = OsRng;
+        let mut state = [Fp::zero(); 12];
+        for s in state.iter_mut() {
+            *s = Fp::random(&mut rng);
+        }
+
+        let mut permuted = state;
+        poseidon_64_12_8::PoseidonPermutation::permute(&mut permuted);
+        let mut expected = [Fp::zero(); 3];
+        for (i, e) in expected.iter_mut().enumerate() {
+            for j in 0..4 {
+                *e += state[3 * j + i] + permuted[3 * j + i];
+            }
+        }
+
+        let mut output = [Fp::zero(); 3];
+        Jive::<poseidon_64_12_8::PoseidonPermutation, 4>::compress(&state, &mut output);
+        assert_eq!(output, expected);
+
+        // Jive_1 is the permutation in feed-forward mode.
+        let mut output = [Fp::zero(); 12];
+        Jive::<rescue_64_12_8::RescuePermutation, 1>::compress(&state, &mut output);
+        let mut permuted = state;
+        rescue_64_12_8::RescuePermutation::permute(&mut permuted);
+        for i in 0..12 {
+            assert_eq!(output[i], state[i] + permuted[i]);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_jive_invalid_chunks() {
+        let mut output = [Fp::zero(); 2];
+        Jive::<anemoi_64_8_4::AnemoiPermutation, 3>::compress(&[Fp::zero(); 8], &mut output);
+    }
+}
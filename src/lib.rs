@@ -33,6 +33,8 @@
 #![deny(missing_docs)]
 #![deny(unsafe_code)]
 #![cfg_attr(not(feature = "std"), no_std)]
+// `usize::is_multiple_of` is only available from Rust 1.87 onwards.
+#![allow(clippy::manual_is_multiple_of)]
 
 #[cfg(not(feature = "std"))]
 extern crate alloc;
@@ -43,8 +45,29 @@ extern crate std;
 /// Traits defining a hash function
 pub mod traits;
 
+/// Utility functions over the Goldilocks field
 #[cfg(feature = "f64")]
-mod f64_utils;
+pub mod f64_utils;
+
+/// Generic sponge construction over a permutation
+#[cfg(feature = "f64")]
+pub mod sponge;
+
+/// Generic Jive compression mode over a permutation
+#[cfg(feature = "f64")]
+pub mod jive;
+
+/// Generic duplex sponge construction over a permutation
+#[cfg(feature = "f64")]
+pub mod duplex;
+
+/// Fiat-Shamir transcript over a hasher
+#[cfg(feature = "f64")]
+pub mod transcript;
+
+/// Merkle trees over a hasher
+#[cfg(feature = "f64")]
+pub mod merkle;
 
 mod anemoi;
 pub use anemoi::*;
@@ -0,0 +1,299 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A `BatchMerkleProof<H>` opens several leaves of a `MerkleTree<H>` at
+//! once, by only providing the sibling digests which cannot be recomputed
+//! from the opened leaves.
+//!
+//! Siblings are listed level by level starting from the leaf level, and
+//! by increasing node position within each level.
+
+use core::fmt;
+
+use super::{MerkleError, MerkleTree};
+use crate::traits::Hasher;
+
+use cheetah::Fp;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// A Merkle proof for several leaves of a Merkle tree over the hasher `H`.
+pub struct BatchMerkleProof<H: Hasher<Fp>> {
+    depth: usize,
+    siblings: Vec<H::Digest>,
+}
+
+impl<H: Hasher<Fp>> fmt::Debug for BatchMerkleProof<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchMerkleProof")
+            .field("depth", &self.depth)
+            .field("siblings", &self.siblings)
+            .finish()
+    }
+}
+
+impl<H: Hasher<Fp>> Clone for BatchMerkleProof<H> {
+    fn clone(&self) -> Self {
+        Self {
+            depth: self.depth,
+            siblings: self.siblings.clone(),
+        }
+    }
+}
+
+impl<H: Hasher<Fp>> PartialEq for BatchMerkleProof<H> {
+    fn eq(&self, other: &Self) -> bool {
+        self.depth == other.depth && self.siblings == other.siblings
+    }
+}
+
+impl<H: Hasher<Fp>> Eq for BatchMerkleProof<H> {}
+
+impl<H: Hasher<Fp>> BatchMerkleProof<H> {
+    /// Returns a new batch proof for a tree of the given depth,
+    /// from the provided sibling digests.
+    pub fn new(depth: usize, siblings: Vec<H::Digest>) -> Self {
+        Self { depth, siblings }
+    }
+
+    /// Returns the depth of the tree this proof refers to.
+    pub fn depth(&self) -> usize {
+        self.depth
+    }
+
+    /// Returns the sibling digests of this proof.
+    pub fn siblings(&self) -> &[H::Digest] {
+        &self.siblings
+    }
+
+    /// Checks that this proof authenticates each leaf of `leaves`
+    /// at the corresponding index of `indices` against `root`.
+    ///
+    /// Indices can be provided in any order, but must be distinct.
+    pub fn verify(&self, root: &H::Digest, indices: &[usize], leaves: &[H::Digest]) -> bool {
+        if indices.is_empty() || indices.len() != leaves.len() {
+            return false;
+        }
+        if self.depth >= usize::BITS as usize {
+            return false;
+        }
+
+        let width = 1 << self.depth;
+        let mut nodes = Vec::with_capacity(indices.len());
+        for (&index, leaf) in indices.iter().zip(leaves) {
+            if index >= width {
+                return false;
+            }
+            nodes.push((index + width, *leaf));
+        }
+        nodes.sort_unstable_by_key(|(node, _)| *node);
+        if nodes.windows(2).any(|w| w[0].0 == w[1].0) {
+            return false;
+        }
+
+        let mut siblings = self.siblings.iter();
+        for _ in 0..self.depth {
+            let mut parents = Vec::with_capacity(nodes.len());
+            let mut i = 0;
+            while i < nodes.len() {
+                let (node, digest) = nodes[i];
+                let pair = if node & 1 == 0 && i + 1 < nodes.len() && nodes[i + 1].0 == node + 1 {
+                    i += 1;
+                    [digest, nodes[i].1]
+                } else {
+                    let sibling = match siblings.next() {
+                        Some(sibling) => *sibling,
+                        None => return false,
+                    };
+                    if node & 1 == 0 {
+                        [digest, sibling]
+                    } else {
+                        [sibling, digest]
+                    }
+                };
+
+                parents.push((node >> 1, H::merge(&pair)));
+                i += 1;
+            }
+            nodes = parents;
+        }
+
+        siblings.next().is_none() && nodes[0].1 == *root
+    }
+}
+
+impl<H: Hasher<Fp>> MerkleTree<H> {
+    /// Returns a batch proof for the leaves at the provided indices,
+    /// which can be given in any order.
+    ///
+    /// Fails if `indices` is empty, contains duplicates, or
+    /// if one of them does not refer to one of the tree leaves.
+    pub fn prove_batch(&self, indices: &[usize]) -> Result<BatchMerkleProof<H>, MerkleError> {
+        if indices.is_empty() {
+            return Err(MerkleError::NoIndices);
+        }
+
+        let width = self.width();
+        let mut nodes = Vec::with_capacity(indices.len());
+        for &index in indices {
+            if index >= self.num_leaves {
+                return Err(MerkleError::InvalidIndex(index));
+            }
+            nodes.push(index + width);
+        }
+        nodes.sort_unstable();
+        if let Some(w) = nodes.windows(2).find(|w| w[0] == w[1]) {
+            return Err(MerkleError::DuplicateIndex(w[0] - width));
+        }
+
+        let mut siblings = Vec::new();
+        for _ in 0..self.depth() {
+            let mut parents = Vec::with_capacity(nodes.len());
+            let mut i = 0;
+            while i < nodes.len() {
+                let node = nodes[i];
+                if node & 1 == 0 && i + 1 < nodes.len() && nodes[i + 1] == node + 1 {
+                    i += 1;
+                } else {
+                    siblings.push(self.nodes[node ^ 1]);
+                }
+
+                parents.push(node >> 1);
+                i += 1;
+            }
+            nodes = parents;
+        }
+
+        Ok(BatchMerkleProof::new(self.depth(), siblings))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{anemoi_64_12_8, anemoi_64_16_8, anemoi_64_8_4};
+    use crate::{griffin_64_12_8, griffin_64_8_4};
+    use crate::{plonky2_poseidon_64_12_8, poseidon_64_12_8, poseidon_64_8_4};
+    use crate::{rescue_64_12_8, rescue_64_8_4, rp64_256, rpo_64_12_8};
+    use rand_core::{OsRng, RngCore};
+
+    // Returns the number of sibling digests needed to open `indices`
+    // in a tree of the given width, by collecting all nodes on their
+    // authentication paths.
+    fn expected_num_siblings(width: usize, indices: &[usize]) -> usize {
+        let mut known = Vec::new();
+        for &index in indices {
+            let mut node = index + width;
+            while node > 0 {
+                known.push(node);
+                node >>= 1;
+            }
+        }
+
+        let mut siblings: Vec<usize> = known
+            .iter()
+            .filter(|&&node| node > 1 && !known.contains(&(node ^ 1)))
+            .map(|&node| node ^ 1)
+            .collect();
+        siblings.sort_unstable();
+        siblings.dedup();
+        siblings.len()
+    }
+
+    fn check_batch_proof<H: Hasher<Fp>>() {
+        let mut rng = OsRng;
+        let leaves: Vec<H::Digest> = (0..27).map(|_| H::hash(&[Fp::random(&mut rng)])).collect();
+        let tree = MerkleTree::<H>::new(&leaves).unwrap();
+
+        // Opening two sibling leaves only requires the upper siblings.
+        let proof = tree.prove_batch(&[5, 4]).unwrap();
+        assert_eq!(proof.siblings(), &tree.prove(4).unwrap()[1..]);
+
+        // A single leaf is opened with its authentication path.
+        let proof = tree.prove_batch(&[11]).unwrap();
+        assert_eq!(proof.siblings(), &tree.prove(11).unwrap()[..]);
+
+        for _ in 0..20 {
+            let mut indices = Vec::new();
+            let num_indices = 1 + rng.next_u32() as usize % 10;
+            while indices.len() < num_indices {
+                let index = rng.next_u32() as usize % leaves.len();
+                if !indices.contains(&index) {
+                    indices.push(index);
+                }
+            }
+            let opened: Vec<H::Digest> = indices.iter().map(|&i| leaves[i]).collect();
+
+            let proof = tree.prove_batch(&indices).unwrap();
+            assert_eq!(proof.depth(), tree.depth());
+            assert_eq!(
+                proof.siblings().len(),
+                expected_num_siblings(1 << tree.depth(), &indices)
+            );
+            assert!(proof.verify(tree.root(), &indices, &opened));
+
+            // The order of the indices does not change the proof.
+            let mut reversed_indices = indices.clone();
+            reversed_indices.reverse();
+            let mut reversed_opened = opened.clone();
+            reversed_opened.reverse();
+            assert_eq!(tree.prove_batch(&reversed_indices).unwrap(), proof);
+            assert!(proof.verify(tree.root(), &reversed_indices, &reversed_opened));
+
+            // Wrong leaf
+            let mut wrong = opened.clone();
+            wrong[0] = H::hash(&[Fp::one()]);
+            assert!(!proof.verify(tree.root(), &indices, &wrong));
+            // Missing or extra sibling
+            if !proof.siblings().is_empty() {
+                let mut siblings = proof.siblings().to_vec();
+                let last = siblings.pop().unwrap();
+                let truncated = BatchMerkleProof::<H>::new(proof.depth(), siblings.clone());
+                assert!(!truncated.verify(tree.root(), &indices, &opened));
+                siblings.push(last);
+                siblings.push(last);
+                let extended = BatchMerkleProof::<H>::new(proof.depth(), siblings);
+                assert!(!extended.verify(tree.root(), &indices, &opened));
+            }
+            // Mismatched leaves
+            assert!(!proof.verify(tree.root(), &indices, &opened[1..]));
+        }
+
+        // Duplicate indices
+        assert_eq!(
+            tree.prove_batch(&[3, 1, 3]).unwrap_err(),
+            MerkleError::DuplicateIndex(3)
+        );
+        let proof = tree.prove_batch(&[3]).unwrap();
+        assert!(!proof.verify(tree.root(), &[3, 3], &[leaves[3], leaves[3]]));
+        // Invalid indices
+        assert_eq!(
+            tree.prove_batch(&[0, 27]).unwrap_err(),
+            MerkleError::InvalidIndex(27)
+        );
+        assert_eq!(tree.prove_batch(&[]).unwrap_err(), MerkleError::NoIndices);
+        assert!(!proof.verify(tree.root(), &[35], &[leaves[3]]));
+    }
+
+    #[test]
+    fn test_batch_merkle_proof() {
+        check_batch_proof::<anemoi_64_8_4::AnemoiHash>();
+        check_batch_proof::<anemoi_64_12_8::AnemoiHash>();
+        check_batch_proof::<anemoi_64_16_8::AnemoiHash>();
+        check_batch_proof::<griffin_64_8_4::GriffinHash>();
+        check_batch_proof::<griffin_64_12_8::GriffinHash>();
+        check_batch_proof::<rescue_64_8_4::RescueHash>();
+        check_batch_proof::<rescue_64_12_8::RescueHash>();
+        check_batch_proof::<rpo_64_12_8::RpoHash>();
+        check_batch_proof::<rp64_256::Rp64_256Hash>();
+        check_batch_proof::<poseidon_64_8_4::PoseidonHash>();
+        check_batch_proof::<poseidon_64_12_8::PoseidonHash>();
+        check_batch_proof::<plonky2_poseidon_64_12_8::Plonky2PoseidonHash>();
+    }
+}
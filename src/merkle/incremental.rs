@@ -0,0 +1,377 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! An `IncrementalMerkleTree<H, DEPTH>` is a Merkle tree with `2**DEPTH`
+//! leaves, initially all equal to `H::Digest::default()`, which are
+//! replaced from left to right as new leaves are appended. Its root hence
+//! matches the one of a `MerkleTree<H>` built from the appended leaves,
+//! padded up to `2**DEPTH` leaves.
+//!
+//! Only the frontier of the tree is stored, that is the digests of the
+//! complete subtrees on the left of the next leaf, at most one per height,
+//! along with the digests of empty subtrees.
+//!
+//! An `IncrementalWitness<H, DEPTH>` keeps the authentication path of a
+//! single leaf up to date, by being provided with all leaves appended to
+//! the tree after it. Right siblings of the path are then filled one after
+//! the other, the one being filled being tracked with its own frontier.
+
+use core::fmt;
+
+use super::{empty_digests, MerkleError};
+use crate::traits::{Digest, Hasher};
+
+use cheetah::Fp;
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+#[derive(Clone, Debug)]
+/// The frontier of an incremental Merkle tree of a given depth.
+struct Frontier<D: Digest> {
+    // Complete subtree of height h on the left of the next
+    // leaf, if bit h of the number of leaves is set.
+    nodes: Vec<D>,
+    num_leaves: usize,
+}
+
+impl<D: Digest> Frontier<D> {
+    fn new(depth: usize) -> Self {
+        Self {
+            nodes: vec![D::default(); depth + 1],
+            num_leaves: 0,
+        }
+    }
+
+    fn depth(&self) -> usize {
+        self.nodes.len() - 1
+    }
+
+    fn append<H: Hasher<Fp, Digest = D>>(&mut self, leaf: D) {
+        self.num_leaves += 1;
+
+        let mut node = leaf;
+        for h in 0..=self.depth() {
+            if (self.num_leaves >> h) & 1 == 1 {
+                self.nodes[h] = node;
+                break;
+            }
+            node = H::merge(&[self.nodes[h], node]);
+        }
+    }
+
+    fn root<H: Hasher<Fp, Digest = D>>(&self, empty_digests: &[D]) -> D {
+        let depth = self.depth();
+        if self.num_leaves == 1 << depth {
+            return self.nodes[depth];
+        }
+
+        let mut node = empty_digests[0];
+        for (h, empty) in empty_digests.iter().enumerate().take(depth) {
+            node = if (self.num_leaves >> h) & 1 == 1 {
+                H::merge(&[self.nodes[h], node])
+            } else {
+                H::merge(&[node, *empty])
+            };
+        }
+
+        node
+    }
+}
+
+/// An append-only Merkle tree of depth `DEPTH` over the hasher `H`.
+pub struct IncrementalMerkleTree<H: Hasher<Fp>, const DEPTH: usize> {
+    frontier: Frontier<H::Digest>,
+    empty_digests: Vec<H::Digest>,
+}
+
+impl<H: Hasher<Fp>, const DEPTH: usize> fmt::Debug for IncrementalMerkleTree<H, DEPTH> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IncrementalMerkleTree")
+            .field("frontier", &self.frontier)
+            .finish()
+    }
+}
+
+impl<H: Hasher<Fp>, const DEPTH: usize> Clone for IncrementalMerkleTree<H, DEPTH> {
+    fn clone(&self) -> Self {
+        Self {
+            frontier: self.frontier.clone(),
+            empty_digests: self.empty_digests.clone(),
+        }
+    }
+}
+
+impl<H: Hasher<Fp>, const DEPTH: usize> Default for IncrementalMerkleTree<H, DEPTH> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: Hasher<Fp>, const DEPTH: usize> IncrementalMerkleTree<H, DEPTH> {
+    /// Returns a new empty incremental Merkle tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `DEPTH` is zero, or if `2**DEPTH` does not fit in a `usize`.
+    pub fn new() -> Self {
+        assert!(
+            DEPTH > 0 && DEPTH < usize::BITS as usize,
+            "invalid incremental Merkle tree depth"
+        );
+
+        Self {
+            frontier: Frontier::new(DEPTH),
+            empty_digests: empty_digests::<H>(DEPTH),
+        }
+    }
+
+    /// Returns the number of leaves appended to this tree.
+    pub fn num_leaves(&self) -> usize {
+        self.frontier.num_leaves
+    }
+
+    /// Returns the root of this tree.
+    pub fn root(&self) -> H::Digest {
+        self.frontier.root::<H>(&self.empty_digests)
+    }
+
+    /// Appends a leaf to this tree, returning its index.
+    ///
+    /// Fails if the tree already has `2**DEPTH` leaves.
+    pub fn append(&mut self, leaf: H::Digest) -> Result<usize, MerkleError> {
+        let index = self.num_leaves();
+        if index == 1 << DEPTH {
+            return Err(MerkleError::TreeFull);
+        }
+
+        self.frontier.append::<H>(leaf);
+        Ok(index)
+    }
+
+    /// Appends a leaf to this tree, returning a witness of this leaf.
+    ///
+    /// Fails if the tree already has `2**DEPTH` leaves.
+    pub fn append_witnessed(
+        &mut self,
+        leaf: H::Digest,
+    ) -> Result<IncrementalWitness<H, DEPTH>, MerkleError> {
+        // Before appending, the frontier holds exactly the
+        // left siblings on the authentication path of the leaf.
+        let index = self.num_leaves();
+        let path = (0..DEPTH)
+            .map(|h| {
+                if (index >> h) & 1 == 1 {
+                    self.frontier.nodes[h]
+                } else {
+                    self.empty_digests[h]
+                }
+            })
+            .collect();
+
+        self.append(leaf)?;
+
+        Ok(IncrementalWitness {
+            index,
+            leaf,
+            path,
+            cursor: Frontier::new(0),
+            num_leaves: index + 1,
+            empty_digests: self.empty_digests.clone(),
+        })
+    }
+}
+
+/// The authentication path of a leaf of an `IncrementalMerkleTree<H, DEPTH>`,
+/// updated as new leaves are appended to the tree.
+pub struct IncrementalWitness<H: Hasher<Fp>, const DEPTH: usize> {
+    index: usize,
+    leaf: H::Digest,
+    path: Vec<H::Digest>,
+    // Frontier of the right sibling of the path being filled.
+    cursor: Frontier<H::Digest>,
+    num_leaves: usize,
+    empty_digests: Vec<H::Digest>,
+}
+
+impl<H: Hasher<Fp>, const DEPTH: usize> fmt::Debug for IncrementalWitness<H, DEPTH> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IncrementalWitness")
+            .field("index", &self.index)
+            .field("leaf", &self.leaf)
+            .field("path", &self.path)
+            .field("cursor", &self.cursor)
+            .field("num_leaves", &self.num_leaves)
+            .finish()
+    }
+}
+
+impl<H: Hasher<Fp>, const DEPTH: usize> Clone for IncrementalWitness<H, DEPTH> {
+    fn clone(&self) -> Self {
+        Self {
+            index: self.index,
+            leaf: self.leaf,
+            path: self.path.clone(),
+            cursor: self.cursor.clone(),
+            num_leaves: self.num_leaves,
+            empty_digests: self.empty_digests.clone(),
+        }
+    }
+}
+
+impl<H: Hasher<Fp>, const DEPTH: usize> IncrementalWitness<H, DEPTH> {
+    /// Returns the index of the witnessed leaf.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the witnessed leaf.
+    pub fn leaf(&self) -> &H::Digest {
+        &self.leaf
+    }
+
+    /// Returns the number of leaves of the tree this witness is up to date with.
+    pub fn num_leaves(&self) -> usize {
+        self.num_leaves
+    }
+
+    /// Returns the current authentication path of the witnessed
+    /// leaf, which can be checked with `MerkleTree::verify`.
+    pub fn path(&self) -> &[H::Digest] {
+        &self.path
+    }
+
+    /// Returns the root of the tree this witness is up to date with.
+    pub fn root(&self) -> H::Digest {
+        let mut node = self.leaf;
+        for (h, sibling) in self.path.iter().enumerate() {
+            node = if (self.index >> h) & 1 == 0 {
+                H::merge(&[node, *sibling])
+            } else {
+                H::merge(&[*sibling, node])
+            };
+        }
+
+        node
+    }
+
+    /// Updates this witness with the next leaf appended to the tree.
+    ///
+    /// Fails if the tree already has `2**DEPTH` leaves.
+    pub fn append(&mut self, leaf: H::Digest) -> Result<(), MerkleError> {
+        let position = self.num_leaves;
+        if position == 1 << DEPTH {
+            return Err(MerkleError::TreeFull);
+        }
+
+        // The new leaf belongs to the right sibling at the
+        // height of the highest bit differing from the index.
+        let height = (usize::BITS - 1 - (position ^ self.index).leading_zeros()) as usize;
+        if position & ((1 << height) - 1) == 0 {
+            self.cursor = Frontier::new(height);
+        }
+        self.cursor.append::<H>(leaf);
+        self.path[height] = self.cursor.root::<H>(&self.empty_digests);
+
+        self.num_leaves += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::MerkleTree;
+    use super::*;
+    use crate::{anemoi_64_12_8, anemoi_64_16_8, anemoi_64_8_4};
+    use crate::{griffin_64_12_8, griffin_64_8_4};
+    use crate::{plonky2_poseidon_64_12_8, poseidon_64_12_8, poseidon_64_8_4};
+    use crate::{rescue_64_12_8, rescue_64_8_4, rp64_256, rpo_64_12_8};
+    use rand_core::OsRng;
+
+    fn check_incremental_tree<H: Hasher<Fp>>() {
+        let mut rng = OsRng;
+        let leaves: Vec<H::Digest> = (0..16).map(|_| H::hash(&[Fp::random(&mut rng)])).collect();
+
+        let mut tree = IncrementalMerkleTree::<H, 4>::new();
+        let mut witnesses: Vec<IncrementalWitness<H, 4>> = Vec::new();
+        let mut padded = vec![H::Digest::default(); 16];
+        assert_eq!(tree.root(), *MerkleTree::<H>::new(&padded).unwrap().root());
+
+        for (i, leaf) in leaves.iter().enumerate() {
+            for witness in witnesses.iter_mut() {
+                witness.append(*leaf).unwrap();
+            }
+            if i % 3 == 0 {
+                witnesses.push(tree.append_witnessed(*leaf).unwrap());
+            } else {
+                assert_eq!(tree.append(*leaf).unwrap(), i);
+            }
+            assert_eq!(tree.num_leaves(), i + 1);
+
+            padded[i] = *leaf;
+            let full_tree = MerkleTree::<H>::new(&padded).unwrap();
+            assert_eq!(tree.root(), *full_tree.root());
+
+            for witness in witnesses.iter() {
+                assert_eq!(witness.num_leaves(), i + 1);
+                assert_eq!(witness.leaf(), &leaves[witness.index()]);
+                assert_eq!(
+                    witness.path(),
+                    &full_tree.prove(witness.index()).unwrap()[..]
+                );
+                assert_eq!(witness.root(), tree.root());
+                assert!(MerkleTree::<H>::verify(
+                    &tree.root(),
+                    witness.index(),
+                    witness.leaf(),
+                    witness.path()
+                ));
+            }
+        }
+
+        assert_eq!(tree.append(leaves[0]), Err(MerkleError::TreeFull));
+        assert_eq!(
+            witnesses[0].clone().append(leaves[0]),
+            Err(MerkleError::TreeFull)
+        );
+    }
+
+    #[test]
+    fn test_incremental_merkle_tree() {
+        check_incremental_tree::<anemoi_64_8_4::AnemoiHash>();
+        check_incremental_tree::<anemoi_64_12_8::AnemoiHash>();
+        check_incremental_tree::<anemoi_64_16_8::AnemoiHash>();
+        check_incremental_tree::<griffin_64_8_4::GriffinHash>();
+        check_incremental_tree::<griffin_64_12_8::GriffinHash>();
+        check_incremental_tree::<rescue_64_8_4::RescueHash>();
+        check_incremental_tree::<rescue_64_12_8::RescueHash>();
+        check_incremental_tree::<rpo_64_12_8::RpoHash>();
+        check_incremental_tree::<rp64_256::Rp64_256Hash>();
+        check_incremental_tree::<poseidon_64_8_4::PoseidonHash>();
+        check_incremental_tree::<poseidon_64_12_8::PoseidonHash>();
+        check_incremental_tree::<plonky2_poseidon_64_12_8::Plonky2PoseidonHash>();
+    }
+
+    #[test]
+    fn test_incremental_merkle_tree_deep() {
+        type H = anemoi_64_8_4::AnemoiHash;
+
+        let mut tree = IncrementalMerkleTree::<H, 32>::new();
+        let empty_root = tree.root();
+        assert_eq!(empty_root, empty_digests::<H>(32)[32]);
+
+        let mut witness = tree.append_witnessed(H::hash(&[Fp::one()])).unwrap();
+        for i in 0..40 {
+            let leaf = H::hash(&[Fp::new(i)]);
+            tree.append(leaf).unwrap();
+            witness.append(leaf).unwrap();
+            assert_eq!(witness.root(), tree.root());
+        }
+        assert_ne!(tree.root(), empty_root);
+    }
+}
@@ -0,0 +1,399 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! An `IndexedMerkleTree<H, DEPTH>` commits to a set of field elements,
+//! stored as a linked list sorted by canonical integer value, in the
+//! leaves of a Merkle tree of depth `DEPTH`.
+//!
+//! Each leaf holds a value, along with the index and the value of the next
+//! leaf in the sorted list, and has digest `H::hash([value, next_index,
+//! next_value])`. Unused leaves are `H::Digest::default()`, and internal
+//! nodes are obtained with `H::merge`, as in a `SparseMerkleTree<H, DEPTH>`
+//! keyed by leaf index. The leaf at index 0 always holds the value 0, which
+//! hence cannot be inserted, and the leaf holding the largest value has a
+//! next index and a next value of 0.
+//!
+//! Membership of a value is proven by opening the leaf holding it, and
+//! non-membership by opening the leaf with the largest lower value, whose
+//! next value must then be greater than the absent one, or 0.
+
+use core::fmt;
+
+use super::{key_from_element, MerkleError, SparseMerkleProof, SparseMerkleTree};
+use crate::traits::Hasher;
+
+use cheetah::Fp;
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// Returns the canonical integer value of a field element.
+fn to_canonical(element: &Fp) -> u64 {
+    u64::from_le_bytes(element.to_bytes())
+}
+
+/// Returns the key of the leaf at `index` in the underlying sparse Merkle tree.
+fn leaf_key(index: usize) -> [u8; 32] {
+    key_from_element(&Fp::new(index as u64))
+}
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+/// A leaf of an indexed Merkle tree.
+pub struct IndexedLeaf {
+    /// The value held by this leaf.
+    pub value: Fp,
+    /// The index of the leaf holding the next value, or 0 if there is none.
+    pub next_index: usize,
+    /// The next value in sorted order, or 0 if there is none.
+    pub next_value: Fp,
+}
+
+impl IndexedLeaf {
+    /// Returns the digest of this leaf.
+    pub fn digest<H: Hasher<Fp>>(&self) -> H::Digest {
+        H::hash(&[self.value, Fp::new(self.next_index as u64), self.next_value])
+    }
+
+    /// Returns whether `value` lies strictly between the value
+    /// of this leaf and the next one.
+    fn is_low_leaf_of(&self, value: &Fp) -> bool {
+        let value = to_canonical(value);
+        let next_value = to_canonical(&self.next_value);
+        to_canonical(&self.value) < value && (next_value == 0 || value < next_value)
+    }
+}
+
+/// An indexed Merkle tree of depth `DEPTH` over the hasher `H`.
+pub struct IndexedMerkleTree<H: Hasher<Fp>, const DEPTH: usize> {
+    leaves: Vec<IndexedLeaf>,
+    // Leaf index of each value.
+    indices: BTreeMap<u64, usize>,
+    tree: SparseMerkleTree<H, DEPTH>,
+}
+
+impl<H: Hasher<Fp>, const DEPTH: usize> fmt::Debug for IndexedMerkleTree<H, DEPTH> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IndexedMerkleTree")
+            .field("leaves", &self.leaves)
+            .field("tree", &self.tree)
+            .finish()
+    }
+}
+
+impl<H: Hasher<Fp>, const DEPTH: usize> Clone for IndexedMerkleTree<H, DEPTH> {
+    fn clone(&self) -> Self {
+        Self {
+            leaves: self.leaves.clone(),
+            indices: self.indices.clone(),
+            tree: self.tree.clone(),
+        }
+    }
+}
+
+impl<H: Hasher<Fp>, const DEPTH: usize> Default for IndexedMerkleTree<H, DEPTH> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: Hasher<Fp>, const DEPTH: usize> IndexedMerkleTree<H, DEPTH> {
+    /// Returns a new indexed Merkle tree, only holding the value 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `DEPTH` is zero or greater than 63.
+    pub fn new() -> Self {
+        assert!(DEPTH > 0 && DEPTH < 64, "invalid indexed Merkle tree depth");
+
+        let leaf = IndexedLeaf::default();
+        let mut tree = SparseMerkleTree::new();
+        tree.insert(&leaf_key(0), leaf.digest::<H>())
+            .expect("leaf index must fit in the tree");
+
+        let mut indices = BTreeMap::new();
+        indices.insert(0, 0);
+
+        Self {
+            leaves: vec![leaf],
+            indices,
+            tree,
+        }
+    }
+
+    /// Returns the root of this indexed Merkle tree.
+    pub fn root(&self) -> H::Digest {
+        self.tree.root()
+    }
+
+    /// Returns the leaves of this indexed Merkle tree, in insertion order.
+    pub fn leaves(&self) -> &[IndexedLeaf] {
+        &self.leaves
+    }
+
+    /// Inserts `value` in this indexed Merkle tree, returning
+    /// the index of the leaf holding it.
+    ///
+    /// Fails if `value` is already present, or if the tree is full.
+    pub fn insert(&mut self, value: Fp) -> Result<usize, MerkleError> {
+        let canonical = to_canonical(&value);
+        if self.indices.contains_key(&canonical) {
+            return Err(MerkleError::ValueExists);
+        }
+        let index = self.leaves.len();
+        if index == 1 << DEPTH {
+            return Err(MerkleError::TreeFull);
+        }
+
+        // The value 0 is always present, so that a lower value exists.
+        let (_, &low_index) = self
+            .indices
+            .range(..canonical)
+            .next_back()
+            .expect("the value 0 is always present");
+
+        let low_leaf = &mut self.leaves[low_index];
+        let leaf = IndexedLeaf {
+            value,
+            next_index: low_leaf.next_index,
+            next_value: low_leaf.next_value,
+        };
+        low_leaf.next_index = index;
+        low_leaf.next_value = value;
+        let low_digest = low_leaf.digest::<H>();
+
+        self.tree.insert(&leaf_key(low_index), low_digest)?;
+        self.tree.insert(&leaf_key(index), leaf.digest::<H>())?;
+        self.leaves.push(leaf);
+        self.indices.insert(canonical, index);
+
+        Ok(index)
+    }
+
+    /// Returns a proof that `value` belongs to this indexed Merkle tree.
+    ///
+    /// Fails if `value` is absent from the tree.
+    pub fn prove_membership(&self, value: &Fp) -> Result<IndexedMerkleProof<H>, MerkleError> {
+        let index = *self
+            .indices
+            .get(&to_canonical(value))
+            .ok_or(MerkleError::ValueNotFound)?;
+
+        self.prove_leaf(index)
+    }
+
+    /// Returns a proof that `value` does not belong to this indexed
+    /// Merkle tree, by opening the leaf holding the largest lower value.
+    ///
+    /// Fails if `value` is present in the tree.
+    pub fn prove_non_membership(&self, value: &Fp) -> Result<IndexedMerkleProof<H>, MerkleError> {
+        let canonical = to_canonical(value);
+        if self.indices.contains_key(&canonical) {
+            return Err(MerkleError::ValueExists);
+        }
+
+        let (_, &low_index) = self
+            .indices
+            .range(..canonical)
+            .next_back()
+            .expect("the value 0 is always present");
+
+        self.prove_leaf(low_index)
+    }
+
+    /// Checks that `proof` shows that `value` belongs to the
+    /// indexed Merkle tree with the given root.
+    pub fn verify_membership(root: &H::Digest, value: &Fp, proof: &IndexedMerkleProof<H>) -> bool {
+        proof.leaf.value == *value && Self::verify_leaf(root, proof)
+    }
+
+    /// Checks that `proof` shows that `value` does not belong
+    /// to the indexed Merkle tree with the given root.
+    pub fn verify_non_membership(
+        root: &H::Digest,
+        value: &Fp,
+        proof: &IndexedMerkleProof<H>,
+    ) -> bool {
+        proof.leaf.is_low_leaf_of(value) && Self::verify_leaf(root, proof)
+    }
+
+    fn prove_leaf(&self, index: usize) -> Result<IndexedMerkleProof<H>, MerkleError> {
+        Ok(IndexedMerkleProof {
+            index,
+            leaf: self.leaves[index],
+            path: self.tree.prove(&leaf_key(index))?,
+        })
+    }
+
+    fn verify_leaf(root: &H::Digest, proof: &IndexedMerkleProof<H>) -> bool {
+        proof.index < 1 << DEPTH
+            && SparseMerkleTree::<H, DEPTH>::verify(
+                root,
+                &leaf_key(proof.index),
+                Some(&proof.leaf.digest::<H>()),
+                &proof.path,
+            )
+    }
+}
+
+/// A membership or non-membership proof for an indexed Merkle tree over the hasher `H`.
+pub struct IndexedMerkleProof<H: Hasher<Fp>> {
+    index: usize,
+    leaf: IndexedLeaf,
+    path: SparseMerkleProof<H>,
+}
+
+impl<H: Hasher<Fp>> fmt::Debug for IndexedMerkleProof<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IndexedMerkleProof")
+            .field("index", &self.index)
+            .field("leaf", &self.leaf)
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl<H: Hasher<Fp>> Clone for IndexedMerkleProof<H> {
+    fn clone(&self) -> Self {
+        Self {
+            index: self.index,
+            leaf: self.leaf,
+            path: self.path.clone(),
+        }
+    }
+}
+
+impl<H: Hasher<Fp>> IndexedMerkleProof<H> {
+    /// Returns the index of the opened leaf.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the opened leaf.
+    pub fn leaf(&self) -> &IndexedLeaf {
+        &self.leaf
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{anemoi_64_12_8, anemoi_64_16_8, anemoi_64_8_4};
+    use crate::{griffin_64_12_8, griffin_64_8_4};
+    use crate::{poseidon_64_12_8, poseidon_64_8_4};
+    use crate::{rescue_64_12_8, rescue_64_8_4, rp64_256, rpo_64_12_8};
+    use rand_core::OsRng;
+
+    fn check_indexed_tree<H: Hasher<Fp>>() {
+        let mut rng = OsRng;
+        let mut tree = IndexedMerkleTree::<H, 5>::new();
+
+        let mut values: Vec<Fp> = (0..20).map(|_| Fp::random(&mut rng)).collect();
+        for (i, value) in values.iter().enumerate() {
+            let root = tree.root();
+            assert!(IndexedMerkleTree::<H, 5>::verify_non_membership(
+                &root,
+                value,
+                &tree.prove_non_membership(value).unwrap()
+            ));
+            assert_eq!(tree.insert(*value).unwrap(), i + 1);
+            assert_ne!(tree.root(), root);
+        }
+        values.sort_unstable_by_key(to_canonical);
+
+        // The leaves form a sorted linked list from the leaf at index 0.
+        let leaves = tree.leaves();
+        let mut leaf = leaves[0];
+        for value in values.iter() {
+            assert_eq!(leaf.next_value, *value);
+            leaf = leaves[leaf.next_index];
+            assert_eq!(leaf.value, *value);
+        }
+        assert_eq!((leaf.next_index, leaf.next_value), (0, Fp::zero()));
+
+        // The root matches a sparse Merkle tree over the leaf digests.
+        let mut sparse = SparseMerkleTree::<H, 5>::new();
+        for (i, leaf) in leaves.iter().enumerate() {
+            sparse.insert(&leaf_key(i), leaf.digest::<H>()).unwrap();
+        }
+        assert_eq!(sparse.root(), tree.root());
+
+        let root = tree.root();
+        for value in values.iter() {
+            let proof = tree.prove_membership(value).unwrap();
+            assert!(IndexedMerkleTree::<H, 5>::verify_membership(
+                &root, value, &proof
+            ));
+            assert!(!IndexedMerkleTree::<H, 5>::verify_non_membership(
+                &root, value, &proof
+            ));
+            assert_eq!(
+                tree.prove_non_membership(value).unwrap_err(),
+                MerkleError::ValueExists
+            );
+
+            // Values right after a present one are absent, unless present too.
+            let next = *value + Fp::one();
+            if tree.prove_membership(&next).is_err() {
+                let proof = tree.prove_non_membership(&next).unwrap();
+                assert_eq!(proof.leaf().value, *value);
+                assert!(IndexedMerkleTree::<H, 5>::verify_non_membership(
+                    &root, &next, &proof
+                ));
+                assert!(!IndexedMerkleTree::<H, 5>::verify_membership(
+                    &root, &next, &proof
+                ));
+            }
+        }
+
+        // Values larger than all others are proven absent with the last leaf.
+        let largest = -Fp::one();
+        let proof = tree.prove_non_membership(&largest).unwrap();
+        assert_eq!(proof.leaf().next_value, Fp::zero());
+        assert!(IndexedMerkleTree::<H, 5>::verify_non_membership(
+            &root, &largest, &proof
+        ));
+
+        // A low leaf does not prove values outside of its range.
+        let low = tree.prove_membership(&values[0]).unwrap();
+        assert!(!IndexedMerkleTree::<H, 5>::verify_non_membership(
+            &root,
+            &values[10],
+            &low
+        ));
+
+        assert_eq!(tree.insert(values[3]), Err(MerkleError::ValueExists));
+        assert_eq!(tree.insert(Fp::zero()), Err(MerkleError::ValueExists));
+        assert_eq!(
+            tree.prove_membership(&largest).unwrap_err(),
+            MerkleError::ValueNotFound
+        );
+
+        // The tree holds 32 leaves, including the one for 0.
+        for i in 0..11 {
+            tree.insert(Fp::new(i + 1)).unwrap();
+        }
+        assert_eq!(tree.insert(Fp::new(100)), Err(MerkleError::TreeFull));
+    }
+
+    #[test]
+    fn test_indexed_merkle_tree() {
+        check_indexed_tree::<anemoi_64_8_4::AnemoiHash>();
+        check_indexed_tree::<anemoi_64_12_8::AnemoiHash>();
+        check_indexed_tree::<anemoi_64_16_8::AnemoiHash>();
+        check_indexed_tree::<griffin_64_8_4::GriffinHash>();
+        check_indexed_tree::<griffin_64_12_8::GriffinHash>();
+        check_indexed_tree::<rescue_64_8_4::RescueHash>();
+        check_indexed_tree::<rescue_64_12_8::RescueHash>();
+        check_indexed_tree::<rpo_64_12_8::RpoHash>();
+        check_indexed_tree::<rp64_256::Rp64_256Hash>();
+        check_indexed_tree::<poseidon_64_8_4::PoseidonHash>();
+        check_indexed_tree::<poseidon_64_12_8::PoseidonHash>();
+    }
+}
@@ -0,0 +1,240 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A `MatrixCommitment<H>` commits to a matrix of field elements by hashing
+//! each row with `H::hash`, and building a `MerkleTree<H>` over the row
+//! digests.
+//!
+//! Instead of the root, the commitment is a Merkle cap: the `2**cap_height`
+//! nodes at distance `cap_height` from the root, from left to right. A cap
+//! of height 0 is hence the root itself. Authentication paths then stop
+//! below the cap, and are `cap_height` digests shorter than full paths.
+
+use core::fmt;
+
+use super::{MerkleError, MerkleTree};
+use crate::traits::Hasher;
+
+use cheetah::Fp;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+impl<H: Hasher<Fp>> MerkleTree<H> {
+    /// Returns the Merkle cap of height `cap_height` of this tree.
+    ///
+    /// Fails if `cap_height` is greater than the depth of the tree.
+    pub fn cap(&self, cap_height: usize) -> Result<&[H::Digest], MerkleError> {
+        if cap_height > self.depth() {
+            return Err(MerkleError::InvalidCapHeight(cap_height));
+        }
+
+        Ok(&self.nodes[1 << cap_height..2 << cap_height])
+    }
+
+    /// Returns the authentication path of the leaf at `index`
+    /// up to the Merkle cap of height `cap_height`.
+    ///
+    /// Fails if `index` does not refer to one of the tree leaves,
+    /// or if `cap_height` is greater than the depth of the tree.
+    pub fn prove_with_cap(
+        &self,
+        index: usize,
+        cap_height: usize,
+    ) -> Result<Vec<H::Digest>, MerkleError> {
+        if cap_height > self.depth() {
+            return Err(MerkleError::InvalidCapHeight(cap_height));
+        }
+
+        let mut path = self.prove(index)?;
+        path.truncate(self.depth() - cap_height);
+        Ok(path)
+    }
+
+    /// Checks that `path` authenticates `leaf` at `index` against `cap`.
+    pub fn verify_with_cap(
+        cap: &[H::Digest],
+        index: usize,
+        leaf: &H::Digest,
+        path: &[H::Digest],
+    ) -> bool {
+        if !cap.len().is_power_of_two() || path.len() >= usize::BITS as usize {
+            return false;
+        }
+
+        let cap_index = index >> path.len();
+        if cap_index >= cap.len() {
+            return false;
+        }
+
+        let mut node = *leaf;
+        for (i, sibling) in path.iter().enumerate() {
+            node = if (index >> i) & 1 == 0 {
+                H::merge(&[node, *sibling])
+            } else {
+                H::merge(&[*sibling, node])
+            };
+        }
+
+        node == cap[cap_index]
+    }
+}
+
+/// A commitment to a matrix of field elements over the hasher `H`.
+pub struct MatrixCommitment<H: Hasher<Fp>> {
+    rows: Vec<Vec<Fp>>,
+    tree: MerkleTree<H>,
+    cap_height: usize,
+}
+
+impl<H: Hasher<Fp>> fmt::Debug for MatrixCommitment<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MatrixCommitment")
+            .field("rows", &self.rows)
+            .field("tree", &self.tree)
+            .field("cap_height", &self.cap_height)
+            .finish()
+    }
+}
+
+impl<H: Hasher<Fp>> Clone for MatrixCommitment<H> {
+    fn clone(&self) -> Self {
+        Self {
+            rows: self.rows.clone(),
+            tree: self.tree.clone(),
+            cap_height: self.cap_height,
+        }
+    }
+}
+
+impl<H: Hasher<Fp>> MatrixCommitment<H> {
+    /// Returns a commitment to the provided rows, with a
+    /// Merkle cap of height `cap_height`.
+    ///
+    /// Fails if `rows` is empty, or if `cap_height` is greater than
+    /// the depth of the Merkle tree over the rows.
+    pub fn new(rows: &[Vec<Fp>], cap_height: usize) -> Result<Self, MerkleError> {
+        let leaves: Vec<H::Digest> = rows.iter().map(|row| H::hash(row)).collect();
+        let tree = MerkleTree::new(&leaves)?;
+        tree.cap(cap_height)?;
+
+        Ok(Self {
+            rows: rows.to_vec(),
+            tree,
+            cap_height,
+        })
+    }
+
+    /// Returns the Merkle cap of this commitment.
+    pub fn cap(&self) -> &[H::Digest] {
+        &self.tree.nodes[1 << self.cap_height..2 << self.cap_height]
+    }
+
+    /// Returns the height of the Merkle cap of this commitment.
+    pub fn cap_height(&self) -> usize {
+        self.cap_height
+    }
+
+    /// Returns the committed rows.
+    pub fn rows(&self) -> &[Vec<Fp>] {
+        &self.rows
+    }
+
+    /// Opens the row at `index`, returning it along with
+    /// its authentication path up to the Merkle cap.
+    ///
+    /// Fails if `index` does not refer to one of the rows.
+    pub fn open(&self, index: usize) -> Result<(&[Fp], Vec<H::Digest>), MerkleError> {
+        let path = self.tree.prove_with_cap(index, self.cap_height)?;
+        Ok((&self.rows[index], path))
+    }
+
+    /// Checks that `path` authenticates `row` at `index` against `cap`.
+    pub fn verify(cap: &[H::Digest], index: usize, row: &[Fp], path: &[H::Digest]) -> bool {
+        MerkleTree::<H>::verify_with_cap(cap, index, &H::hash(row), path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{anemoi_64_12_8, anemoi_64_16_8, anemoi_64_8_4};
+    use crate::{griffin_64_12_8, griffin_64_8_4};
+    use crate::{poseidon_64_12_8, poseidon_64_8_4};
+    use crate::{rescue_64_12_8, rescue_64_8_4, rp64_256, rpo_64_12_8};
+    use rand_core::OsRng;
+
+    fn check_matrix_commitment<H: Hasher<Fp>>() {
+        let mut rng = OsRng;
+        let rows: Vec<Vec<Fp>> = (0..13)
+            .map(|_| (0..10).map(|_| Fp::random(&mut rng)).collect())
+            .collect();
+
+        let leaves: Vec<H::Digest> = rows.iter().map(|row| H::hash(row)).collect();
+        let tree = MerkleTree::<H>::new(&leaves).unwrap();
+        assert_eq!(tree.depth(), 4);
+
+        for cap_height in 0..=4 {
+            let commitment = MatrixCommitment::<H>::new(&rows, cap_height).unwrap();
+            let cap = commitment.cap();
+            assert_eq!(cap.len(), 1 << cap_height);
+            assert_eq!(cap, tree.cap(cap_height).unwrap());
+            if cap_height == 0 {
+                assert_eq!(cap, [*tree.root()]);
+            }
+            if cap_height == 4 {
+                assert_eq!(&cap[..13], &leaves[..]);
+            }
+
+            for (index, row) in rows.iter().enumerate() {
+                let (opened, path) = commitment.open(index).unwrap();
+                assert_eq!(opened, &row[..]);
+                assert_eq!(path.len(), 4 - cap_height);
+                assert_eq!(path, tree.prove(index).unwrap()[..4 - cap_height]);
+                assert!(MatrixCommitment::<H>::verify(cap, index, row, &path));
+
+                // Wrong row
+                let mut wrong = row.clone();
+                wrong[0] += Fp::one();
+                assert!(!MatrixCommitment::<H>::verify(cap, index, &wrong, &path));
+                // Wrong index
+                assert!(!MatrixCommitment::<H>::verify(cap, index ^ 1, row, &path));
+                assert!(!MatrixCommitment::<H>::verify(cap, index + 16, row, &path));
+            }
+
+            assert_eq!(
+                commitment.open(13).unwrap_err(),
+                MerkleError::InvalidIndex(13)
+            );
+        }
+
+        assert_eq!(
+            MatrixCommitment::<H>::new(&rows, 5).unwrap_err(),
+            MerkleError::InvalidCapHeight(5)
+        );
+        assert_eq!(
+            MatrixCommitment::<H>::new(&[], 0).unwrap_err(),
+            MerkleError::NoLeaves
+        );
+    }
+
+    #[test]
+    fn test_matrix_commitment() {
+        check_matrix_commitment::<anemoi_64_8_4::AnemoiHash>();
+        check_matrix_commitment::<anemoi_64_12_8::AnemoiHash>();
+        check_matrix_commitment::<anemoi_64_16_8::AnemoiHash>();
+        check_matrix_commitment::<griffin_64_8_4::GriffinHash>();
+        check_matrix_commitment::<griffin_64_12_8::GriffinHash>();
+        check_matrix_commitment::<rescue_64_8_4::RescueHash>();
+        check_matrix_commitment::<rescue_64_12_8::RescueHash>();
+        check_matrix_commitment::<rpo_64_12_8::RpoHash>();
+        check_matrix_commitment::<rp64_256::Rp64_256Hash>();
+        check_matrix_commitment::<poseidon_64_8_4::PoseidonHash>();
+        check_matrix_commitment::<poseidon_64_12_8::PoseidonHash>();
+    }
+}
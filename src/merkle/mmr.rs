@@ -0,0 +1,362 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A `MerkleMountainRange<H>` is an append-only list of leaves, split into
+//! perfect binary Merkle trees (the mountains) of strictly decreasing
+//! heights, one for each bit set in the number of leaves. Internal nodes
+//! are obtained with `H::merge` over their two children.
+//!
+//! The peaks `P_1, ..., P_k` of the mountains, from left to right, are
+//! bagged from right to left into `B = merge(P_1, merge(P_2, ... P_k))`,
+//! with `B = H::Digest::default()` for an empty range. The root is then
+//! `H::merge(H::hash([n]), B)`, where `n` is the number of leaves, so that
+//! ranges of different sizes never share a root.
+//!
+//! As mountains never change once complete, the root and inclusion proofs
+//! of any previous size of the range can still be computed.
+
+use core::fmt;
+
+use super::MerkleError;
+use crate::traits::Hasher;
+
+use cheetah::Fp;
+
+#[cfg(not(feature = "std"))]
+use alloc::{vec, vec::Vec};
+
+/// A Merkle Mountain Range over the hasher `H`.
+pub struct MerkleMountainRange<H: Hasher<Fp>> {
+    // The digests of all complete subtrees, by height
+    // and position from the left at this height.
+    levels: Vec<Vec<H::Digest>>,
+}
+
+impl<H: Hasher<Fp>> fmt::Debug for MerkleMountainRange<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MerkleMountainRange")
+            .field("levels", &self.levels)
+            .finish()
+    }
+}
+
+impl<H: Hasher<Fp>> Clone for MerkleMountainRange<H> {
+    fn clone(&self) -> Self {
+        Self {
+            levels: self.levels.clone(),
+        }
+    }
+}
+
+impl<H: Hasher<Fp>> Default for MerkleMountainRange<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: Hasher<Fp>> MerkleMountainRange<H> {
+    /// Returns a new empty Merkle Mountain Range.
+    pub fn new() -> Self {
+        Self {
+            levels: vec![Vec::new()],
+        }
+    }
+
+    /// Returns the number of leaves of this Merkle Mountain Range.
+    pub fn num_leaves(&self) -> usize {
+        self.levels[0].len()
+    }
+
+    /// Appends a leaf to this Merkle Mountain Range, returning its index.
+    pub fn append(&mut self, leaf: H::Digest) -> usize {
+        let index = self.num_leaves();
+        self.levels[0].push(leaf);
+
+        let mut h = 0;
+        while self.levels[h].len() & 1 == 0 {
+            let len = self.levels[h].len();
+            let node = H::merge(&[self.levels[h][len - 2], self.levels[h][len - 1]]);
+            if h + 1 == self.levels.len() {
+                self.levels.push(Vec::new());
+            }
+            self.levels[h + 1].push(node);
+            h += 1;
+        }
+
+        index
+    }
+
+    /// Returns the peaks of this Merkle Mountain Range, from left to right.
+    pub fn peaks(&self) -> Vec<H::Digest> {
+        self.peaks_at(self.num_leaves())
+    }
+
+    /// Returns the root of this Merkle Mountain Range.
+    pub fn root(&self) -> H::Digest {
+        self.root_at(self.num_leaves())
+    }
+
+    /// Returns the root this Merkle Mountain Range had when it
+    /// contained `num_leaves` leaves.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_leaves` is greater than the current number of leaves.
+    pub fn root_at(&self, num_leaves: usize) -> H::Digest {
+        bag_peaks::<H>(num_leaves, &self.peaks_at(num_leaves))
+    }
+
+    /// Returns an inclusion proof of the leaf at `index`.
+    ///
+    /// Fails if `index` does not refer to one of the leaves.
+    pub fn prove(&self, index: usize) -> Result<MmrProof<H>, MerkleError> {
+        self.prove_at(index, self.num_leaves())
+    }
+
+    /// Returns an inclusion proof of the leaf at `index`, against the root
+    /// this Merkle Mountain Range had when it contained `num_leaves` leaves.
+    ///
+    /// Fails if `index` is not lower than `num_leaves`, or if `num_leaves`
+    /// is greater than the current number of leaves.
+    pub fn prove_at(&self, index: usize, num_leaves: usize) -> Result<MmrProof<H>, MerkleError> {
+        if index >= num_leaves || num_leaves > self.num_leaves() {
+            return Err(MerkleError::InvalidIndex(index));
+        }
+
+        let (height, _) = mountain(num_leaves, index);
+        let path = (0..height)
+            .map(|h| self.levels[h][(index >> h) ^ 1])
+            .collect();
+
+        Ok(MmrProof {
+            num_leaves,
+            index,
+            path,
+            peaks: self.peaks_at(num_leaves),
+        })
+    }
+
+    /// Checks that `proof` authenticates `leaf` against `root`.
+    pub fn verify(root: &H::Digest, leaf: &H::Digest, proof: &MmrProof<H>) -> bool {
+        if proof.index >= proof.num_leaves {
+            return false;
+        }
+
+        let (height, peak) = mountain(proof.num_leaves, proof.index);
+        if proof.path.len() != height || proof.peaks.len() != proof.num_leaves.count_ones() as usize
+        {
+            return false;
+        }
+
+        let mut node = *leaf;
+        for (h, sibling) in proof.path.iter().enumerate() {
+            node = if (proof.index >> h) & 1 == 0 {
+                H::merge(&[node, *sibling])
+            } else {
+                H::merge(&[*sibling, node])
+            };
+        }
+
+        node == proof.peaks[peak] && bag_peaks::<H>(proof.num_leaves, &proof.peaks) == *root
+    }
+
+    fn peaks_at(&self, num_leaves: usize) -> Vec<H::Digest> {
+        assert!(
+            num_leaves <= self.num_leaves(),
+            "Merkle Mountain Range does not have that many leaves"
+        );
+
+        let mut peaks = Vec::with_capacity(num_leaves.count_ones() as usize);
+        let mut offset = 0;
+        for h in (0..usize::BITS as usize).rev() {
+            if (num_leaves >> h) & 1 == 1 {
+                peaks.push(self.levels[h][offset >> h]);
+                offset += 1 << h;
+            }
+        }
+
+        peaks
+    }
+}
+
+/// Returns the height of the mountain containing the leaf at `index` in a
+/// range of `num_leaves` leaves, along with the position of its peak.
+fn mountain(num_leaves: usize, index: usize) -> (usize, usize) {
+    let mut peak = 0;
+    let mut offset = 0;
+    for h in (0..usize::BITS as usize).rev() {
+        if (num_leaves >> h) & 1 == 1 {
+            offset += 1 << h;
+            if index < offset {
+                return (h, peak);
+            }
+            peak += 1;
+        }
+    }
+
+    unreachable!("leaf index must be lower than the number of leaves")
+}
+
+/// Returns the root of a range of `num_leaves` leaves with the given peaks.
+fn bag_peaks<H: Hasher<Fp>>(num_leaves: usize, peaks: &[H::Digest]) -> H::Digest {
+    let bag = match peaks.split_last() {
+        Some((last, rest)) => rest
+            .iter()
+            .rev()
+            .fold(*last, |bag, peak| H::merge(&[*peak, bag])),
+        None => H::Digest::default(),
+    };
+
+    H::merge(&[H::hash(&[Fp::new(num_leaves as u64)]), bag])
+}
+
+/// An inclusion proof for a Merkle Mountain Range over the hasher `H`.
+pub struct MmrProof<H: Hasher<Fp>> {
+    num_leaves: usize,
+    index: usize,
+    path: Vec<H::Digest>,
+    peaks: Vec<H::Digest>,
+}
+
+impl<H: Hasher<Fp>> fmt::Debug for MmrProof<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MmrProof")
+            .field("num_leaves", &self.num_leaves)
+            .field("index", &self.index)
+            .field("path", &self.path)
+            .field("peaks", &self.peaks)
+            .finish()
+    }
+}
+
+impl<H: Hasher<Fp>> Clone for MmrProof<H> {
+    fn clone(&self) -> Self {
+        Self {
+            num_leaves: self.num_leaves,
+            index: self.index,
+            path: self.path.clone(),
+            peaks: self.peaks.clone(),
+        }
+    }
+}
+
+impl<H: Hasher<Fp>> MmrProof<H> {
+    /// Returns the number of leaves of the range this proof refers to.
+    pub fn num_leaves(&self) -> usize {
+        self.num_leaves
+    }
+
+    /// Returns the index of the leaf this proof refers to.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the authentication path of the leaf within its mountain.
+    pub fn path(&self) -> &[H::Digest] {
+        &self.path
+    }
+
+    /// Returns the peaks of the range this proof refers to.
+    pub fn peaks(&self) -> &[H::Digest] {
+        &self.peaks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::MerkleTree;
+    use super::*;
+    use crate::{anemoi_64_12_8, anemoi_64_16_8, anemoi_64_8_4};
+    use crate::{griffin_64_12_8, griffin_64_8_4};
+    use crate::{plonky2_poseidon_64_12_8, poseidon_64_12_8, poseidon_64_8_4};
+    use crate::{rescue_64_12_8, rescue_64_8_4, rp64_256, rpo_64_12_8};
+    use rand_core::OsRng;
+
+    fn check_mmr<H: Hasher<Fp>>() {
+        let mut rng = OsRng;
+        let leaves: Vec<H::Digest> = (0..21).map(|_| H::hash(&[Fp::random(&mut rng)])).collect();
+
+        let mut mmr = MerkleMountainRange::<H>::new();
+        assert_eq!(
+            mmr.root(),
+            H::merge(&[H::hash(&[Fp::zero()]), H::Digest::default()])
+        );
+
+        let mut roots = vec![mmr.root()];
+        for (i, leaf) in leaves.iter().enumerate() {
+            assert_eq!(mmr.append(*leaf), i);
+            roots.push(mmr.root());
+        }
+        assert_eq!(mmr.num_leaves(), 21);
+
+        // 21 = 16 + 4 + 1 leaves, bagged from right to left.
+        let peaks = [
+            *MerkleTree::<H>::new(&leaves[..16]).unwrap().root(),
+            *MerkleTree::<H>::new(&leaves[16..20]).unwrap().root(),
+            leaves[20],
+        ];
+        assert_eq!(mmr.peaks(), peaks);
+        let bag = H::merge(&[peaks[0], H::merge(&[peaks[1], peaks[2]])]);
+        assert_eq!(mmr.root(), H::merge(&[H::hash(&[Fp::new(21)]), bag]));
+
+        for num_leaves in 1..=leaves.len() {
+            assert_eq!(mmr.root_at(num_leaves), roots[num_leaves]);
+
+            for (index, leaf) in leaves[..num_leaves].iter().enumerate() {
+                let proof = mmr.prove_at(index, num_leaves).unwrap();
+                assert_eq!(proof.num_leaves(), num_leaves);
+                assert_eq!(proof.index(), index);
+                assert!(MerkleMountainRange::<H>::verify(
+                    &roots[num_leaves],
+                    leaf,
+                    &proof
+                ));
+
+                // Wrong leaf
+                assert!(!MerkleMountainRange::<H>::verify(
+                    &roots[num_leaves],
+                    &H::hash(&[]),
+                    &proof
+                ));
+                // Wrong size
+                assert!(!MerkleMountainRange::<H>::verify(
+                    &roots[num_leaves - 1],
+                    leaf,
+                    &proof
+                ));
+            }
+        }
+
+        // Paths within complete mountains stay valid as the range grows.
+        let old = mmr.prove_at(3, 4).unwrap();
+        let new = mmr.prove(3).unwrap();
+        assert_eq!(old.path(), &new.path()[..2]);
+
+        assert_eq!(mmr.prove(21).unwrap_err(), MerkleError::InvalidIndex(21));
+        assert_eq!(
+            mmr.prove_at(0, 22).unwrap_err(),
+            MerkleError::InvalidIndex(0)
+        );
+    }
+
+    #[test]
+    fn test_merkle_mountain_range() {
+        check_mmr::<anemoi_64_8_4::AnemoiHash>();
+        check_mmr::<anemoi_64_12_8::AnemoiHash>();
+        check_mmr::<anemoi_64_16_8::AnemoiHash>();
+        check_mmr::<griffin_64_8_4::GriffinHash>();
+        check_mmr::<griffin_64_12_8::GriffinHash>();
+        check_mmr::<rescue_64_8_4::RescueHash>();
+        check_mmr::<rescue_64_12_8::RescueHash>();
+        check_mmr::<rpo_64_12_8::RpoHash>();
+        check_mmr::<rp64_256::Rp64_256Hash>();
+        check_mmr::<poseidon_64_8_4::PoseidonHash>();
+        check_mmr::<poseidon_64_12_8::PoseidonHash>();
+        check_mmr::<plonky2_poseidon_64_12_8::Plonky2PoseidonHash>();
+    }
+}
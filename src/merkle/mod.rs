@@ -0,0 +1,338 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A `MerkleTree<H>` is a binary tree whose internal nodes are obtained
+//! with `H::merge` over their two children.
+//!
+//! If the number of leaves is not a power of two, the leaves are padded
+//! on the right with `H::Digest::default()` up to the next power of two.
+//! Padding leaves are part of the tree and hence of the root, but `prove`
+//! refuses to open them. A tree with a single leaf has this leaf as root.
+//!
+//! The root does not commit to the number of leaves: a path to a padding
+//! position is a valid opening of `H::Digest::default()` at this position.
+//! Verifiers must hence check opened indices against the number of leaves
+//! they expect.
+//!
+//! Authentication paths list the sibling digests of the nodes on the
+//! path from a leaf to the root, starting from the leaf level.
+
+use core::fmt::{self, Display};
+
+use crate::traits::Hasher;
+
+use cheetah::Fp;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Batch proofs for Merkle trees
+mod batch;
+/// Incremental Merkle trees
+mod incremental;
+/// Indexed Merkle trees
+mod indexed;
+/// Matrix commitments with Merkle caps
+mod matrix;
+/// Merkle Mountain Ranges
+mod mmr;
+/// Salted Merkle trees
+mod salted;
+/// Binary serialization of Merkle proofs and digests
+mod serialization;
+/// Sparse Merkle trees
+mod sparse;
+/// Batched updates for Merkle trees
+mod update;
+
+pub use batch::BatchMerkleProof;
+pub use incremental::{IncrementalMerkleTree, IncrementalWitness};
+pub use indexed::{IndexedLeaf, IndexedMerkleProof, IndexedMerkleTree};
+pub use matrix::MatrixCommitment;
+pub use mmr::{MerkleMountainRange, MmrProof};
+pub use salted::{SaltedMerkleTree, SaltedOpening, SALT_SIZE};
+pub use serialization::{
+    deserialize_batch_proof, deserialize_digest, deserialize_path, serialize_batch_proof,
+    serialize_digest, serialize_path, DeserializationError, HashFamily, HasherId,
+    MAX_SERIALIZED_DEPTH, SERIALIZATION_VERSION,
+};
+pub use sparse::{
+    key_from_element, SparseMerkleKey, SparseMerkleProof, SparseMerkleTree, MAX_SPARSE_DEPTH,
+};
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// Errors that can occur when building or opening a Merkle tree.
+pub enum MerkleError {
+    /// A Merkle tree cannot be built from an empty set of leaves.
+    NoLeaves,
+    /// The given leaf index does not refer to one of the tree leaves.
+    InvalidIndex(usize),
+    /// A batch proof cannot be built from an empty set of indices.
+    NoIndices,
+    /// The given leaf index appears several times in a batch opening.
+    DuplicateIndex(usize),
+    /// The given key has non-zero bits above the depth of a sparse Merkle tree.
+    InvalidKey,
+    /// No more leaves can be appended to a fixed-depth Merkle tree.
+    TreeFull,
+    /// The given Merkle cap height exceeds the depth of the tree.
+    InvalidCapHeight(usize),
+    /// The given value is already present in an indexed Merkle tree.
+    ValueExists,
+    /// The given value is absent from an indexed Merkle tree.
+    ValueNotFound,
+}
+
+impl Display for MerkleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MerkleError::NoLeaves => write!(f, "a Merkle tree requires at least one leaf"),
+            MerkleError::InvalidIndex(index) => write!(f, "leaf index {} is out of bounds", index),
+            MerkleError::NoIndices => write!(f, "a batch proof requires at least one index"),
+            MerkleError::DuplicateIndex(index) => {
+                write!(f, "leaf index {} is opened more than once", index)
+            }
+            MerkleError::InvalidKey => write!(f, "key exceeds the sparse Merkle tree depth"),
+            MerkleError::TreeFull => write!(f, "the Merkle tree is full"),
+            MerkleError::InvalidCapHeight(height) => {
+                write!(f, "cap height {} exceeds the Merkle tree depth", height)
+            }
+            MerkleError::ValueExists => write!(f, "value is already present in the tree"),
+            MerkleError::ValueNotFound => write!(f, "value is absent from the tree"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for MerkleError {}
+
+/// Returns the digests of empty subtrees of height 0 to `depth`, whose
+/// leaves are all `H::Digest::default()`.
+pub(crate) fn empty_digests<H: Hasher<Fp>>(depth: usize) -> Vec<H::Digest> {
+    let mut digests = Vec::with_capacity(depth + 1);
+    digests.push(H::Digest::default());
+    for h in 0..depth {
+        digests.push(H::merge(&[digests[h], digests[h]]));
+    }
+
+    digests
+}
+
+/// A binary Merkle tree over the hasher `H`.
+pub struct MerkleTree<H: Hasher<Fp>> {
+    // All nodes of the tree, the root being at index 1 and the children
+    // of node i at indices 2i and 2i + 1. Index 0 is unused.
+    nodes: Vec<H::Digest>,
+    num_leaves: usize,
+}
+
+impl<H: Hasher<Fp>> fmt::Debug for MerkleTree<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MerkleTree")
+            .field("nodes", &self.nodes)
+            .field("num_leaves", &self.num_leaves)
+            .finish()
+    }
+}
+
+impl<H: Hasher<Fp>> Clone for MerkleTree<H> {
+    fn clone(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+            num_leaves: self.num_leaves,
+        }
+    }
+}
+
+impl<H: Hasher<Fp>> MerkleTree<H> {
+    /// Returns a new Merkle tree built from the provided leaves.
+    ///
+    /// Fails if `leaves` is empty.
+    pub fn new(leaves: &[H::Digest]) -> Result<Self, MerkleError> {
+        if leaves.is_empty() {
+            return Err(MerkleError::NoLeaves);
+        }
+
+        let width = leaves.len().next_power_of_two();
+        let mut nodes = Vec::with_capacity(2 * width);
+        nodes.resize(width, H::Digest::default());
+        nodes.extend_from_slice(leaves);
+        nodes.resize(2 * width, H::Digest::default());
+
+        for i in (1..width).rev() {
+            nodes[i] = H::merge(&[nodes[2 * i], nodes[2 * i + 1]]);
+        }
+
+        Ok(Self {
+            nodes,
+            num_leaves: leaves.len(),
+        })
+    }
+
+    /// Returns the root of this Merkle tree.
+    pub fn root(&self) -> &H::Digest {
+        &self.nodes[1]
+    }
+
+    /// Returns the depth of this Merkle tree, i.e. the length of its
+    /// authentication paths.
+    pub fn depth(&self) -> usize {
+        self.width().trailing_zeros() as usize
+    }
+
+    /// Returns the leaves this Merkle tree was built from, without padding.
+    pub fn leaves(&self) -> &[H::Digest] {
+        let width = self.width();
+        &self.nodes[width..width + self.num_leaves]
+    }
+
+    /// Returns the authentication path of the leaf at `index`.
+    ///
+    /// Fails if `index` does not refer to one of the tree leaves.
+    pub fn prove(&self, index: usize) -> Result<Vec<H::Digest>, MerkleError> {
+        if index >= self.num_leaves {
+            return Err(MerkleError::InvalidIndex(index));
+        }
+
+        let mut path = Vec::with_capacity(self.depth());
+        let mut node = index + self.width();
+        while node > 1 {
+            path.push(self.nodes[node ^ 1]);
+            node >>= 1;
+        }
+
+        Ok(path)
+    }
+
+    /// Checks that `path` authenticates `leaf` at `index` against `root`.
+    ///
+    /// As the root does not commit to the number of leaves, this accepts
+    /// openings of `H::Digest::default()` at padding positions. Callers
+    /// must check that `index` is lower than the number of leaves.
+    pub fn verify(root: &H::Digest, index: usize, leaf: &H::Digest, path: &[H::Digest]) -> bool {
+        if path.len() < usize::BITS as usize && index >> path.len() != 0 {
+            return false;
+        }
+
+        let mut node = *leaf;
+        for (i, sibling) in path.iter().enumerate() {
+            node = if (index >> i) & 1 == 0 {
+                H::merge(&[node, *sibling])
+            } else {
+                H::merge(&[*sibling, node])
+            };
+        }
+
+        node == *root
+    }
+
+    fn width(&self) -> usize {
+        self.nodes.len() / 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{anemoi_64_12_8, anemoi_64_16_8, anemoi_64_8_4};
+    use crate::{griffin_64_12_8, griffin_64_8_4};
+    use crate::{plonky2_poseidon_64_12_8, poseidon_64_12_8, poseidon_64_8_4};
+    use crate::{rescue_64_12_8, rescue_64_8_4, rp64_256, rpo_64_12_8};
+    use rand_core::OsRng;
+
+    fn random_leaves<H: Hasher<Fp>>(num_leaves: usize) -> Vec<H::Digest> {
+        let mut rng = OsRng;
+        (0..num_leaves)
+            .map(|_| H::hash(&[Fp::random(&mut rng)]))
+            .collect()
+    }
+
+    fn check_merkle_tree<H: Hasher<Fp>>() {
+        // Tree over four leaves, computed by hand.
+        let leaves = random_leaves::<H>(4);
+        let tree = MerkleTree::<H>::new(&leaves).unwrap();
+        let left = H::merge(&[leaves[0], leaves[1]]);
+        let right = H::merge(&[leaves[2], leaves[3]]);
+        assert_eq!(*tree.root(), H::merge(&[left, right]));
+        assert_eq!(tree.depth(), 2);
+        assert_eq!(tree.prove(2).unwrap(), [leaves[3], left]);
+
+        for num_leaves in 1..=17 {
+            let leaves = random_leaves::<H>(num_leaves);
+            let tree = MerkleTree::<H>::new(&leaves).unwrap();
+            assert_eq!(tree.leaves(), &leaves[..]);
+            assert_eq!(1 << tree.depth(), num_leaves.next_power_of_two());
+
+            // Leaves are padded with default digests.
+            let mut padded = leaves.clone();
+            padded.resize(num_leaves.next_power_of_two(), H::Digest::default());
+            assert_eq!(MerkleTree::<H>::new(&padded).unwrap().root(), tree.root());
+
+            for (index, leaf) in leaves.iter().enumerate() {
+                let path = tree.prove(index).unwrap();
+                assert_eq!(path.len(), tree.depth());
+                assert!(MerkleTree::<H>::verify(tree.root(), index, leaf, &path));
+
+                // Wrong leaf
+                let other = H::hash(&[Fp::one()]);
+                assert!(!MerkleTree::<H>::verify(tree.root(), index, &other, &path));
+                // Wrong index
+                assert!(!MerkleTree::<H>::verify(
+                    tree.root(),
+                    index + (1 << tree.depth()),
+                    leaf,
+                    &path
+                ));
+                // Wrong path
+                if num_leaves > 1 {
+                    let mut path = path.clone();
+                    path[0] = other;
+                    assert!(!MerkleTree::<H>::verify(tree.root(), index, leaf, &path));
+                }
+            }
+
+            assert_eq!(
+                tree.prove(num_leaves),
+                Err(MerkleError::InvalidIndex(num_leaves))
+            );
+        }
+
+        // The root does not commit to the number of leaves, hence
+        // padding positions can be opened as default digests.
+        let leaves = random_leaves::<H>(3);
+        let tree = MerkleTree::<H>::new(&leaves).unwrap();
+        let path = [leaves[2], H::merge(&[leaves[0], leaves[1]])];
+        assert!(MerkleTree::<H>::verify(
+            tree.root(),
+            3,
+            &H::Digest::default(),
+            &path
+        ));
+
+        assert_eq!(
+            MerkleTree::<H>::new(&[]).unwrap_err(),
+            MerkleError::NoLeaves
+        );
+    }
+
+    #[test]
+    fn test_merkle_tree() {
+        check_merkle_tree::<anemoi_64_8_4::AnemoiHash>();
+        check_merkle_tree::<anemoi_64_12_8::AnemoiHash>();
+        check_merkle_tree::<anemoi_64_16_8::AnemoiHash>();
+        check_merkle_tree::<griffin_64_8_4::GriffinHash>();
+        check_merkle_tree::<griffin_64_12_8::GriffinHash>();
+        check_merkle_tree::<rescue_64_8_4::RescueHash>();
+        check_merkle_tree::<rescue_64_12_8::RescueHash>();
+        check_merkle_tree::<rpo_64_12_8::RpoHash>();
+        check_merkle_tree::<rp64_256::Rp64_256Hash>();
+        check_merkle_tree::<poseidon_64_8_4::PoseidonHash>();
+        check_merkle_tree::<poseidon_64_12_8::PoseidonHash>();
+        check_merkle_tree::<plonky2_poseidon_64_12_8::Plonky2PoseidonHash>();
+    }
+}
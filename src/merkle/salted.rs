@@ -0,0 +1,254 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A `SaltedMerkleTree<H>` is a hiding commitment to a sequence of leaf
+//! values, each being a sequence of field elements.
+//!
+//! Each leaf value is concatenated with a salt of `SALT_SIZE` uniformly
+//! random field elements before being hashed with `H::hash`, and a
+//! `MerkleTree<H>` is built over the resulting digests. Openings carry
+//! the salt of the opened leaf, so that sibling digests along the path
+//! reveal nothing about the other leaf values.
+
+use core::fmt;
+
+use super::{MerkleError, MerkleTree};
+use crate::traits::Hasher;
+
+use cheetah::Fp;
+use rand_core::{CryptoRng, RngCore};
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Number of field elements of a leaf salt.
+pub const SALT_SIZE: usize = 4;
+
+/// Returns the digest of a leaf value concatenated with its salt.
+fn salted_leaf<H: Hasher<Fp>>(values: &[Fp], salt: &[Fp; SALT_SIZE]) -> H::Digest {
+    let mut input = Vec::with_capacity(values.len() + SALT_SIZE);
+    input.extend_from_slice(values);
+    input.extend_from_slice(salt);
+    H::hash(&input)
+}
+
+/// A Merkle tree over salted leaves, with the hasher `H`.
+pub struct SaltedMerkleTree<H: Hasher<Fp>> {
+    values: Vec<Vec<Fp>>,
+    salts: Vec<[Fp; SALT_SIZE]>,
+    tree: MerkleTree<H>,
+}
+
+impl<H: Hasher<Fp>> fmt::Debug for SaltedMerkleTree<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SaltedMerkleTree")
+            .field("values", &self.values)
+            .field("salts", &self.salts)
+            .field("tree", &self.tree)
+            .finish()
+    }
+}
+
+impl<H: Hasher<Fp>> Clone for SaltedMerkleTree<H> {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            salts: self.salts.clone(),
+            tree: self.tree.clone(),
+        }
+    }
+}
+
+impl<H: Hasher<Fp>> SaltedMerkleTree<H> {
+    /// Returns a new salted Merkle tree over the provided leaf
+    /// values, drawing the leaf salts from `rng`.
+    ///
+    /// Fails if `values` is empty.
+    pub fn new<R: RngCore + CryptoRng>(
+        values: &[Vec<Fp>],
+        rng: &mut R,
+    ) -> Result<Self, MerkleError> {
+        let salts: Vec<[Fp; SALT_SIZE]> = values
+            .iter()
+            .map(|_| {
+                let mut salt = [Fp::zero(); SALT_SIZE];
+                salt.iter_mut().for_each(|s| *s = Fp::random(&mut *rng));
+                salt
+            })
+            .collect();
+
+        let leaves: Vec<H::Digest> = values
+            .iter()
+            .zip(&salts)
+            .map(|(value, salt)| salted_leaf::<H>(value, salt))
+            .collect();
+
+        Ok(Self {
+            values: values.to_vec(),
+            salts,
+            tree: MerkleTree::new(&leaves)?,
+        })
+    }
+
+    /// Returns the root of this salted Merkle tree.
+    pub fn root(&self) -> &H::Digest {
+        self.tree.root()
+    }
+
+    /// Returns the depth of this salted Merkle tree.
+    pub fn depth(&self) -> usize {
+        self.tree.depth()
+    }
+
+    /// Opens the leaf at `index`, returning its value, its
+    /// salt and its authentication path.
+    ///
+    /// Fails if `index` does not refer to one of the tree leaves.
+    pub fn open(&self, index: usize) -> Result<SaltedOpening<H>, MerkleError> {
+        let path = self.tree.prove(index)?;
+
+        Ok(SaltedOpening {
+            values: self.values[index].clone(),
+            salt: self.salts[index],
+            path,
+        })
+    }
+
+    /// Checks that `opening` authenticates a leaf at `index` against `root`.
+    pub fn verify(root: &H::Digest, index: usize, opening: &SaltedOpening<H>) -> bool {
+        let leaf = salted_leaf::<H>(&opening.values, &opening.salt);
+        MerkleTree::<H>::verify(root, index, &leaf, &opening.path)
+    }
+}
+
+/// An opening of a leaf of a salted Merkle tree over the hasher `H`.
+pub struct SaltedOpening<H: Hasher<Fp>> {
+    values: Vec<Fp>,
+    salt: [Fp; SALT_SIZE],
+    path: Vec<H::Digest>,
+}
+
+impl<H: Hasher<Fp>> fmt::Debug for SaltedOpening<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SaltedOpening")
+            .field("values", &self.values)
+            .field("salt", &self.salt)
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl<H: Hasher<Fp>> Clone for SaltedOpening<H> {
+    fn clone(&self) -> Self {
+        Self {
+            values: self.values.clone(),
+            salt: self.salt,
+            path: self.path.clone(),
+        }
+    }
+}
+
+impl<H: Hasher<Fp>> SaltedOpening<H> {
+    /// Returns a new opening from a leaf value, its salt and its authentication path.
+    pub fn new(values: Vec<Fp>, salt: [Fp; SALT_SIZE], path: Vec<H::Digest>) -> Self {
+        Self { values, salt, path }
+    }
+
+    /// Returns the opened leaf value.
+    pub fn values(&self) -> &[Fp] {
+        &self.values
+    }
+
+    /// Returns the salt of the opened leaf.
+    pub fn salt(&self) -> &[Fp; SALT_SIZE] {
+        &self.salt
+    }
+
+    /// Returns the authentication path of the opened leaf.
+    pub fn path(&self) -> &[H::Digest] {
+        &self.path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{anemoi_64_12_8, anemoi_64_16_8, anemoi_64_8_4};
+    use crate::{griffin_64_12_8, griffin_64_8_4};
+    use crate::{poseidon_64_12_8, poseidon_64_8_4};
+    use crate::{rescue_64_12_8, rescue_64_8_4, rp64_256, rpo_64_12_8};
+    use rand_core::OsRng;
+
+    fn check_salted_tree<H: Hasher<Fp>>() {
+        let mut rng = OsRng;
+        let values: Vec<Vec<Fp>> = (0..11)
+            .map(|_| (0..5).map(|_| Fp::random(&mut rng)).collect())
+            .collect();
+
+        let tree = SaltedMerkleTree::<H>::new(&values, &mut rng).unwrap();
+        assert_eq!(tree.depth(), 4);
+
+        // Committing twice to the same values yields distinct roots.
+        let other = SaltedMerkleTree::<H>::new(&values, &mut rng).unwrap();
+        assert_ne!(tree.root(), other.root());
+
+        for (index, value) in values.iter().enumerate() {
+            let opening = tree.open(index).unwrap();
+            assert_eq!(opening.values(), &value[..]);
+            assert!(SaltedMerkleTree::<H>::verify(tree.root(), index, &opening));
+            assert!(!SaltedMerkleTree::<H>::verify(
+                other.root(),
+                index,
+                &opening
+            ));
+
+            // The leaf digest is the hash of the value followed by the salt.
+            let mut input = value.clone();
+            input.extend_from_slice(opening.salt());
+            assert!(MerkleTree::<H>::verify(
+                tree.root(),
+                index,
+                &H::hash(&input),
+                opening.path()
+            ));
+
+            // Wrong salt
+            let mut salt = *opening.salt();
+            salt[SALT_SIZE - 1] += Fp::one();
+            let wrong = SaltedOpening::<H>::new(value.clone(), salt, opening.path().to_vec());
+            assert!(!SaltedMerkleTree::<H>::verify(tree.root(), index, &wrong));
+            // Wrong value
+            let mut wrong_value = value.clone();
+            wrong_value[0] += Fp::one();
+            let wrong =
+                SaltedOpening::<H>::new(wrong_value, *opening.salt(), opening.path().to_vec());
+            assert!(!SaltedMerkleTree::<H>::verify(tree.root(), index, &wrong));
+        }
+
+        assert_eq!(tree.open(11).unwrap_err(), MerkleError::InvalidIndex(11));
+        assert_eq!(
+            SaltedMerkleTree::<H>::new(&[], &mut rng).unwrap_err(),
+            MerkleError::NoLeaves
+        );
+    }
+
+    #[test]
+    fn test_salted_merkle_tree() {
+        check_salted_tree::<anemoi_64_8_4::AnemoiHash>();
+        check_salted_tree::<anemoi_64_12_8::AnemoiHash>();
+        check_salted_tree::<anemoi_64_16_8::AnemoiHash>();
+        check_salted_tree::<griffin_64_8_4::GriffinHash>();
+        check_salted_tree::<griffin_64_12_8::GriffinHash>();
+        check_salted_tree::<rescue_64_8_4::RescueHash>();
+        check_salted_tree::<rescue_64_12_8::RescueHash>();
+        check_salted_tree::<rpo_64_12_8::RpoHash>();
+        check_salted_tree::<rp64_256::Rp64_256Hash>();
+        check_salted_tree::<poseidon_64_8_4::PoseidonHash>();
+        check_salted_tree::<poseidon_64_12_8::PoseidonHash>();
+    }
+}
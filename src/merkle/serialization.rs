@@ -0,0 +1,685 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A versioned binary format for digests, authentication paths and batch
+//! proofs of Merkle trees.
+//!
+//! Every encoding starts with a 5-byte header made of the format version,
+//! the kind of encoded object, and the identifier of the hasher: its hash
+//! family, state width and rate width. Integers are little-endian, and
+//! digests are encoded with `Digest::to_bytes`. The header is followed by:
+//!
+//! - for a digest: the 32 bytes of the digest;
+//! - for an authentication path: the depth (1 byte), the leaf index
+//!   (8 bytes) and the `depth` sibling digests, from the leaf level;
+//! - for a batch proof: the depth (1 byte), the number of leaf indices
+//!   (4 bytes), the leaf indices (8 bytes each), the number of sibling
+//!   digests (4 bytes) and the sibling digests, in the order of
+//!   `BatchMerkleProof::siblings`.
+
+use core::fmt::{self, Display};
+
+use super::BatchMerkleProof;
+use crate::traits::{Digest, DigestError, Hasher};
+use crate::{anemoi_64_12_8, anemoi_64_16_8, anemoi_64_8_4};
+use crate::{griffin_64_12_8, griffin_64_8_4};
+use crate::{plonky2_poseidon_64_12_8, poseidon_64_12_8, poseidon_64_8_4};
+use crate::{rescue_64_12_8, rescue_64_8_4, rpo_64_12_8};
+
+use cheetah::Fp;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Version of the serialization format.
+pub const SERIALIZATION_VERSION: u8 = 1;
+
+/// Maximum depth of a serialized authentication path or batch proof.
+pub const MAX_SERIALIZED_DEPTH: usize = 63;
+
+const KIND_DIGEST: u8 = 0;
+const KIND_PATH: u8 = 1;
+const KIND_BATCH_PROOF: u8 = 2;
+
+const HEADER_SIZE: usize = 5;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u8)]
+/// Hash families, as identified in serialized data.
+pub enum HashFamily {
+    /// The Anemoi hash family
+    Anemoi = 1,
+    /// The Griffin hash family
+    Griffin = 2,
+    /// The Rescue-Prime hash family
+    Rescue = 3,
+    /// The Poseidon hash family
+    Poseidon = 4,
+    /// The Rescue Prime Optimized hash family
+    Rpo = 5,
+    // 6 is reserved for a Winterfell-compatible `Rp64_256` instance.
+    /// The Poseidon hash family, with Plonky2's parameters
+    Plonky2Poseidon = 7,
+}
+
+/// Trait identifying a hasher in serialized data.
+pub trait HasherId: Hasher<Fp> {
+    /// Hash family of this hasher.
+    const FAMILY: HashFamily;
+    /// Number of field elements of the state of this hasher.
+    const STATE_WIDTH: u8;
+    /// Number of field elements of the rate of this hasher.
+    const RATE_WIDTH: u8;
+}
+
+impl HasherId for anemoi_64_8_4::AnemoiHash {
+    const FAMILY: HashFamily = HashFamily::Anemoi;
+    const STATE_WIDTH: u8 = anemoi_64_8_4::STATE_WIDTH as u8;
+    const RATE_WIDTH: u8 = anemoi_64_8_4::RATE_WIDTH as u8;
+}
+
+impl HasherId for anemoi_64_12_8::AnemoiHash {
+    const FAMILY: HashFamily = HashFamily::Anemoi;
+    const STATE_WIDTH: u8 = anemoi_64_12_8::STATE_WIDTH as u8;
+    const RATE_WIDTH: u8 = anemoi_64_12_8::RATE_WIDTH as u8;
+}
+
+impl HasherId for anemoi_64_16_8::AnemoiHash {
+    const FAMILY: HashFamily = HashFamily::Anemoi;
+    const STATE_WIDTH: u8 = anemoi_64_16_8::STATE_WIDTH as u8;
+    const RATE_WIDTH: u8 = anemoi_64_16_8::RATE_WIDTH as u8;
+}
+
+impl HasherId for griffin_64_8_4::GriffinHash {
+    const FAMILY: HashFamily = HashFamily::Griffin;
+    const STATE_WIDTH: u8 = griffin_64_8_4::STATE_WIDTH as u8;
+    const RATE_WIDTH: u8 = griffin_64_8_4::RATE_WIDTH as u8;
+}
+
+impl HasherId for griffin_64_12_8::GriffinHash {
+    const FAMILY: HashFamily = HashFamily::Griffin;
+    const STATE_WIDTH: u8 = griffin_64_12_8::STATE_WIDTH as u8;
+    const RATE_WIDTH: u8 = griffin_64_12_8::RATE_WIDTH as u8;
+}
+
+impl HasherId for rescue_64_8_4::RescueHash {
+    const FAMILY: HashFamily = HashFamily::Rescue;
+    const STATE_WIDTH: u8 = rescue_64_8_4::STATE_WIDTH as u8;
+    const RATE_WIDTH: u8 = rescue_64_8_4::RATE_WIDTH as u8;
+}
+
+impl HasherId for rescue_64_12_8::RescueHash {
+    const FAMILY: HashFamily = HashFamily::Rescue;
+    const STATE_WIDTH: u8 = rescue_64_12_8::STATE_WIDTH as u8;
+    const RATE_WIDTH: u8 = rescue_64_12_8::RATE_WIDTH as u8;
+}
+
+impl HasherId for rpo_64_12_8::RpoHash {
+    const FAMILY: HashFamily = HashFamily::Rpo;
+    const STATE_WIDTH: u8 = rpo_64_12_8::STATE_WIDTH as u8;
+    const RATE_WIDTH: u8 = rpo_64_12_8::RATE_WIDTH as u8;
+}
+
+impl HasherId for plonky2_poseidon_64_12_8::Plonky2PoseidonHash {
+    const FAMILY: HashFamily = HashFamily::Plonky2Poseidon;
+    const STATE_WIDTH: u8 = plonky2_poseidon_64_12_8::STATE_WIDTH as u8;
+    const RATE_WIDTH: u8 = plonky2_poseidon_64_12_8::RATE_WIDTH as u8;
+}
+
+impl HasherId for poseidon_64_8_4::PoseidonHash {
+    const FAMILY: HashFamily = HashFamily::Poseidon;
+    const STATE_WIDTH: u8 = poseidon_64_8_4::STATE_WIDTH as u8;
+    const RATE_WIDTH: u8 = poseidon_64_8_4::RATE_WIDTH as u8;
+}
+
+impl HasherId for poseidon_64_12_8::PoseidonHash {
+    const FAMILY: HashFamily = HashFamily::Poseidon;
+    const STATE_WIDTH: u8 = poseidon_64_12_8::STATE_WIDTH as u8;
+    const RATE_WIDTH: u8 = poseidon_64_12_8::RATE_WIDTH as u8;
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// Errors that can occur when deserializing Merkle proofs and digests.
+pub enum DeserializationError {
+    /// The data ends before the end of the encoded object.
+    Truncated,
+    /// The data continues after the end of the encoded object.
+    TrailingBytes,
+    /// The data was encoded with an unsupported format version.
+    UnsupportedVersion(u8),
+    /// The data encodes another kind of object than the expected one.
+    UnexpectedKind(u8),
+    /// The data was encoded for another hasher than the expected one.
+    HasherMismatch,
+    /// The encoded depth exceeds `MAX_SERIALIZED_DEPTH`.
+    InvalidDepth(u8),
+    /// The encoded leaf index does not fit in a tree of the encoded depth.
+    InvalidIndex(u64),
+    /// The digest at the given position in the encoded object is invalid.
+    InvalidDigest(usize, DigestError),
+}
+
+impl Display for DeserializationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeserializationError::Truncated => write!(f, "serialized data is truncated"),
+            DeserializationError::TrailingBytes => {
+                write!(f, "serialized data has trailing bytes")
+            }
+            DeserializationError::UnsupportedVersion(version) => {
+                write!(f, "serialization version {} is not supported", version)
+            }
+            DeserializationError::UnexpectedKind(kind) => {
+                write!(f, "serialized object kind {} is not the expected one", kind)
+            }
+            DeserializationError::HasherMismatch => {
+                write!(f, "serialized data was encoded for another hasher")
+            }
+            DeserializationError::InvalidDepth(depth) => {
+                write!(f, "serialized depth {} is too large", depth)
+            }
+            DeserializationError::InvalidIndex(index) => {
+                write!(f, "serialized leaf index {} is out of bounds", index)
+            }
+            DeserializationError::InvalidDigest(position, error) => {
+                write!(f, "serialized digest {} is invalid: {}", position, error)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DeserializationError {}
+
+/// Returns a new buffer starting with the header of an object of the given kind.
+fn write_header<H: HasherId>(kind: u8, capacity: usize) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(HEADER_SIZE + capacity);
+    bytes.extend_from_slice(&[
+        SERIALIZATION_VERSION,
+        kind,
+        H::FAMILY as u8,
+        H::STATE_WIDTH,
+        H::RATE_WIDTH,
+    ]);
+
+    bytes
+}
+
+/// A cursor over serialized data.
+struct Reader<'a> {
+    bytes: &'a [u8],
+    num_digests: usize,
+}
+
+impl<'a> Reader<'a> {
+    /// Returns a reader over `bytes`, after checking the header
+    /// of an object of the given kind.
+    fn new<H: HasherId>(bytes: &'a [u8], kind: u8) -> Result<Self, DeserializationError> {
+        let mut reader = Self {
+            bytes,
+            num_digests: 0,
+        };
+
+        let header = reader.read_bytes(HEADER_SIZE)?;
+        if header[0] != SERIALIZATION_VERSION {
+            return Err(DeserializationError::UnsupportedVersion(header[0]));
+        }
+        if header[1] != kind {
+            return Err(DeserializationError::UnexpectedKind(header[1]));
+        }
+        if header[2..] != [H::FAMILY as u8, H::STATE_WIDTH, H::RATE_WIDTH] {
+            return Err(DeserializationError::HasherMismatch);
+        }
+
+        Ok(reader)
+    }
+
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], DeserializationError> {
+        if self.bytes.len() < len {
+            return Err(DeserializationError::Truncated);
+        }
+        let (head, tail) = self.bytes.split_at(len);
+        self.bytes = tail;
+
+        Ok(head)
+    }
+
+    fn read_depth(&mut self) -> Result<usize, DeserializationError> {
+        let depth = self.read_bytes(1)?[0];
+        if depth as usize > MAX_SERIALIZED_DEPTH || depth as u32 >= usize::BITS {
+            return Err(DeserializationError::InvalidDepth(depth));
+        }
+
+        Ok(depth as usize)
+    }
+
+    fn read_u32(&mut self) -> Result<usize, DeserializationError> {
+        let bytes = self.read_bytes(4)?;
+        Ok(u32::from_le_bytes(bytes.try_into().unwrap()) as usize)
+    }
+
+    /// Reads a leaf index of a tree of the given depth.
+    fn read_index(&mut self, depth: usize) -> Result<usize, DeserializationError> {
+        let bytes = self.read_bytes(8)?;
+        let index = u64::from_le_bytes(bytes.try_into().unwrap());
+        if index >> depth != 0 {
+            return Err(DeserializationError::InvalidIndex(index));
+        }
+
+        Ok(index as usize)
+    }
+
+    fn read_digest<D: Digest>(&mut self) -> Result<D, DeserializationError> {
+        let bytes = self.read_bytes(32)?;
+        let digest = D::from_bytes(bytes.try_into().unwrap())
+            .map_err(|e| DeserializationError::InvalidDigest(self.num_digests, e))?;
+        self.num_digests += 1;
+
+        Ok(digest)
+    }
+
+    /// Reads `count` digests, after checking that enough data remains.
+    fn read_digests<D: Digest>(&mut self, count: usize) -> Result<Vec<D>, DeserializationError> {
+        if self.bytes.len() / 32 < count {
+            return Err(DeserializationError::Truncated);
+        }
+
+        (0..count).map(|_| self.read_digest()).collect()
+    }
+
+    fn finish(self) -> Result<(), DeserializationError> {
+        if self.bytes.is_empty() {
+            Ok(())
+        } else {
+            Err(DeserializationError::TrailingBytes)
+        }
+    }
+}
+
+/// Serializes a digest of the hasher `H`.
+pub fn serialize_digest<H: HasherId>(digest: &H::Digest) -> Vec<u8> {
+    let mut bytes = write_header::<H>(KIND_DIGEST, 32);
+    bytes.extend_from_slice(&digest.to_bytes());
+
+    bytes
+}
+
+/// Deserializes a digest of the hasher `H`.
+///
+/// Fails if `bytes` is not a valid serialized digest for `H`.
+pub fn deserialize_digest<H: HasherId>(bytes: &[u8]) -> Result<H::Digest, DeserializationError> {
+    let mut reader = Reader::new::<H>(bytes, KIND_DIGEST)?;
+    let digest = reader.read_digest()?;
+    reader.finish()?;
+
+    Ok(digest)
+}
+
+/// Serializes the authentication path `path` of the leaf at `index`,
+/// as returned by `MerkleTree::prove`.
+///
+/// # Panics
+///
+/// Panics if `path` is longer than `MAX_SERIALIZED_DEPTH`, or if `index`
+/// does not fit in a tree whose depth is the length of `path`.
+pub fn serialize_path<H: HasherId>(index: usize, path: &[H::Digest]) -> Vec<u8> {
+    assert!(
+        path.len() <= MAX_SERIALIZED_DEPTH,
+        "authentication path is too long"
+    );
+    assert!(
+        (index as u64) >> path.len() == 0,
+        "leaf index does not fit in the tree"
+    );
+
+    let mut bytes = write_header::<H>(KIND_PATH, 9 + 32 * path.len());
+    bytes.push(path.len() as u8);
+    bytes.extend_from_slice(&(index as u64).to_le_bytes());
+    for digest in path {
+        bytes.extend_from_slice(&digest.to_bytes());
+    }
+
+    bytes
+}
+
+/// Deserializes an authentication path of the hasher `H`,
+/// returning the leaf index and the sibling digests.
+///
+/// Fails if `bytes` is not a valid serialized authentication path for `H`.
+pub fn deserialize_path<H: HasherId>(
+    bytes: &[u8],
+) -> Result<(usize, Vec<H::Digest>), DeserializationError> {
+    let mut reader = Reader::new::<H>(bytes, KIND_PATH)?;
+    let depth = reader.read_depth()?;
+    let index = reader.read_index(depth)?;
+    let path = reader.read_digests(depth)?;
+    reader.finish()?;
+
+    Ok((index, path))
+}
+
+/// Serializes the batch proof `proof` of the leaves at `indices`,
+/// as returned by `MerkleTree::prove_batch`.
+///
+/// # Panics
+///
+/// Panics if the depth of `proof` exceeds `MAX_SERIALIZED_DEPTH`, if
+/// one of `indices` does not fit in a tree of this depth, or if there
+/// are more than `u32::MAX` indices or siblings.
+pub fn serialize_batch_proof<H: HasherId>(
+    indices: &[usize],
+    proof: &BatchMerkleProof<H>,
+) -> Vec<u8> {
+    let depth = proof.depth();
+    let siblings = proof.siblings();
+    assert!(depth <= MAX_SERIALIZED_DEPTH, "batch proof is too deep");
+    assert!(
+        indices.iter().all(|&index| (index as u64) >> depth == 0),
+        "leaf index does not fit in the tree"
+    );
+    assert!(
+        indices.len() <= u32::MAX as usize && siblings.len() <= u32::MAX as usize,
+        "batch proof is too large"
+    );
+
+    let mut bytes = write_header::<H>(
+        KIND_BATCH_PROOF,
+        9 + 8 * indices.len() + 32 * siblings.len(),
+    );
+    bytes.push(depth as u8);
+    bytes.extend_from_slice(&(indices.len() as u32).to_le_bytes());
+    for &index in indices {
+        bytes.extend_from_slice(&(index as u64).to_le_bytes());
+    }
+    bytes.extend_from_slice(&(siblings.len() as u32).to_le_bytes());
+    for digest in siblings {
+        bytes.extend_from_slice(&digest.to_bytes());
+    }
+
+    bytes
+}
+
+/// Deserializes a batch proof of the hasher `H`, returning
+/// the leaf indices and the proof.
+///
+/// Fails if `bytes` is not a valid serialized batch proof for `H`.
+pub fn deserialize_batch_proof<H: HasherId>(
+    bytes: &[u8],
+) -> Result<(Vec<usize>, BatchMerkleProof<H>), DeserializationError> {
+    let mut reader = Reader::new::<H>(bytes, KIND_BATCH_PROOF)?;
+    let depth = reader.read_depth()?;
+
+    let num_indices = reader.read_u32()?;
+    if reader.bytes.len() / 8 < num_indices {
+        return Err(DeserializationError::Truncated);
+    }
+    let indices = (0..num_indices)
+        .map(|_| reader.read_index(depth))
+        .collect::<Result<Vec<usize>, _>>()?;
+
+    let num_siblings = reader.read_u32()?;
+    let siblings = reader.read_digests(num_siblings)?;
+    reader.finish()?;
+
+    Ok((indices, BatchMerkleProof::new(depth, siblings)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::MerkleTree;
+    use super::*;
+
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+
+    // Returns a digest whose bytes all equal `byte`, which is canonical
+    // as long as `byte` is not 0xff.
+    fn digest_of<H: HasherId>(byte: u8) -> H::Digest {
+        H::Digest::from_bytes(&[byte; 32]).unwrap()
+    }
+
+    fn check_roundtrip<H: HasherId>() {
+        let leaves: Vec<H::Digest> = (0..11).map(|i| H::hash(&[Fp::new(i)])).collect();
+        let tree = MerkleTree::<H>::new(&leaves).unwrap();
+
+        let bytes = serialize_digest::<H>(tree.root());
+        assert_eq!(bytes.len(), HEADER_SIZE + 32);
+        assert_eq!(deserialize_digest::<H>(&bytes).unwrap(), *tree.root());
+
+        for (index, leaf) in leaves.iter().enumerate() {
+            let path = tree.prove(index).unwrap();
+            let bytes = serialize_path::<H>(index, &path);
+            assert_eq!(bytes.len(), HEADER_SIZE + 9 + 32 * 4);
+
+            let (decoded_index, decoded_path) = deserialize_path::<H>(&bytes).unwrap();
+            assert_eq!(decoded_index, index);
+            assert_eq!(decoded_path, path);
+            assert!(MerkleTree::<H>::verify(
+                tree.root(),
+                decoded_index,
+                leaf,
+                &decoded_path
+            ));
+        }
+
+        let indices = [9, 0, 3, 4];
+        let proof = tree.prove_batch(&indices).unwrap();
+        let bytes = serialize_batch_proof(&indices, &proof);
+        let (decoded_indices, decoded_proof) = deserialize_batch_proof::<H>(&bytes).unwrap();
+        assert_eq!(decoded_indices, indices);
+        assert!(decoded_proof == proof);
+        let opened: Vec<H::Digest> = indices.iter().map(|&i| leaves[i]).collect();
+        assert!(decoded_proof.verify(tree.root(), &decoded_indices, &opened));
+
+        // Any strict prefix is truncated, and any extension has trailing bytes.
+        for len in 0..bytes.len() {
+            assert_eq!(
+                deserialize_batch_proof::<H>(&bytes[..len]).unwrap_err(),
+                DeserializationError::Truncated
+            );
+        }
+        let mut extended = bytes.clone();
+        extended.push(0);
+        assert_eq!(
+            deserialize_batch_proof::<H>(&extended).unwrap_err(),
+            DeserializationError::TrailingBytes
+        );
+
+        // A batch proof is not an authentication path.
+        assert_eq!(
+            deserialize_path::<H>(&bytes).unwrap_err(),
+            DeserializationError::UnexpectedKind(KIND_BATCH_PROOF)
+        );
+    }
+
+    #[test]
+    fn test_serialization_roundtrip() {
+        check_roundtrip::<anemoi_64_8_4::AnemoiHash>();
+        check_roundtrip::<anemoi_64_12_8::AnemoiHash>();
+        check_roundtrip::<anemoi_64_16_8::AnemoiHash>();
+        check_roundtrip::<griffin_64_8_4::GriffinHash>();
+        check_roundtrip::<griffin_64_12_8::GriffinHash>();
+        check_roundtrip::<rescue_64_8_4::RescueHash>();
+        check_roundtrip::<rescue_64_12_8::RescueHash>();
+        check_roundtrip::<rpo_64_12_8::RpoHash>();
+        check_roundtrip::<poseidon_64_8_4::PoseidonHash>();
+        check_roundtrip::<poseidon_64_12_8::PoseidonHash>();
+        check_roundtrip::<plonky2_poseidon_64_12_8::Plonky2PoseidonHash>();
+    }
+
+    #[test]
+    fn test_hasher_ids() {
+        let ids = [
+            serialize_digest::<anemoi_64_8_4::AnemoiHash>(&Default::default())[..HEADER_SIZE]
+                .to_vec(),
+            serialize_digest::<anemoi_64_12_8::AnemoiHash>(&Default::default())[..HEADER_SIZE]
+                .to_vec(),
+            serialize_digest::<anemoi_64_16_8::AnemoiHash>(&Default::default())[..HEADER_SIZE]
+                .to_vec(),
+            serialize_digest::<griffin_64_8_4::GriffinHash>(&Default::default())[..HEADER_SIZE]
+                .to_vec(),
+            serialize_digest::<griffin_64_12_8::GriffinHash>(&Default::default())[..HEADER_SIZE]
+                .to_vec(),
+            serialize_digest::<rescue_64_8_4::RescueHash>(&Default::default())[..HEADER_SIZE]
+                .to_vec(),
+            serialize_digest::<rescue_64_12_8::RescueHash>(&Default::default())[..HEADER_SIZE]
+                .to_vec(),
+            serialize_digest::<poseidon_64_8_4::PoseidonHash>(&Default::default())[..HEADER_SIZE]
+                .to_vec(),
+            serialize_digest::<poseidon_64_12_8::PoseidonHash>(&Default::default())[..HEADER_SIZE]
+                .to_vec(),
+            serialize_digest::<rpo_64_12_8::RpoHash>(&Default::default())[..HEADER_SIZE].to_vec(),
+            serialize_digest::<plonky2_poseidon_64_12_8::Plonky2PoseidonHash>(&Default::default())
+                [..HEADER_SIZE]
+                .to_vec(),
+        ];
+
+        assert_eq!(
+            ids,
+            [
+                [1, 0, 1, 8, 4],
+                [1, 0, 1, 12, 8],
+                [1, 0, 1, 16, 8],
+                [1, 0, 2, 8, 4],
+                [1, 0, 2, 12, 8],
+                [1, 0, 3, 8, 4],
+                [1, 0, 3, 12, 8],
+                [1, 0, 4, 8, 4],
+                [1, 0, 4, 12, 8],
+                [1, 0, 5, 12, 8],
+                [1, 0, 7, 12, 8],
+            ]
+        );
+
+        // Data encoded for one hasher cannot be decoded for another.
+        let bytes = serialize_digest::<rescue_64_8_4::RescueHash>(&Default::default());
+        assert_eq!(
+            deserialize_digest::<rescue_64_12_8::RescueHash>(&bytes).unwrap_err(),
+            DeserializationError::HasherMismatch
+        );
+        assert_eq!(
+            deserialize_digest::<poseidon_64_8_4::PoseidonHash>(&bytes).unwrap_err(),
+            DeserializationError::HasherMismatch
+        );
+    }
+
+    #[test]
+    fn test_golden_digest() {
+        type H = anemoi_64_8_4::AnemoiHash;
+
+        let mut expected = vec![0x01, 0x00, 0x01, 0x08, 0x04];
+        expected.extend_from_slice(&[0x5a; 32]);
+        assert_eq!(serialize_digest::<H>(&digest_of::<H>(0x5a)), expected);
+        assert_eq!(
+            deserialize_digest::<H>(&expected).unwrap(),
+            digest_of::<H>(0x5a)
+        );
+    }
+
+    #[test]
+    fn test_golden_path() {
+        type H = poseidon_64_12_8::PoseidonHash;
+
+        let path = [digest_of::<H>(0x11), digest_of::<H>(0x22)];
+        let mut expected = vec![
+            0x01, 0x01, 0x04, 0x0c, 0x08, // header
+            0x02, // depth
+            0x03, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // index
+        ];
+        expected.extend_from_slice(&[0x11; 32]);
+        expected.extend_from_slice(&[0x22; 32]);
+
+        assert_eq!(serialize_path::<H>(3, &path), expected);
+        assert_eq!(
+            deserialize_path::<H>(&expected).unwrap(),
+            (3, path.to_vec())
+        );
+
+        // A single-leaf tree has an empty path.
+        assert_eq!(
+            serialize_path::<H>(0, &[]),
+            [0x01, 0x01, 0x04, 0x0c, 0x08, 0x00, 0, 0, 0, 0, 0, 0, 0, 0]
+        );
+
+        // Non-canonical limb in the second digest
+        let mut bytes = expected.clone();
+        bytes[14 + 32 + 8..14 + 32 + 16].copy_from_slice(&[0xff; 8]);
+        assert_eq!(
+            deserialize_path::<H>(&bytes).unwrap_err(),
+            DeserializationError::InvalidDigest(1, DigestError::NonCanonicalElement(1))
+        );
+
+        // Index out of bounds
+        let mut bytes = expected.clone();
+        bytes[6] = 0x04;
+        assert_eq!(
+            deserialize_path::<H>(&bytes).unwrap_err(),
+            DeserializationError::InvalidIndex(4)
+        );
+
+        // Depth too large
+        let mut bytes = expected.clone();
+        bytes[5] = 64;
+        assert_eq!(
+            deserialize_path::<H>(&bytes).unwrap_err(),
+            DeserializationError::InvalidDepth(64)
+        );
+
+        // Unknown version
+        let mut bytes = expected;
+        bytes[0] = 0x02;
+        assert_eq!(
+            deserialize_path::<H>(&bytes).unwrap_err(),
+            DeserializationError::UnsupportedVersion(2)
+        );
+    }
+
+    #[test]
+    fn test_golden_batch_proof() {
+        type H = griffin_64_8_4::GriffinHash;
+
+        let proof = BatchMerkleProof::<H>::new(3, vec![digest_of::<H>(0x33), digest_of::<H>(0x44)]);
+        let mut expected = vec![
+            0x01, 0x02, 0x02, 0x08, 0x04, // header
+            0x03, // depth
+            0x02, 0x00, 0x00, 0x00, // number of indices
+            0x05, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // first index
+            0x01, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, // second index
+            0x02, 0x00, 0x00, 0x00, // number of siblings
+        ];
+        expected.extend_from_slice(&[0x33; 32]);
+        expected.extend_from_slice(&[0x44; 32]);
+
+        assert_eq!(serialize_batch_proof(&[5, 1], &proof), expected);
+        let (indices, decoded) = deserialize_batch_proof::<H>(&expected).unwrap();
+        assert_eq!(indices, [5, 1]);
+        assert!(decoded == proof);
+
+        // Counts larger than the remaining data are truncated.
+        let mut bytes = expected.clone();
+        bytes[6..10].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert_eq!(
+            deserialize_batch_proof::<H>(&bytes).unwrap_err(),
+            DeserializationError::Truncated
+        );
+        let mut bytes = expected.clone();
+        bytes[26..30].copy_from_slice(&3u32.to_le_bytes());
+        assert_eq!(
+            deserialize_batch_proof::<H>(&bytes).unwrap_err(),
+            DeserializationError::Truncated
+        );
+
+        // Non-canonical limb in the first digest
+        let mut bytes = expected;
+        bytes[30..38].copy_from_slice(&[0xff; 8]);
+        assert_eq!(
+            deserialize_batch_proof::<H>(&bytes).unwrap_err(),
+            DeserializationError::InvalidDigest(0, DigestError::NonCanonicalElement(0))
+        );
+    }
+}
@@ -0,0 +1,447 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A `SparseMerkleTree<H, DEPTH>` commits to a map from `DEPTH`-bit keys
+//! to digests, as a Merkle tree with `2**DEPTH` leaves in which only the
+//! non-empty nodes are stored.
+//!
+//! Keys are 256-bit little-endian integers encoded as `[u8; 32]`, whose
+//! bits above `DEPTH` must be zero; the key of a field element is its
+//! canonical encoding, to be used with a depth of 64. The leaf at index
+//! `key` holds the digest associated to `key`, or `H::Digest::default()`
+//! if there is none. Empty subtrees of height `h` hence have the digest
+//! `E_h`, with `E_0 = H::Digest::default()` and
+//! `E_{h+1} = H::merge(E_h, E_h)`.
+//!
+//! Proofs only carry the siblings which are not empty subtrees, along
+//! with a bitmap of their heights. Non-membership of a key is proven by
+//! opening its leaf to `H::Digest::default()`.
+
+use core::fmt;
+
+use super::{empty_digests, MerkleError};
+use crate::traits::Hasher;
+
+use cheetah::Fp;
+
+#[cfg(not(feature = "std"))]
+use alloc::{collections::BTreeMap, vec::Vec};
+#[cfg(feature = "std")]
+use std::collections::BTreeMap;
+
+/// Maximal depth of a sparse Merkle tree.
+pub const MAX_SPARSE_DEPTH: usize = 256;
+
+/// A key of a sparse Merkle tree, as a 256-bit little-endian integer.
+pub type SparseMerkleKey = [u8; 32];
+
+/// Returns the sparse Merkle tree key of a field element.
+pub fn key_from_element(element: &Fp) -> SparseMerkleKey {
+    let mut key = [0u8; 32];
+    key[..8].copy_from_slice(&element.to_bytes());
+    key
+}
+
+/// Returns the bit of `key` at position `i`.
+fn bit(key: &SparseMerkleKey, i: usize) -> bool {
+    (key[i / 8] >> (i % 8)) & 1 == 1
+}
+
+/// Returns `key` with the bit at position `i` flipped.
+fn flip_bit(key: &SparseMerkleKey, i: usize) -> SparseMerkleKey {
+    let mut key = *key;
+    key[i / 8] ^= 1 << (i % 8);
+    key
+}
+
+/// Returns `key` with its `n` least significant bits cleared, which
+/// identifies the node of height `n` above the leaf at `key`.
+fn clear_low_bits(key: &SparseMerkleKey, n: usize) -> SparseMerkleKey {
+    let mut key = *key;
+    for byte in key.iter_mut().take(n / 8) {
+        *byte = 0;
+    }
+    if n % 8 != 0 {
+        key[n / 8] &= 0xff << (n % 8);
+    }
+    key
+}
+
+/// A sparse Merkle tree of depth `DEPTH` over the hasher `H`.
+pub struct SparseMerkleTree<H: Hasher<Fp>, const DEPTH: usize> {
+    // Non-empty nodes, indexed by their height and their key.
+    nodes: BTreeMap<(usize, SparseMerkleKey), H::Digest>,
+    empty_digests: Vec<H::Digest>,
+}
+
+impl<H: Hasher<Fp>, const DEPTH: usize> fmt::Debug for SparseMerkleTree<H, DEPTH> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SparseMerkleTree")
+            .field("nodes", &self.nodes)
+            .finish()
+    }
+}
+
+impl<H: Hasher<Fp>, const DEPTH: usize> Clone for SparseMerkleTree<H, DEPTH> {
+    fn clone(&self) -> Self {
+        Self {
+            nodes: self.nodes.clone(),
+            empty_digests: self.empty_digests.clone(),
+        }
+    }
+}
+
+impl<H: Hasher<Fp>, const DEPTH: usize> Default for SparseMerkleTree<H, DEPTH> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H: Hasher<Fp>, const DEPTH: usize> SparseMerkleTree<H, DEPTH> {
+    /// Returns a new empty sparse Merkle tree.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `DEPTH` is zero or greater than `MAX_SPARSE_DEPTH`.
+    pub fn new() -> Self {
+        assert!(
+            DEPTH > 0 && DEPTH <= MAX_SPARSE_DEPTH,
+            "invalid sparse Merkle tree depth"
+        );
+
+        Self {
+            nodes: BTreeMap::new(),
+            empty_digests: empty_digests::<H>(DEPTH),
+        }
+    }
+
+    /// Returns the root of this sparse Merkle tree.
+    pub fn root(&self) -> H::Digest {
+        self.node(DEPTH, &[0u8; 32])
+    }
+
+    /// Returns the digest of an empty subtree of height `height`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `height` is greater than `DEPTH`.
+    pub fn empty_digest(&self, height: usize) -> H::Digest {
+        self.empty_digests[height]
+    }
+
+    /// Returns the value associated to `key`, if any.
+    pub fn get(&self, key: &SparseMerkleKey) -> Option<H::Digest> {
+        self.nodes.get(&(0, *key)).copied()
+    }
+
+    /// Associates `value` to `key`, returning the previous value, if any.
+    ///
+    /// As empty leaves hold `H::Digest::default()`, inserting this
+    /// digest is equivalent to removing `key`.
+    ///
+    /// Fails if `key` has non-zero bits above `DEPTH`.
+    pub fn insert(
+        &mut self,
+        key: &SparseMerkleKey,
+        value: H::Digest,
+    ) -> Result<Option<H::Digest>, MerkleError> {
+        Self::check_key(key)?;
+
+        let previous = self.get(key);
+        self.set_node(0, *key, value);
+
+        let mut node = *key;
+        for h in 0..DEPTH {
+            let sibling = flip_bit(&node, h);
+            let pair = if bit(&node, h) {
+                [self.node(h, &sibling), self.node(h, &node)]
+            } else {
+                [self.node(h, &node), self.node(h, &sibling)]
+            };
+
+            node = clear_low_bits(&node, h + 1);
+            self.set_node(h + 1, node, H::merge(&pair));
+        }
+
+        Ok(previous)
+    }
+
+    /// Removes the value associated to `key`, returning it, if any.
+    ///
+    /// Fails if `key` has non-zero bits above `DEPTH`.
+    pub fn remove(&mut self, key: &SparseMerkleKey) -> Result<Option<H::Digest>, MerkleError> {
+        self.insert(key, H::Digest::default())
+    }
+
+    /// Returns a proof of the value associated to `key`, or of the
+    /// absence of such value.
+    ///
+    /// Fails if `key` has non-zero bits above `DEPTH`.
+    pub fn prove(&self, key: &SparseMerkleKey) -> Result<SparseMerkleProof<H>, MerkleError> {
+        Self::check_key(key)?;
+
+        let mut bitmap = [0u8; 32];
+        let mut siblings = Vec::new();
+        for h in 0..DEPTH {
+            let sibling = flip_bit(&clear_low_bits(key, h), h);
+            if let Some(digest) = self.nodes.get(&(h, sibling)) {
+                bitmap[h / 8] |= 1 << (h % 8);
+                siblings.push(*digest);
+            }
+        }
+
+        Ok(SparseMerkleProof { bitmap, siblings })
+    }
+
+    /// Checks that `proof` authenticates `value` as the value associated
+    /// to `key` against `root`, a value of `None` meaning that `key` is
+    /// absent from the tree.
+    pub fn verify(
+        root: &H::Digest,
+        key: &SparseMerkleKey,
+        value: Option<&H::Digest>,
+        proof: &SparseMerkleProof<H>,
+    ) -> bool {
+        if Self::check_key(key).is_err() {
+            return false;
+        }
+        if (DEPTH..MAX_SPARSE_DEPTH).any(|h| bit(&proof.bitmap, h)) {
+            return false;
+        }
+
+        // Only the empty subtrees up to the highest empty sibling are needed.
+        let highest_empty = (0..DEPTH).rev().find(|&h| !bit(&proof.bitmap, h));
+        let empty = empty_digests::<H>(highest_empty.unwrap_or(0))
+            .into_iter()
+            .chain(core::iter::repeat(H::Digest::default()));
+
+        let mut siblings = proof.siblings.iter();
+        let mut node = value.copied().unwrap_or_default();
+        for (h, empty) in empty.take(DEPTH).enumerate() {
+            let sibling = if bit(&proof.bitmap, h) {
+                match siblings.next() {
+                    Some(sibling) => *sibling,
+                    None => return false,
+                }
+            } else {
+                empty
+            };
+
+            node = if bit(key, h) {
+                H::merge(&[sibling, node])
+            } else {
+                H::merge(&[node, sibling])
+            };
+        }
+
+        siblings.next().is_none() && node == *root
+    }
+
+    fn check_key(key: &SparseMerkleKey) -> Result<(), MerkleError> {
+        if (DEPTH..MAX_SPARSE_DEPTH).any(|i| bit(key, i)) {
+            Err(MerkleError::InvalidKey)
+        } else {
+            Ok(())
+        }
+    }
+
+    fn node(&self, height: usize, key: &SparseMerkleKey) -> H::Digest {
+        match self.nodes.get(&(height, *key)) {
+            Some(digest) => *digest,
+            None => self.empty_digests[height],
+        }
+    }
+
+    fn set_node(&mut self, height: usize, key: SparseMerkleKey, digest: H::Digest) {
+        if digest == self.empty_digests[height] {
+            self.nodes.remove(&(height, key));
+        } else {
+            self.nodes.insert((height, key), digest);
+        }
+    }
+}
+
+/// A membership or non-membership proof for a sparse Merkle tree over the hasher `H`.
+pub struct SparseMerkleProof<H: Hasher<Fp>> {
+    // Bit h is set if the sibling at height h is not an empty subtree.
+    bitmap: [u8; 32],
+    siblings: Vec<H::Digest>,
+}
+
+impl<H: Hasher<Fp>> fmt::Debug for SparseMerkleProof<H> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SparseMerkleProof")
+            .field("bitmap", &self.bitmap)
+            .field("siblings", &self.siblings)
+            .finish()
+    }
+}
+
+impl<H: Hasher<Fp>> Clone for SparseMerkleProof<H> {
+    fn clone(&self) -> Self {
+        Self {
+            bitmap: self.bitmap,
+            siblings: self.siblings.clone(),
+        }
+    }
+}
+
+impl<H: Hasher<Fp>> SparseMerkleProof<H> {
+    /// Returns the non-empty sibling digests of this proof, from the leaf level.
+    pub fn siblings(&self) -> &[H::Digest] {
+        &self.siblings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::MerkleTree;
+    use super::*;
+    use crate::{anemoi_64_12_8, anemoi_64_16_8, anemoi_64_8_4};
+    use crate::{griffin_64_12_8, griffin_64_8_4};
+    use crate::{poseidon_64_12_8, poseidon_64_8_4};
+    use crate::{rescue_64_12_8, rescue_64_8_4, rp64_256, rpo_64_12_8};
+    use rand_core::{OsRng, RngCore};
+
+    fn check_sparse_merkle_tree<H: Hasher<Fp>>() {
+        let mut rng = OsRng;
+
+        // A small sparse tree matches the dense tree over all its leaves.
+        let mut tree = SparseMerkleTree::<H, 4>::new();
+        let mut leaves = [H::Digest::default(); 16];
+        assert_eq!(tree.root(), *MerkleTree::<H>::new(&leaves).unwrap().root());
+        for i in [3, 12, 7, 3] {
+            let value = H::hash(&[Fp::random(&mut rng)]);
+            let mut key = [0u8; 32];
+            key[0] = i;
+            let previous = tree.get(&key);
+            assert_eq!(tree.insert(&key, value).unwrap(), previous);
+            leaves[i as usize] = value;
+            assert_eq!(tree.root(), *MerkleTree::<H>::new(&leaves).unwrap().root());
+        }
+        assert_eq!(
+            tree.insert(&[16; 32], H::Digest::default()),
+            Err(MerkleError::InvalidKey)
+        );
+
+        // Tree keyed by field elements
+        let mut tree = SparseMerkleTree::<H, 64>::new();
+        let empty_root = tree.root();
+        assert_eq!(empty_root, tree.empty_digest(64));
+        let keys: Vec<SparseMerkleKey> = (0..10)
+            .map(|_| key_from_element(&Fp::random(&mut rng)))
+            .collect();
+        let values: Vec<H::Digest> = (0..10).map(|_| H::hash(&[Fp::random(&mut rng)])).collect();
+        for (key, value) in keys.iter().zip(&values) {
+            assert_eq!(tree.insert(key, *value).unwrap(), None);
+        }
+
+        let root = tree.root();
+        for (key, value) in keys.iter().zip(&values) {
+            assert_eq!(tree.get(key), Some(*value));
+            let proof = tree.prove(key).unwrap();
+            assert!(SparseMerkleTree::<H, 64>::verify(
+                &root,
+                key,
+                Some(value),
+                &proof
+            ));
+            assert!(!SparseMerkleTree::<H, 64>::verify(&root, key, None, &proof));
+            assert!(!SparseMerkleTree::<H, 64>::verify(
+                &root,
+                key,
+                Some(&H::hash(&[])),
+                &proof
+            ));
+        }
+
+        // Non-membership
+        let absent = key_from_element(&Fp::random(&mut rng));
+        let proof = tree.prove(&absent).unwrap();
+        assert!(SparseMerkleTree::<H, 64>::verify(
+            &root, &absent, None, &proof
+        ));
+        assert!(!SparseMerkleTree::<H, 64>::verify(
+            &root,
+            &absent,
+            Some(&values[0]),
+            &proof
+        ));
+
+        // Update
+        let value = H::hash(&[Fp::one()]);
+        assert_eq!(tree.insert(&keys[0], value).unwrap(), Some(values[0]));
+        let proof = tree.prove(&keys[0]).unwrap();
+        assert!(SparseMerkleTree::<H, 64>::verify(
+            &tree.root(),
+            &keys[0],
+            Some(&value),
+            &proof
+        ));
+        assert_ne!(tree.root(), root);
+
+        // Deleting all keys restores the empty tree.
+        for key in keys.iter() {
+            assert!(tree.remove(key).unwrap().is_some());
+            assert_eq!(tree.get(key), None);
+        }
+        assert_eq!(tree.root(), empty_root);
+        assert!(tree.nodes.is_empty());
+
+        // Tree keyed by 256-bit values
+        let mut tree = SparseMerkleTree::<H, 256>::new();
+        let mut key = [0u8; 32];
+        rng.fill_bytes(&mut key);
+        let value = H::hash(&[Fp::random(&mut rng)]);
+        tree.insert(&key, value).unwrap();
+        let proof = tree.prove(&key).unwrap();
+        assert_eq!(proof.siblings().len(), 0);
+        assert!(SparseMerkleTree::<H, 256>::verify(
+            &tree.root(),
+            &key,
+            Some(&value),
+            &proof
+        ));
+        let other = flip_bit(&key, 200);
+        let proof = tree.prove(&other).unwrap();
+        assert_eq!(proof.siblings().len(), 1);
+        assert!(SparseMerkleTree::<H, 256>::verify(
+            &tree.root(),
+            &other,
+            None,
+            &proof
+        ));
+    }
+
+    #[test]
+    fn test_sparse_merkle_tree() {
+        check_sparse_merkle_tree::<anemoi_64_8_4::AnemoiHash>();
+        check_sparse_merkle_tree::<anemoi_64_12_8::AnemoiHash>();
+        check_sparse_merkle_tree::<anemoi_64_16_8::AnemoiHash>();
+        check_sparse_merkle_tree::<griffin_64_8_4::GriffinHash>();
+        check_sparse_merkle_tree::<griffin_64_12_8::GriffinHash>();
+        check_sparse_merkle_tree::<rescue_64_8_4::RescueHash>();
+        check_sparse_merkle_tree::<rescue_64_12_8::RescueHash>();
+        check_sparse_merkle_tree::<rpo_64_12_8::RpoHash>();
+        check_sparse_merkle_tree::<rp64_256::Rp64_256Hash>();
+        check_sparse_merkle_tree::<poseidon_64_8_4::PoseidonHash>();
+        check_sparse_merkle_tree::<poseidon_64_12_8::PoseidonHash>();
+    }
+
+    #[test]
+    fn test_sparse_merkle_keys() {
+        let mut key = [0u8; 32];
+        key[1] = 0b1011_0110;
+        assert!(bit(&key, 9) && !bit(&key, 8));
+        assert_eq!(clear_low_bits(&key, 10)[1], 0b1011_0100);
+        assert_eq!(clear_low_bits(&key, 16), [0u8; 32]);
+        assert_eq!(flip_bit(&key, 8)[1], 0b1011_0111);
+
+        assert_eq!(key_from_element(&Fp::new(0x0102))[..2], [2, 1]);
+    }
+}
@@ -0,0 +1,161 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Batched leaf updates of a `MerkleTree<H>`.
+//!
+//! All leaves of a batch are replaced first, after which the internal
+//! nodes having at least one updated leaf below them are recomputed level
+//! by level, each of them with a single call to `H::merge`.
+
+use super::{MerkleError, MerkleTree};
+use crate::traits::Hasher;
+
+use cheetah::Fp;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+impl<H: Hasher<Fp>> MerkleTree<H> {
+    /// Replaces the leaf at `index` with `leaf`, returning the
+    /// number of `H::merge` calls performed.
+    ///
+    /// Fails if `index` does not refer to one of the tree leaves.
+    pub fn update(&mut self, index: usize, leaf: H::Digest) -> Result<usize, MerkleError> {
+        self.update_batch(&[(index, leaf)])
+    }
+
+    /// Applies a batch of `(index, leaf)` updates to this tree, returning
+    /// the number of `H::merge` calls performed. If an index appears
+    /// several times, the last update of this index is kept.
+    ///
+    /// Fails if one of the indices does not refer to one of the tree
+    /// leaves, in which case the tree is left unchanged.
+    pub fn update_batch(&mut self, updates: &[(usize, H::Digest)]) -> Result<usize, MerkleError> {
+        if let Some((index, _)) = updates.iter().find(|(i, _)| *i >= self.num_leaves) {
+            return Err(MerkleError::InvalidIndex(*index));
+        }
+
+        let width = self.width();
+        let mut nodes = Vec::with_capacity(updates.len());
+        for (index, leaf) in updates {
+            self.nodes[index + width] = *leaf;
+            nodes.push((index + width) >> 1);
+        }
+        nodes.sort_unstable();
+        nodes.dedup();
+
+        let mut num_merges = 0;
+        while !nodes.is_empty() && nodes[0] > 0 {
+            for &node in nodes.iter() {
+                self.nodes[node] = H::merge(&[self.nodes[2 * node], self.nodes[2 * node + 1]]);
+            }
+            num_merges += nodes.len();
+
+            // Parents of sorted nodes are sorted, possibly with duplicates.
+            nodes.iter_mut().for_each(|node| *node >>= 1);
+            nodes.dedup();
+        }
+
+        Ok(num_merges)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{anemoi_64_12_8, anemoi_64_16_8, anemoi_64_8_4};
+    use crate::{griffin_64_12_8, griffin_64_8_4};
+    use crate::{plonky2_poseidon_64_12_8, poseidon_64_12_8, poseidon_64_8_4};
+    use crate::{rescue_64_12_8, rescue_64_8_4, rp64_256, rpo_64_12_8};
+    use rand_core::{OsRng, RngCore};
+
+    fn check_update_batch<H: Hasher<Fp>>() {
+        let mut rng = OsRng;
+        let mut leaves: Vec<H::Digest> =
+            (0..50).map(|_| H::hash(&[Fp::random(&mut rng)])).collect();
+        let mut tree = MerkleTree::<H>::new(&leaves).unwrap();
+        assert_eq!(tree.depth(), 6);
+
+        // Two sibling leaves share all their ancestors.
+        let updates = [(6, H::hash(&[Fp::zero()])), (7, H::hash(&[Fp::one()]))];
+        assert_eq!(tree.update_batch(&updates).unwrap(), 6);
+        leaves[6] = updates[0].1;
+        leaves[7] = updates[1].1;
+        assert_eq!(tree.root(), MerkleTree::<H>::new(&leaves).unwrap().root());
+
+        // Leaves in both halves only share the root.
+        assert_eq!(tree.update(0, leaves[1]).unwrap(), 6);
+        leaves[0] = leaves[1];
+        let updates = [(0, leaves[2]), (32, leaves[3])];
+        assert_eq!(tree.update_batch(&updates).unwrap(), 11);
+        leaves[0] = leaves[2];
+        leaves[32] = leaves[3];
+        assert_eq!(tree.root(), MerkleTree::<H>::new(&leaves).unwrap().root());
+
+        for _ in 0..10 {
+            let updates: Vec<(usize, H::Digest)> = (0..10)
+                .map(|_| {
+                    let index = rng.next_u32() as usize % leaves.len();
+                    (index, H::hash(&[Fp::random(&mut rng)]))
+                })
+                .collect();
+
+            let mut ancestors = Vec::new();
+            for (index, leaf) in updates.iter() {
+                leaves[*index] = *leaf;
+                let mut node = (index + 64) >> 1;
+                while node > 0 {
+                    ancestors.push(node);
+                    node >>= 1;
+                }
+            }
+            ancestors.sort_unstable();
+            ancestors.dedup();
+
+            assert_eq!(tree.update_batch(&updates).unwrap(), ancestors.len());
+            assert_eq!(tree.leaves(), &leaves[..]);
+            assert_eq!(tree.root(), MerkleTree::<H>::new(&leaves).unwrap().root());
+        }
+
+        // Invalid updates leave the tree unchanged.
+        let root = *tree.root();
+        let updates = [(0, H::hash(&[])), (50, H::hash(&[]))];
+        assert_eq!(
+            tree.update_batch(&updates).unwrap_err(),
+            MerkleError::InvalidIndex(50)
+        );
+        assert_eq!(*tree.root(), root);
+        assert_eq!(tree.update_batch(&[]).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_update_batch() {
+        check_update_batch::<anemoi_64_8_4::AnemoiHash>();
+        check_update_batch::<anemoi_64_12_8::AnemoiHash>();
+        check_update_batch::<anemoi_64_16_8::AnemoiHash>();
+        check_update_batch::<griffin_64_8_4::GriffinHash>();
+        check_update_batch::<griffin_64_12_8::GriffinHash>();
+        check_update_batch::<rescue_64_8_4::RescueHash>();
+        check_update_batch::<rescue_64_12_8::RescueHash>();
+        check_update_batch::<rpo_64_12_8::RpoHash>();
+        check_update_batch::<rp64_256::Rp64_256Hash>();
+        check_update_batch::<poseidon_64_8_4::PoseidonHash>();
+        check_update_batch::<poseidon_64_12_8::PoseidonHash>();
+        check_update_batch::<plonky2_poseidon_64_12_8::Plonky2PoseidonHash>();
+    }
+
+    #[test]
+    fn test_update_single_leaf_tree() {
+        type H = anemoi_64_8_4::AnemoiHash;
+
+        let mut tree = MerkleTree::<H>::new(&[H::hash(&[])]).unwrap();
+        let leaf = H::hash(&[Fp::one()]);
+        assert_eq!(tree.update(0, leaf).unwrap(), 0);
+        assert_eq!(*tree.root(), leaf);
+    }
+}
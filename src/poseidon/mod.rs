@@ -15,3 +15,8 @@ pub mod poseidon_64_12_8;
 /// primefield with state width 8 and rate 4.
 #[cfg(feature = "f64")]
 pub mod poseidon_64_8_4;
+
+/// The Poseidon hash function over Fp's small primefield with
+/// state width 12 and rate 8, compatible with Plonky2.
+#[cfg(feature = "f64")]
+pub mod plonky2_poseidon_64_12_8;
@@ -0,0 +1,160 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Digest trait implementation for Plonky2 Poseidon
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+use super::DIGEST_SIZE;
+use crate::f64_utils::fp_from_canonical_bytes;
+use crate::traits::{Digest, DigestError};
+
+use cheetah::Fp;
+use subtle::{Choice, ConstantTimeEq};
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+/// A Plonky2 Poseidon Digest for the Plonky2 Poseidon Hash over Fp
+pub struct Plonky2PoseidonDigest([Fp; DIGEST_SIZE]);
+
+impl Plonky2PoseidonDigest {
+    /// Returns a new Digest from a provided array
+    pub fn new(value: [Fp; DIGEST_SIZE]) -> Self {
+        Self(value)
+    }
+
+    /// Returns a reference to the wrapped digest
+    pub fn as_elements(&self) -> &[Fp; DIGEST_SIZE] {
+        &self.0
+    }
+
+    /// Returns the wrapped digest
+    pub fn to_elements(&self) -> [Fp; DIGEST_SIZE] {
+        self.0
+    }
+
+    /// Returns a `Vec<Fp>` from the provided digest slice
+    pub fn digests_to_elements(digests: &[Self]) -> Vec<Fp> {
+        let mut res = Vec::with_capacity(digests.len() * DIGEST_SIZE);
+        for digest in digests {
+            for i in 0..DIGEST_SIZE {
+                res.push(digest.0[i]);
+            }
+        }
+
+        res
+    }
+}
+
+impl Default for Plonky2PoseidonDigest {
+    fn default() -> Self {
+        Plonky2PoseidonDigest([Fp::default(); DIGEST_SIZE])
+    }
+}
+
+impl ConstantTimeEq for Plonky2PoseidonDigest {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0[..].ct_eq(&other.0[..])
+    }
+}
+
+impl Digest for Plonky2PoseidonDigest {
+    fn to_bytes(&self) -> [u8; 32] {
+        let mut digest = [0u8; 32];
+        digest[0..8].copy_from_slice(&self.0[0].to_bytes());
+        digest[8..16].copy_from_slice(&self.0[1].to_bytes());
+        digest[16..24].copy_from_slice(&self.0[2].to_bytes());
+        digest[24..32].copy_from_slice(&self.0[3].to_bytes());
+
+        digest
+    }
+
+    fn from_bytes(bytes: &[u8; 32]) -> Result<Self, DigestError> {
+        let mut digest = [Fp::zero(); DIGEST_SIZE];
+        for (i, (element, chunk)) in digest.iter_mut().zip(bytes.chunks_exact(8)).enumerate() {
+            *element = fp_from_canonical_bytes(chunk.try_into().unwrap())
+                .ok_or(DigestError::NonCanonicalElement(i))?;
+        }
+
+        Ok(Self(digest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::f64_utils::MODULUS;
+    #[cfg(not(feature = "std"))]
+    use alloc::vec;
+    use rand_core::OsRng;
+
+    #[test]
+    fn digest_elements() {
+        let mut rng = OsRng;
+
+        for _ in 0..100 {
+            let mut array = [Fp::zero(); DIGEST_SIZE];
+            for item in array.iter_mut() {
+                *item = Fp::random(&mut rng);
+            }
+
+            let digest = Plonky2PoseidonDigest::new(array);
+            assert_eq!(digest.to_elements(), array);
+            assert_eq!(&digest.to_elements(), digest.as_elements());
+            assert_eq!(
+                digest.as_elements(),
+                &Plonky2PoseidonDigest::digests_to_elements(&[digest])[..]
+            );
+        }
+
+        let digest = Plonky2PoseidonDigest::default();
+        assert_eq!(digest.to_elements(), [Fp::zero(); DIGEST_SIZE]);
+        assert_eq!(digest.as_elements(), &vec![Fp::zero(); DIGEST_SIZE][..]);
+        assert_eq!(digest.to_bytes(), [0u8; 32]);
+    }
+
+    #[test]
+    fn digest_bytes() {
+        let mut rng = OsRng;
+
+        for _ in 0..100 {
+            let mut array = [Fp::zero(); DIGEST_SIZE];
+            for item in array.iter_mut() {
+                *item = Fp::random(&mut rng);
+            }
+
+            let digest = Plonky2PoseidonDigest::new(array);
+            assert_eq!(
+                Plonky2PoseidonDigest::from_bytes(&digest.to_bytes()),
+                Ok(digest)
+            );
+        }
+
+        let mut bytes = [0u8; 32];
+        bytes[0..8].copy_from_slice(&(MODULUS - 1).to_le_bytes());
+        assert_eq!(
+            Plonky2PoseidonDigest::from_bytes(&bytes)
+                .unwrap()
+                .to_elements()[0],
+            -Fp::one()
+        );
+
+        bytes[8..16].copy_from_slice(&MODULUS.to_le_bytes());
+        assert_eq!(
+            Plonky2PoseidonDigest::from_bytes(&bytes),
+            Err(DigestError::NonCanonicalElement(1))
+        );
+
+        let mut bytes = [0u8; 32];
+        bytes[24..32].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert_eq!(
+            Plonky2PoseidonDigest::from_bytes(&bytes),
+            Err(DigestError::NonCanonicalElement(3))
+        );
+    }
+}
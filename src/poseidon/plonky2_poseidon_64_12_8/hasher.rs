@@ -0,0 +1,252 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Hasher trait implementation for Plonky2 Poseidon
+
+use core::convert::TryInto;
+
+use super::digest::Plonky2PoseidonDigest;
+use super::{apply_permutation, DIGEST_SIZE, RATE_WIDTH, STATE_WIDTH};
+use crate::traits::Hasher;
+
+use cheetah::Fp;
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+/// A Poseidon Hash over Fp, whose `hash` and `merge` outputs match
+/// Plonky2's `PoseidonHash::hash_no_pad` and `PoseidonHash::two_to_one`.
+///
+/// As `hash_no_pad` neither pads its input nor encodes its length, `hash`
+/// is only injective over inputs of a fixed length: appending zeros to
+/// an input whose last rate block is partial does not change its digest,
+/// and the empty input hashes to the default digest. This hasher must
+/// hence not be used by constructions hashing inputs of variable length,
+/// or giving a special meaning to the default digest, such as
+/// [`Transcript`](crate::transcript::Transcript), salted Merkle trees,
+/// matrix commitments, or sparse and indexed Merkle trees.
+pub struct Plonky2PoseidonHash;
+
+impl Hasher<Fp> for Plonky2PoseidonHash {
+    type Digest = Plonky2PoseidonDigest;
+
+    fn hash(bytes: &[Fp]) -> Self::Digest {
+        // Absorb elements in overwrite mode, without padding nor length
+        // encoding, permuting after each full or partial rate block.
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        for chunk in bytes.chunks(RATE_WIDTH) {
+            state[..chunk.len()].copy_from_slice(chunk);
+            apply_permutation(&mut state);
+        }
+
+        Plonky2PoseidonDigest::new(state[..DIGEST_SIZE].try_into().unwrap())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        state[..DIGEST_SIZE].copy_from_slice(values[0].as_elements());
+        state[DIGEST_SIZE..RATE_WIDTH].copy_from_slice(values[1].as_elements());
+        apply_permutation(&mut state);
+
+        Plonky2PoseidonDigest::new(state[..DIGEST_SIZE].try_into().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_plonky2_poseidon_hash() {
+        // Hardcoded outputs of the hash of the sequences `[0, 1, ..., i - 1]`
+        // for i in 1..=19, generated from a Python port of Plonky2's
+        // `hash_no_pad`, whose permutation reproduces the test vectors of
+        // Plonky2 checked in `test_permutation`.
+        let output_data = [
+            [
+                Fp::new(4330397376401421145),
+                Fp::new(14124799381142128323),
+                Fp::new(8742572140681234676),
+                Fp::new(14345658006221440202),
+            ],
+            [
+                Fp::new(5925289563669776526),
+                Fp::new(17862864563611980209),
+                Fp::new(7408400857933612578),
+                Fp::new(6701567711406704748),
+            ],
+            [
+                Fp::new(2804056946294192048),
+                Fp::new(12461277492087673997),
+                Fp::new(16649701092187974514),
+                Fp::new(11595237671902203088),
+            ],
+            [
+                Fp::new(18308065892207596462),
+                Fp::new(3638003665108702549),
+                Fp::new(7892565246137888711),
+                Fp::new(1196369532598581249),
+            ],
+            [
+                Fp::new(9837019370014485768),
+                Fp::new(7183813399062562257),
+                Fp::new(9836076442767469025),
+                Fp::new(5672308522905618436),
+            ],
+            [
+                Fp::new(11384332331339667343),
+                Fp::new(14267568524932023598),
+                Fp::new(8424106131866877169),
+                Fp::new(7261376228296103478),
+            ],
+            [
+                Fp::new(13371083541496999660),
+                Fp::new(7739921955450379130),
+                Fp::new(10572004275396999076),
+                Fp::new(3599502497184312851),
+            ],
+            [
+                Fp::new(17291601223193097753),
+                Fp::new(9133441755544524598),
+                Fp::new(17736579132324177718),
+                Fp::new(14132891516240416332),
+            ],
+            [
+                Fp::new(18007381329477297286),
+                Fp::new(11010590292829788888),
+                Fp::new(258931329831288973),
+                Fp::new(9046877563820385107),
+            ],
+            [
+                Fp::new(17494714350791569908),
+                Fp::new(10470613666551602892),
+                Fp::new(849740293742938164),
+                Fp::new(15130529846295861728),
+            ],
+            [
+                Fp::new(16785568083461670113),
+                Fp::new(16972699155608888603),
+                Fp::new(9740707058024791435),
+                Fp::new(5260664652692353943),
+            ],
+            [
+                Fp::new(15204461021133795791),
+                Fp::new(15771039747183168578),
+                Fp::new(15104818665914894456),
+                Fp::new(10180562885933053981),
+            ],
+            [
+                Fp::new(3483314936450278202),
+                Fp::new(11204135054483951704),
+                Fp::new(658847032893283462),
+                Fp::new(10714759119390308103),
+            ],
+            [
+                Fp::new(12487726488102645972),
+                Fp::new(2182831308337150642),
+                Fp::new(10366801686033483895),
+                Fp::new(4461584180584087313),
+            ],
+            [
+                Fp::new(9214286428848140192),
+                Fp::new(1724472067633703552),
+                Fp::new(2602611577261020109),
+                Fp::new(15482673218169700408),
+            ],
+            [
+                Fp::new(3047308842360922440),
+                Fp::new(10591378326149447922),
+                Fp::new(5991327740561014578),
+                Fp::new(5671799819667753500),
+            ],
+            [
+                Fp::new(10021192454749994305),
+                Fp::new(2171504916884204864),
+                Fp::new(2927377153865832470),
+                Fp::new(17965113669149857032),
+            ],
+            [
+                Fp::new(16107642885196674214),
+                Fp::new(10195761458213011293),
+                Fp::new(1795083663164500358),
+                Fp::new(7577246393702910503),
+            ],
+            [
+                Fp::new(936208058696122196),
+                Fp::new(271065522075305586),
+                Fp::new(6195588383612593648),
+                Fp::new(10070623154194314483),
+            ],
+        ];
+
+        for (i, expected) in output_data.iter().enumerate() {
+            let input: Vec<Fp> = (0..=i as u64).map(Fp::new).collect();
+            assert_eq!(*expected, Plonky2PoseidonHash::hash(&input).to_elements());
+        }
+
+        // An empty input is not permuted
+        assert_eq!(
+            Plonky2PoseidonHash::hash(&[]),
+            Plonky2PoseidonDigest::default()
+        );
+
+        // Trailing zeros in a partial block do not change the digest
+        assert_eq!(
+            Plonky2PoseidonHash::hash(&[Fp::one()]),
+            Plonky2PoseidonHash::hash(&[Fp::one(), Fp::zero()])
+        );
+    }
+
+    #[test]
+    fn test_plonky2_poseidon_merge() {
+        let input_data = [
+            [
+                Plonky2PoseidonDigest::default(),
+                Plonky2PoseidonDigest::default(),
+            ],
+            [
+                Plonky2PoseidonDigest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+                Plonky2PoseidonDigest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+            ],
+            [
+                Plonky2PoseidonDigest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+                Plonky2PoseidonDigest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+            ],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(4330397376401421145),
+                Fp::new(14124799381142128323),
+                Fp::new(8742572140681234676),
+                Fp::new(14345658006221440202),
+            ],
+            [
+                Fp::new(17291601223193097753),
+                Fp::new(9133441755544524598),
+                Fp::new(17736579132324177718),
+                Fp::new(14132891516240416332),
+            ],
+            [
+                Fp::new(3949383866169344993),
+                Fp::new(1550317881515098174),
+                Fp::new(4653661145927614416),
+                Fp::new(6322398069471115196),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, Plonky2PoseidonHash::merge(input).to_elements());
+            // Without padding, merging is equivalent to hashing a single rate block
+            assert_eq!(
+                Plonky2PoseidonHash::merge(input),
+                Plonky2PoseidonHash::hash(&Plonky2PoseidonDigest::digests_to_elements(input))
+            );
+        }
+    }
+}
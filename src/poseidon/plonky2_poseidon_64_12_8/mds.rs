@@ -0,0 +1,15 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::STATE_WIDTH;
+
+/// First row of the circulant part of the Plonky2 Poseidon MDS matrix.
+pub(crate) const MDS_CIRC: [u64; STATE_WIDTH] = [17, 15, 41, 16, 2, 28, 13, 13, 39, 18, 34, 20];
+
+/// Diagonal part of the Plonky2 Poseidon MDS matrix.
+pub(crate) const MDS_DIAG: [u64; STATE_WIDTH] = [8, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0];
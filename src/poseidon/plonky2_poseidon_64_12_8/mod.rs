@@ -0,0 +1,231 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use cheetah::fp_arith_utils::reduce_u96;
+use cheetah::Fp;
+
+use crate::f64_utils::apply_rescue_sbox;
+
+/// Digest for Plonky2 Poseidon
+mod digest;
+/// Hasher for Plonky2 Poseidon
+mod hasher;
+/// MDS matrix for Plonky2 Poseidon
+mod mds;
+/// Permutation for Plonky2 Poseidon
+mod permutation;
+/// Round constants for Plonky2 Poseidon
+mod round_constants;
+
+pub use digest::Plonky2PoseidonDigest;
+pub use hasher::Plonky2PoseidonHash;
+pub use permutation::Plonky2PoseidonPermutation;
+
+// POSEIDON CONSTANTS
+// ================================================================================================
+
+/// Function state is set to 12 field elements or 96 bytes;
+/// 4 elements of the state are reserved for capacity
+pub const STATE_WIDTH: usize = 12;
+/// 8 elements of the state are reserved for rate
+pub const RATE_WIDTH: usize = 8;
+/// 4 elements of the state are reserved for capacity
+pub const CAPACITY_WIDTH: usize = STATE_WIDTH - RATE_WIDTH;
+
+/// Four elements (32-bytes) are returned as digest.
+pub const DIGEST_SIZE: usize = 4;
+
+/// The number of full rounds is set to 8, as in Plonky2.
+pub const NUM_HALF_FULL_ROUNDS: usize = 4;
+
+/// The number of partial rounds is set to 22, as in Plonky2.
+pub const NUM_PARTIAL_ROUNDS: usize = 22;
+
+/// The total number of rounds, each one using its own round constants.
+pub const NUM_ROUNDS: usize = 2 * NUM_HALF_FULL_ROUNDS + NUM_PARTIAL_ROUNDS;
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+#[inline(always)]
+/// Applies matrix-vector multiplication of the current hash state
+/// with the Plonky2 Poseidon MDS matrix, sum of a circulant matrix
+/// and a diagonal one.
+pub(crate) fn apply_mds(state: &mut [Fp; STATE_WIDTH]) {
+    let mut x = [0u128; STATE_WIDTH];
+    for (x, s) in x.iter_mut().zip(state.iter()) {
+        *x = s.output_unreduced_internal() as u128;
+    }
+
+    // The matrix coefficients being small, the sums
+    // can be reduced once at the end.
+    for (r, s) in state.iter_mut().enumerate() {
+        let mut acc = x[r] * mds::MDS_DIAG[r] as u128;
+        for (i, &m) in mds::MDS_CIRC.iter().enumerate() {
+            acc += x[(i + r) % STATE_WIDTH] * m as u128;
+        }
+
+        *s = Fp::from_raw_unchecked(reduce_u96(acc));
+    }
+}
+
+#[inline(always)]
+/// Applies exponentiation of the current element by 7
+pub(crate) fn pow_7(x: &mut Fp) {
+    let t2 = x.square();
+    let t4 = t2.square();
+    *x *= t2 * t4;
+}
+
+// POSEIDON PERMUTATION
+// ================================================================================================
+
+/// Applies Plonky2 Poseidon permutation to the provided state.
+pub(crate) fn apply_permutation(state: &mut [Fp; STATE_WIDTH]) {
+    for i in 0..NUM_HALF_FULL_ROUNDS {
+        apply_full_round(state, i);
+    }
+
+    for i in 0..NUM_PARTIAL_ROUNDS {
+        apply_partial_round(state, NUM_HALF_FULL_ROUNDS + i);
+    }
+
+    for i in 0..NUM_HALF_FULL_ROUNDS {
+        apply_full_round(state, NUM_PARTIAL_ROUNDS + NUM_HALF_FULL_ROUNDS + i);
+    }
+}
+
+/// Plonky2 Poseidon full round function.
+#[inline(always)]
+pub(crate) fn apply_full_round(state: &mut [Fp; STATE_WIDTH], step: usize) {
+    for (s, k) in state.iter_mut().zip(round_constants::ARK[step]) {
+        *s += k;
+    }
+
+    apply_rescue_sbox(state);
+    apply_mds(state);
+}
+
+/// Plonky2 Poseidon partial round function.
+///
+/// This follows the unoptimized specification, which is the
+/// one used by Plonky2 to generate its reference test vectors.
+#[inline(always)]
+pub(crate) fn apply_partial_round(state: &mut [Fp; STATE_WIDTH], step: usize) {
+    for (s, k) in state.iter_mut().zip(round_constants::ARK[step]) {
+        *s += k;
+    }
+
+    pow_7(&mut state[0]);
+    apply_mds(state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permutation() {
+        // Inputs of the test vectors of Plonky2's Goldilocks Poseidon implementation
+        let input_data = [
+            [
+                Fp::new(0),
+                Fp::new(0),
+                Fp::new(0),
+                Fp::new(0),
+                Fp::new(0),
+                Fp::new(0),
+                Fp::new(0),
+                Fp::new(0),
+                Fp::new(0),
+                Fp::new(0),
+                Fp::new(0),
+                Fp::new(0),
+            ],
+            [
+                Fp::new(0),
+                Fp::new(1),
+                Fp::new(2),
+                Fp::new(3),
+                Fp::new(4),
+                Fp::new(5),
+                Fp::new(6),
+                Fp::new(7),
+                Fp::new(8),
+                Fp::new(9),
+                Fp::new(10),
+                Fp::new(11),
+            ],
+            [
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+                Fp::new(18446744069414584320),
+            ],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(4330397376401421145),
+                Fp::new(14124799381142128323),
+                Fp::new(8742572140681234676),
+                Fp::new(14345658006221440202),
+                Fp::new(15524073338516903644),
+                Fp::new(5091405722150716653),
+                Fp::new(15002163819607624508),
+                Fp::new(2047012902665707362),
+                Fp::new(16106391063450633726),
+                Fp::new(4680844749859802542),
+                Fp::new(15019775476387350140),
+                Fp::new(1698615465718385111),
+            ],
+            [
+                Fp::new(15442313428170673822),
+                Fp::new(6009603122036124231),
+                Fp::new(15276919505380083749),
+                Fp::new(7005999589691109842),
+                Fp::new(4703821519083557360),
+                Fp::new(14636568497518936639),
+                Fp::new(7976624690322644239),
+                Fp::new(1802209762296193110),
+                Fp::new(17313479547752415775),
+                Fp::new(16435059422334172133),
+                Fp::new(14537566946116046030),
+                Fp::new(6632157367509271963),
+            ],
+            [
+                Fp::new(13691089994624172887),
+                Fp::new(15662102337790434313),
+                Fp::new(14940024623104903507),
+                Fp::new(10772674582659927682),
+                Fp::new(18219768259309428209),
+                Fp::new(16182999571863580713),
+                Fp::new(15997791131152847259),
+                Fp::new(9021379528672530481),
+                Fp::new(1212541725329713824),
+                Fp::new(12138732650860653127),
+                Fp::new(16249659704347285752),
+                Fp::new(16325151664021332179),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            let mut state = *input;
+            apply_permutation(&mut state);
+            assert_eq!(state, expected);
+        }
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Permutation trait implementation for Plonky2 Poseidon
+
+use super::{apply_permutation, STATE_WIDTH};
+use crate::traits::Permutation;
+
+use cheetah::Fp;
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+/// The Plonky2 Poseidon permutation over Fp
+pub struct Plonky2PoseidonPermutation;
+
+impl Permutation<Fp> for Plonky2PoseidonPermutation {
+    const WIDTH: usize = STATE_WIDTH;
+
+    type State = [Fp; STATE_WIDTH];
+
+    fn permute(state: &mut Self::State) {
+        apply_permutation(state)
+    }
+}
@@ -0,0 +1,435 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::{NUM_ROUNDS, STATE_WIDTH};
+use cheetah::Fp;
+
+/// Additive Round Keys constants for Plonky2 Poseidon, sampled with
+/// a `ChaCha8Rng` seeded with 0 as in Plonky2's `generate_constants`.
+pub(crate) const ARK: [[Fp; STATE_WIDTH]; NUM_ROUNDS] = [
+    [
+        Fp::new(13080132714287612933),
+        Fp::new(8594738767457295063),
+        Fp::new(12896916465481390516),
+        Fp::new(1109962092811921367),
+        Fp::new(16216730422861946898),
+        Fp::new(10137062673499593713),
+        Fp::new(15292064466732465823),
+        Fp::new(17255573294985989181),
+        Fp::new(14827154241873003558),
+        Fp::new(2846171647972703231),
+        Fp::new(16246264663680317601),
+        Fp::new(14214208087951879286),
+    ],
+    [
+        Fp::new(9667108687426275457),
+        Fp::new(6470857420712283733),
+        Fp::new(14103331940138337652),
+        Fp::new(11854816473550292865),
+        Fp::new(3498097497301325516),
+        Fp::new(7947235692523864220),
+        Fp::new(11110078701231901946),
+        Fp::new(16384314112672821048),
+        Fp::new(15404405912655775739),
+        Fp::new(14077880830714445579),
+        Fp::new(9555554662709218279),
+        Fp::new(13859595358210603949),
+    ],
+    [
+        Fp::new(16859897325061800066),
+        Fp::new(17685474420222222349),
+        Fp::new(17858764734618734949),
+        Fp::new(9410011022665866671),
+        Fp::new(12495243629579414666),
+        Fp::new(12416945298171515742),
+        Fp::new(5776666812364270983),
+        Fp::new(6314421662864060481),
+        Fp::new(7402742471423223171),
+        Fp::new(982536713192432718),
+        Fp::new(17321168865775127905),
+        Fp::new(2934354895005980211),
+    ],
+    [
+        Fp::new(10567510598607410195),
+        Fp::new(8135543733717919110),
+        Fp::new(116353493081713692),
+        Fp::new(8029688163494945618),
+        Fp::new(9003846637224807585),
+        Fp::new(7052445132467233849),
+        Fp::new(9645665432288852853),
+        Fp::new(5446430061030868787),
+        Fp::new(16770910634346036823),
+        Fp::new(17708360571433944729),
+        Fp::new(4661556288322237631),
+        Fp::new(11977051899316327985),
+    ],
+    [
+        Fp::new(4378616569090929672),
+        Fp::new(3334807502817538491),
+        Fp::new(8019184735943344966),
+        Fp::new(2395043908812246395),
+        Fp::new(6558421058331732611),
+        Fp::new(11735894060727326369),
+        Fp::new(8143540538889204488),
+        Fp::new(5991753489563751169),
+        Fp::new(12235918791502088007),
+        Fp::new(2880312033702687139),
+        Fp::new(18224748115308382355),
+        Fp::new(18070411013125314165),
+    ],
+    [
+        Fp::new(8156487614120951180),
+        Fp::new(10615269510047010719),
+        Fp::new(12489426404754222075),
+        Fp::new(5055279340069995710),
+        Fp::new(7231927319780248664),
+        Fp::new(2602078848106763799),
+        Fp::new(12445944369334781425),
+        Fp::new(3978905923892496205),
+        Fp::new(16711272944329818038),
+        Fp::new(10439032361227108922),
+        Fp::new(15110119871725214866),
+        Fp::new(821141790655890946),
+    ],
+    [
+        Fp::new(11073536380651186235),
+        Fp::new(4866839313097607757),
+        Fp::new(13118391689513956636),
+        Fp::new(14527674973762312380),
+        Fp::new(7612751959265567999),
+        Fp::new(6808090907814178161),
+        Fp::new(6899703779492644997),
+        Fp::new(3664666286336986826),
+        Fp::new(783179505424462608),
+        Fp::new(8990689241814097697),
+        Fp::new(9646603555412825679),
+        Fp::new(7351246026167205041),
+    ],
+    [
+        Fp::new(16970959813722173256),
+        Fp::new(15735726858241466429),
+        Fp::new(10347018221892268419),
+        Fp::new(12195545878449322889),
+        Fp::new(7423314197114049891),
+        Fp::new(14908016116973904153),
+        Fp::new(5840340122527363265),
+        Fp::new(17740311462440614128),
+        Fp::new(815306421953744623),
+        Fp::new(17456357368219253949),
+        Fp::new(6982651076559329072),
+        Fp::new(11970987324614963868),
+    ],
+    [
+        Fp::new(8167785008538063246),
+        Fp::new(9483259819397403968),
+        Fp::new(954550221664291548),
+        Fp::new(10339565171024313256),
+        Fp::new(8651171084286500102),
+        Fp::new(16974445528003515956),
+        Fp::new(15104530047940621190),
+        Fp::new(103271880867179718),
+        Fp::new(14654666245504492663),
+        Fp::new(12445769555936887967),
+        Fp::new(11250582358051997490),
+        Fp::new(6730977207490590241),
+    ],
+    [
+        Fp::new(15919951556166196935),
+        Fp::new(4423540216573360915),
+        Fp::new(16317664700341473511),
+        Fp::new(4723997214951767765),
+        Fp::new(10098756619006575500),
+        Fp::new(3223149401237667964),
+        Fp::new(6870494874300767682),
+        Fp::new(2902095711130291898),
+        Fp::new(7159372652788439733),
+        Fp::new(11500508372997952671),
+        Fp::new(13348148181479462670),
+        Fp::new(12729401155983882093),
+    ],
+    [
+        Fp::new(15021242795466053388),
+        Fp::new(3802990509227527157),
+        Fp::new(4665459515680145682),
+        Fp::new(13165553315407675603),
+        Fp::new(6496364397926233172),
+        Fp::new(12800832566287577810),
+        Fp::new(9737592377590267426),
+        Fp::new(8687131091302514939),
+        Fp::new(1488200421755445892),
+        Fp::new(11004377668730991641),
+        Fp::new(13516338734600228410),
+        Fp::new(2953581820660217936),
+    ],
+    [
+        Fp::new(3505040783153922951),
+        Fp::new(3710332827435113697),
+        Fp::new(15414874040873320221),
+        Fp::new(8602547649919482301),
+        Fp::new(13971349938398812007),
+        Fp::new(187239246702636066),
+        Fp::new(12886019973971254144),
+        Fp::new(4512274763990493707),
+        Fp::new(2986635507805503192),
+        Fp::new(2315252455709119454),
+        Fp::new(12537995864054210246),
+        Fp::new(2039491936479859267),
+    ],
+    [
+        Fp::new(1558644089185031256),
+        Fp::new(4074089203264759305),
+        Fp::new(2522268501749395707),
+        Fp::new(3414760436185256196),
+        Fp::new(17420887529146466921),
+        Fp::new(2817020417938125001),
+        Fp::new(16538346563888261485),
+        Fp::new(5592270336833998770),
+        Fp::new(16876602064684906232),
+        Fp::new(1793025614521516343),
+        Fp::new(2178510518148748532),
+        Fp::new(2726440714374752509),
+    ],
+    [
+        Fp::new(6502946837278398021),
+        Fp::new(15816362857667988792),
+        Fp::new(12997958454165692924),
+        Fp::new(5314892854495903792),
+        Fp::new(15533907063555687782),
+        Fp::new(12312015675698548715),
+        Fp::new(14140016464013350248),
+        Fp::new(16325589062962838690),
+        Fp::new(6796145646370327654),
+        Fp::new(1168753512742361735),
+        Fp::new(4100789820704709368),
+        Fp::new(15947554381540469177),
+    ],
+    [
+        Fp::new(8597377839806076919),
+        Fp::new(9704018824195918000),
+        Fp::new(12763288618765762688),
+        Fp::new(17249257732622847695),
+        Fp::new(1998710993415069759),
+        Fp::new(923759906393011543),
+        Fp::new(1271051229666811593),
+        Fp::new(17822362132088738077),
+        Fp::new(11797234543722669271),
+        Fp::new(5864538787265942447),
+        Fp::new(15975583211110506970),
+        Fp::new(7258516085733671960),
+    ],
+    [
+        Fp::new(17999926471875633100),
+        Fp::new(635992114476018166),
+        Fp::new(17205047318256576347),
+        Fp::new(17384900867876315312),
+        Fp::new(16484825562915784226),
+        Fp::new(16694130609036138894),
+        Fp::new(10575069350371260875),
+        Fp::new(8330575162062887277),
+        Fp::new(6212375704691932880),
+        Fp::new(15965138197626618226),
+        Fp::new(14285453069600046939),
+        Fp::new(10005163510208402517),
+    ],
+    [
+        Fp::new(885298637936952595),
+        Fp::new(541790758138118921),
+        Fp::new(5985203084790372993),
+        Fp::new(4685030219775483721),
+        Fp::new(1411106851304815020),
+        Fp::new(11290732479954096478),
+        Fp::new(208280581124868513),
+        Fp::new(10979018648467968495),
+        Fp::new(8600643745023338215),
+        Fp::new(3477453626867126061),
+        Fp::new(6428436309340258604),
+        Fp::new(5695415667275657934),
+    ],
+    [
+        Fp::new(15952065508715623490),
+        Fp::new(15571300830419767248),
+        Fp::new(17259785660502616862),
+        Fp::new(4298425495274316083),
+        Fp::new(9023601070579319352),
+        Fp::new(7353589709321807492),
+        Fp::new(2988848909076209475),
+        Fp::new(10439527789422046135),
+        Fp::new(6097734044161429459),
+        Fp::new(1113429873817861476),
+        Fp::new(1639063372386966591),
+        Fp::new(7863102812716788759),
+    ],
+    [
+        Fp::new(216040220732135364),
+        Fp::new(14252611488623712688),
+        Fp::new(9543395466794536974),
+        Fp::new(2714461051639810934),
+        Fp::new(2588317208781407279),
+        Fp::new(15458529123534594916),
+        Fp::new(15748417817551040856),
+        Fp::new(16414455697114422951),
+        Fp::new(13378164466674639511),
+        Fp::new(13894319928411294675),
+        Fp::new(5032680892090751540),
+        Fp::new(17201338494743078916),
+    ],
+    [
+        Fp::new(4397422800601932505),
+        Fp::new(11285062031581972327),
+        Fp::new(7309354640676468207),
+        Fp::new(10457152817239331848),
+        Fp::new(8855911538863247046),
+        Fp::new(4301853449821814398),
+        Fp::new(13001502396339103326),
+        Fp::new(10218424535115580246),
+        Fp::new(8628244713920681895),
+        Fp::new(17410423622514037261),
+        Fp::new(14080683768439215375),
+        Fp::new(11453161143447188100),
+    ],
+    [
+        Fp::new(16761509772042181939),
+        Fp::new(6688821660695954082),
+        Fp::new(12083434295263160416),
+        Fp::new(8540021431714616589),
+        Fp::new(6891616215679974226),
+        Fp::new(10229217098454812721),
+        Fp::new(3292165387203778711),
+        Fp::new(6090113424998243490),
+        Fp::new(13431780521962358660),
+        Fp::new(6061081364215809883),
+        Fp::new(16792066504222214142),
+        Fp::new(16134314044798124799),
+    ],
+    [
+        Fp::new(17070233710126619765),
+        Fp::new(6915716851370550800),
+        Fp::new(9505009849073026581),
+        Fp::new(6422700465081897153),
+        Fp::new(17977653991560529185),
+        Fp::new(5800870252836247255),
+        Fp::new(12096124733159345520),
+        Fp::new(7679273623392321940),
+        Fp::new(17835783910585744964),
+        Fp::new(2478664878205754377),
+        Fp::new(1720314468413114967),
+        Fp::new(10376757819003248056),
+    ],
+    [
+        Fp::new(10376377187857634245),
+        Fp::new(13344930747504284997),
+        Fp::new(11579281865160153596),
+        Fp::new(10300256980048736962),
+        Fp::new(378765236515040565),
+        Fp::new(11412420941557253424),
+        Fp::new(12931662470734252786),
+        Fp::new(43018908376346374),
+        Fp::new(3589810689190160071),
+        Fp::new(4688229274750659741),
+        Fp::new(13688957436484306091),
+        Fp::new(11424740943016984272),
+    ],
+    [
+        Fp::new(16001900718237913960),
+        Fp::new(5548469743008097574),
+        Fp::new(14584404916672178680),
+        Fp::new(3396622135873576824),
+        Fp::new(7861729246871155992),
+        Fp::new(16112271126908045545),
+        Fp::new(16988163966860016012),
+        Fp::new(273641680619529493),
+        Fp::new(15222677154027327363),
+        Fp::new(4070328078309830604),
+        Fp::new(13520458500363296391),
+        Fp::new(8235111705801363015),
+    ],
+    [
+        Fp::new(5575990058472514138),
+        Fp::new(2751301609188252989),
+        Fp::new(6478598528223547074),
+        Fp::new(386565553848556638),
+        Fp::new(9417729078939938713),
+        Fp::new(15204315939835727483),
+        Fp::new(14942015033780606261),
+        Fp::new(18369423901636582012),
+        Fp::new(4715338437538604447),
+        Fp::new(6840590980607806319),
+        Fp::new(5535471161490539014),
+        Fp::new(5341328005359029952),
+    ],
+    [
+        Fp::new(1475161295215894444),
+        Fp::new(7999197814297036636),
+        Fp::new(2984233088665867938),
+        Fp::new(3097746028144832229),
+        Fp::new(8849530863480031517),
+        Fp::new(7464920943249009773),
+        Fp::new(3802996844641460514),
+        Fp::new(6284458522545927646),
+        Fp::new(2307388003445002779),
+        Fp::new(4461479354745457623),
+        Fp::new(1649739722664588460),
+        Fp::new(3008391274160432867),
+    ],
+    [
+        Fp::new(5142217010456550622),
+        Fp::new(1775580461722730120),
+        Fp::new(161694268822794344),
+        Fp::new(1518963253808031703),
+        Fp::new(16475258091652710137),
+        Fp::new(119575899007375159),
+        Fp::new(1275863735937973999),
+        Fp::new(16539412514520642374),
+        Fp::new(2303365191438051950),
+        Fp::new(6435126839960916075),
+        Fp::new(17794599201026020053),
+        Fp::new(13847097589277840330),
+    ],
+    [
+        Fp::new(16645869274577729720),
+        Fp::new(8039205965509554440),
+        Fp::new(4788586935019371140),
+        Fp::new(15129007200040077746),
+        Fp::new(2055561615223771341),
+        Fp::new(4149731103701412892),
+        Fp::new(10268130195734144189),
+        Fp::new(13406631635880074708),
+        Fp::new(11429218277824986203),
+        Fp::new(15773968030812198565),
+        Fp::new(16050275277550506872),
+        Fp::new(11858586752031736643),
+    ],
+    [
+        Fp::new(8927746344866569756),
+        Fp::new(11802068403177695792),
+        Fp::new(157833420806751556),
+        Fp::new(4698875910749767878),
+        Fp::new(1616722774788291698),
+        Fp::new(3990951895163748090),
+        Fp::new(16758609224720795472),
+        Fp::new(3045571693290741477),
+        Fp::new(9281634245289836419),
+        Fp::new(13517688176723875370),
+        Fp::new(7961395585333219380),
+        Fp::new(1606574359105691080),
+    ],
+    [
+        Fp::new(17564372683613562171),
+        Fp::new(4664015225343144418),
+        Fp::new(6133721340680280128),
+        Fp::new(2667022304383014929),
+        Fp::new(12316557761857340230),
+        Fp::new(10375614850625292317),
+        Fp::new(8141542666379135068),
+        Fp::new(9185476451083834432),
+        Fp::new(4991072365274649547),
+        Fp::new(17398204971778820365),
+        Fp::new(16127888338958422584),
+        Fp::new(13586792051317758204),
+    ],
+];
@@ -6,6 +6,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use crate::duplex::Duplex;
 use cheetah::Fp;
 
 /// Digest for Poseidon
@@ -14,11 +15,17 @@ mod digest;
 mod hasher;
 /// MDS matrix for Poseidon
 mod mds;
+/// Permutation for Poseidon
+mod permutation;
 /// Round constants for Poseidon
 mod round_constants;
 
 pub use digest::PoseidonDigest;
 pub use hasher::PoseidonHash;
+pub use permutation::PoseidonPermutation;
+
+/// A duplex sponge over the Poseidon permutation, with the rate and capacity of this instance.
+pub type PoseidonDuplex = Duplex<PoseidonPermutation, RATE_WIDTH, CAPACITY_WIDTH>;
 
 use self::round_constants::ARK;
 
@@ -33,6 +40,8 @@ use alloc::vec::Vec;
 pub const STATE_WIDTH: usize = 12;
 /// 8 elements of the state are reserved for rate
 pub const RATE_WIDTH: usize = 8;
+/// 4 elements of the state are reserved for capacity
+pub const CAPACITY_WIDTH: usize = STATE_WIDTH - RATE_WIDTH;
 
 /// Seven elements (32-bytes) are returned as digest.
 pub const DIGEST_SIZE: usize = 4;
@@ -8,83 +8,124 @@
 
 //! Hasher trait implementation for Poseidon
 
-use core::convert::TryInto;
-
 use super::digest::PoseidonDigest;
-use super::{apply_permutation, DIGEST_SIZE, RATE_WIDTH, STATE_WIDTH};
+use super::{PoseidonPermutation, CAPACITY_WIDTH, DIGEST_SIZE, RATE_WIDTH, STATE_WIDTH};
+use crate::f64_utils::bytes_to_elements;
+use crate::jive::Jive;
+use crate::sponge::{Padding, Sponge, XofReader};
 use crate::traits::Hasher;
 
 use cheetah::Fp;
+use subtle::ConstantTimeEq;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 /// A Poseidon Hash over Fp
 pub struct PoseidonHash {
-    state: [Fp; STATE_WIDTH],
-    idx: usize,
+    sponge: Sponge<PoseidonPermutation, RATE_WIDTH, CAPACITY_WIDTH>,
 }
 
 impl Default for PoseidonHash {
     fn default() -> Self {
         Self {
-            state: [Fp::zero(); STATE_WIDTH],
-            idx: 0,
+            sponge: Sponge::new(Padding::OneZeros),
         }
     }
 }
 
-impl Hasher<Fp> for PoseidonHash {
-    type Digest = PoseidonDigest;
+impl PoseidonHash {
+    /// Returns a new hasher keyed with `key`, to compute a message
+    /// authentication code through `update` and `finalize`.
+    ///
+    /// The key is set as the initial capacity, and the message is
+    /// always padded with a one, even when its length is a multiple
+    /// of the rate, so that no two messages share the same padding.
+    pub fn new_keyed(key: &[Fp; CAPACITY_WIDTH]) -> Self {
+        Self {
+            sponge: Sponge::with_initial_capacity(Padding::AlwaysOne, *key),
+        }
+    }
 
-    fn hash(bytes: &[Fp]) -> Self::Digest {
-        // initialize state to all zeros
-        let mut state = [Fp::zero(); STATE_WIDTH];
+    /// Absorbs a new sequence of field elements into the hasher state.
+    ///
+    /// This can be called several times on chunks of a message, the
+    /// resulting digest being identical to the one returned by
+    /// `PoseidonHash::hash` on the concatenated input.
+    pub fn update(&mut self, bytes: &[Fp]) {
+        self.sponge.absorb(bytes);
+    }
 
-        let mut i = 0;
-        for &element in bytes.iter() {
-            state[i] += element;
-            i += 1;
-            if i % RATE_WIDTH == 0 {
-                apply_permutation(&mut state);
-                i = 0;
-            }
-        }
+    /// Pads the absorbed sequence and returns its digest.
+    pub fn finalize(self) -> PoseidonDigest {
+        let mut digest = [Fp::zero(); DIGEST_SIZE];
+        self.finalize_xof().read(&mut digest);
 
-        if i > 0 {
-            state[i] += Fp::one();
-            i += 1;
+        PoseidonDigest::new(digest)
+    }
 
-            while i % RATE_WIDTH != 0 {
-                state[i] = Fp::zero();
-                i += 1;
-            }
+    /// Pads the absorbed sequence and returns a reader over an arbitrarily
+    /// long output, whose first `DIGEST_SIZE` elements form its digest.
+    pub fn finalize_xof(self) -> XofReader<PoseidonPermutation, RATE_WIDTH, CAPACITY_WIDTH> {
+        self.sponge.into_reader()
+    }
 
-            apply_permutation(&mut state);
-        }
+    /// Returns a reader over an arbitrarily long hash output
+    /// of the provided sequence of field elements.
+    pub fn hash_xof(bytes: &[Fp]) -> XofReader<PoseidonPermutation, RATE_WIDTH, CAPACITY_WIDTH> {
+        let mut hasher = Self::default();
+        hasher.update(bytes);
+        hasher.finalize_xof()
+    }
+
+    /// Returns a hash of the provided sequence of bytes.
+    ///
+    /// The bytes are first packed into field elements with
+    /// `f64_utils::bytes_to_elements`.
+    pub fn hash_bytes(bytes: &[u8]) -> PoseidonDigest {
+        Self::hash(&bytes_to_elements(bytes))
+    }
 
-        PoseidonDigest::new(state[..DIGEST_SIZE].try_into().unwrap())
+    /// Returns a message authentication code of the provided
+    /// sequence of field elements under `key`, as computed by a
+    /// hasher returned by `new_keyed`.
+    pub fn mac(key: &[Fp; CAPACITY_WIDTH], bytes: &[Fp]) -> PoseidonDigest {
+        let mut hasher = Self::new_keyed(key);
+        hasher.update(bytes);
+        hasher.finalize()
     }
 
+    /// Checks in constant time that `tag` is a valid message
+    /// authentication code of `bytes` under `key`.
+    pub fn verify(key: &[Fp; CAPACITY_WIDTH], bytes: &[Fp], tag: &PoseidonDigest) -> bool {
+        Self::mac(key, bytes).ct_eq(tag).into()
+    }
+}
+
+impl Hasher<Fp> for PoseidonHash {
+    type Digest = PoseidonDigest;
+
+    fn hash(bytes: &[Fp]) -> Self::Digest {
+        let mut hasher = Self::default();
+        hasher.update(bytes);
+        hasher.finalize()
+    }
+
+    // Uses Jive compression to fill the whole state and perform a single permutation
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
         let mut state = [Fp::zero(); STATE_WIDTH];
-        let digest1 = values[0].as_elements();
-        let digest2 = values[1].as_elements();
-        // Uses Jive compression to fill the whole state and perform a single permutation
-        state[..RATE_WIDTH].copy_from_slice(digest1);
-        state[RATE_WIDTH..STATE_WIDTH].copy_from_slice(digest2);
-        apply_permutation(&mut state);
+        state[..RATE_WIDTH].copy_from_slice(values[0].as_elements());
+        state[RATE_WIDTH..STATE_WIDTH].copy_from_slice(values[1].as_elements());
 
         let mut result = [Fp::zero(); DIGEST_SIZE];
-        for (i, r) in result.iter_mut().enumerate() {
-            *r = digest1[i] + digest2[i] + state[i] + state[i + STATE_WIDTH / 2];
-        }
+        Jive::<PoseidonPermutation, 2>::compress(&state, &mut result);
 
-        PoseidonDigest::new(state[..DIGEST_SIZE].try_into().unwrap())
+        PoseidonDigest::new(result)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand_core::OsRng;
 
     #[test]
     fn test_poseidon_hash() {
@@ -236,4 +277,116 @@ mod tests {
             assert_eq!(expected, PoseidonHash::hash(input).to_elements());
         }
     }
+
+    #[test]
+    fn test_poseidon_hash_update() {
+        let mut rng = OsRng;
+        let mut input = [Fp::zero(); 3 * RATE_WIDTH + 1];
+        for element in input.iter_mut() {
+            *element = Fp::random(&mut rng);
+        }
+
+        for len in 0..=input.len() {
+            let expected = PoseidonHash::hash(&input[..len]);
+
+            for chunk_size in 1..=RATE_WIDTH + 1 {
+                let mut hasher = PoseidonHash::default();
+                for chunk in input[..len].chunks(chunk_size) {
+                    hasher.update(chunk);
+                }
+
+                assert_eq!(expected, hasher.finalize());
+            }
+        }
+    }
+
+    #[test]
+    fn test_poseidon_hash_bytes() {
+        let inputs: [&[u8]; 5] = [b"", b"\0", b"\0\0", b"0123456", b"01234567"];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let digest = PoseidonHash::hash_bytes(input);
+            assert_eq!(digest, PoseidonHash::hash(&bytes_to_elements(input)));
+
+            for other in inputs.iter().skip(i + 1) {
+                assert_ne!(digest, PoseidonHash::hash_bytes(other));
+            }
+        }
+    }
+
+    #[test]
+    fn test_poseidon_hash_xof() {
+        let input = [Fp::zero(), Fp::one(), Fp::new(2)];
+
+        // Output spanning more than two rate blocks, to cover squeezing
+        // beyond the first permutation.
+        let expected = [
+            Fp::new(11970355170247655134),
+            Fp::new(11958728596265825502),
+            Fp::new(4829018063371745763),
+            Fp::new(6719484792811666197),
+            Fp::new(9727019645863922709),
+            Fp::new(2142310140083452635),
+            Fp::new(10217080429294953586),
+            Fp::new(12026609970075154631),
+            Fp::new(15525444061755646936),
+        ];
+
+        let mut output = [Fp::zero(); 9];
+        PoseidonHash::hash_xof(&input).read(&mut output);
+        assert_eq!(output, expected);
+        assert_eq!(
+            output[..DIGEST_SIZE],
+            PoseidonHash::hash(&input).to_elements()
+        );
+
+        let mut hasher = PoseidonHash::default();
+        hasher.update(&input);
+        let mut reader = hasher.finalize_xof();
+        let mut output = [Fp::zero(); 9];
+        for chunk in output.chunks_mut(3) {
+            reader.read(chunk);
+        }
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_poseidon_merge() {
+        let input_data = [
+            [PoseidonDigest::default(), PoseidonDigest::default()],
+            [
+                PoseidonDigest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+                PoseidonDigest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+            ],
+            [
+                PoseidonDigest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+                PoseidonDigest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+            ],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(7805829924809547362),
+                Fp::new(3903671610527375687),
+                Fp::new(4119400631623191500),
+                Fp::new(15364712782964581855),
+            ],
+            [
+                Fp::new(3831488037605460953),
+                Fp::new(10467034769113843195),
+                Fp::new(8938651796738487532),
+                Fp::new(9715314367699888039),
+            ],
+            [
+                Fp::new(13627904559001156394),
+                Fp::new(14678991588505608018),
+                Fp::new(4684988981385493141),
+                Fp::new(5424248276668573936),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, PoseidonHash::merge(input).to_elements());
+        }
+    }
 }
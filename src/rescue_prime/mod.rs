@@ -15,3 +15,15 @@ pub mod rescue_64_12_8;
 /// primefield with state width 8 and rate 4.
 #[cfg(feature = "f64")]
 pub mod rescue_64_8_4;
+
+/// The Rescue Prime Optimized hash function over Fp's
+/// small primefield with state width 12 and rate 8.
+#[cfg(feature = "f64")]
+pub mod rpo_64_12_8;
+
+/// The Rescue-Prime hash function over Fp's small primefield
+/// with state width 12 and rate 8, in a sponge mode modelled on
+/// Winterfell's `Rp64_256`. Its round constants are not Winterfell's,
+/// hence its digests are not compatible with Winterfell.
+#[cfg(feature = "f64")]
+pub mod rp64_256;
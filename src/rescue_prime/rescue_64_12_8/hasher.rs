@@ -11,58 +11,105 @@
 use core::convert::TryInto;
 
 use super::digest::RescueDigest;
-use super::{apply_permutation, DIGEST_SIZE, RATE_WIDTH, STATE_WIDTH};
+use super::{
+    apply_permutation, RescuePermutation, CAPACITY_WIDTH, DIGEST_SIZE, RATE_WIDTH, STATE_WIDTH,
+};
+use crate::f64_utils::bytes_to_elements;
+use crate::sponge::{Padding, Sponge, XofReader};
 use crate::traits::Hasher;
 
 use cheetah::Fp;
+use subtle::ConstantTimeEq;
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 /// A Rescue Hash over Fp
 pub struct RescueHash {
-    state: [Fp; STATE_WIDTH],
-    idx: usize,
+    sponge: Sponge<RescuePermutation, RATE_WIDTH, CAPACITY_WIDTH>,
 }
 
 impl Default for RescueHash {
     fn default() -> Self {
         Self {
-            state: [Fp::zero(); STATE_WIDTH],
-            idx: 0,
+            sponge: Sponge::new(Padding::OneZeros),
         }
     }
 }
 
-impl Hasher<Fp> for RescueHash {
-    type Digest = RescueDigest;
+impl RescueHash {
+    /// Returns a new hasher keyed with `key`, to compute a message
+    /// authentication code through `update` and `finalize`.
+    ///
+    /// The key is set as the initial capacity, and the message is
+    /// always padded with a one, even when its length is a multiple
+    /// of the rate, so that no two messages share the same padding.
+    pub fn new_keyed(key: &[Fp; CAPACITY_WIDTH]) -> Self {
+        Self {
+            sponge: Sponge::with_initial_capacity(Padding::AlwaysOne, *key),
+        }
+    }
 
-    fn hash(bytes: &[Fp]) -> Self::Digest {
-        // initialize state to all zeros
-        let mut state = [Fp::zero(); STATE_WIDTH];
+    /// Absorbs a new sequence of field elements into the hasher state.
+    ///
+    /// This can be called several times on chunks of a message, the
+    /// resulting digest being identical to the one returned by
+    /// `RescueHash::hash` on the concatenated input.
+    pub fn update(&mut self, bytes: &[Fp]) {
+        self.sponge.absorb(bytes);
+    }
 
-        let mut i = 0;
-        for &element in bytes.iter() {
-            state[i] += element;
-            i += 1;
-            if i % RATE_WIDTH == 0 {
-                apply_permutation(&mut state);
-                i = 0;
-            }
-        }
+    /// Pads the absorbed sequence and returns its digest.
+    pub fn finalize(self) -> RescueDigest {
+        let mut digest = [Fp::zero(); DIGEST_SIZE];
+        self.finalize_xof().read(&mut digest);
 
-        // Apply padding specification from https://eprint.iacr.org/2020/1143.pdf, Algorithm 2
-        if i > 0 {
-            state[i] += Fp::one();
-            i += 1;
+        RescueDigest::new(digest)
+    }
 
-            while i % RATE_WIDTH != 0 {
-                state[i] = Fp::zero();
-                i += 1;
-            }
+    /// Pads the absorbed sequence and returns a reader over an arbitrarily
+    /// long output, whose first `DIGEST_SIZE` elements form its digest.
+    pub fn finalize_xof(self) -> XofReader<RescuePermutation, RATE_WIDTH, CAPACITY_WIDTH> {
+        self.sponge.into_reader()
+    }
 
-            apply_permutation(&mut state);
-        }
+    /// Returns a reader over an arbitrarily long hash output
+    /// of the provided sequence of field elements.
+    pub fn hash_xof(bytes: &[Fp]) -> XofReader<RescuePermutation, RATE_WIDTH, CAPACITY_WIDTH> {
+        let mut hasher = Self::default();
+        hasher.update(bytes);
+        hasher.finalize_xof()
+    }
 
-        RescueDigest::new(state[..DIGEST_SIZE].try_into().unwrap())
+    /// Returns a hash of the provided sequence of bytes.
+    ///
+    /// The bytes are first packed into field elements with
+    /// `f64_utils::bytes_to_elements`.
+    pub fn hash_bytes(bytes: &[u8]) -> RescueDigest {
+        Self::hash(&bytes_to_elements(bytes))
+    }
+
+    /// Returns a message authentication code of the provided
+    /// sequence of field elements under `key`, as computed by a
+    /// hasher returned by `new_keyed`.
+    pub fn mac(key: &[Fp; CAPACITY_WIDTH], bytes: &[Fp]) -> RescueDigest {
+        let mut hasher = Self::new_keyed(key);
+        hasher.update(bytes);
+        hasher.finalize()
+    }
+
+    /// Checks in constant time that `tag` is a valid message
+    /// authentication code of `bytes` under `key`.
+    pub fn verify(key: &[Fp; CAPACITY_WIDTH], bytes: &[Fp], tag: &RescueDigest) -> bool {
+        Self::mac(key, bytes).ct_eq(tag).into()
+    }
+}
+
+impl Hasher<Fp> for RescueHash {
+    type Digest = RescueDigest;
+
+    fn hash(bytes: &[Fp]) -> Self::Digest {
+        let mut hasher = Self::default();
+        hasher.update(bytes);
+        hasher.finalize()
     }
 
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
@@ -78,6 +125,7 @@ impl Hasher<Fp> for RescueHash {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand_core::OsRng;
 
     #[test]
     fn test_rescue_hash() {
@@ -269,4 +317,193 @@ mod tests {
             assert_eq!(expected, RescueHash::hash(input).to_elements());
         }
     }
+
+    #[test]
+    fn test_rescue_hash_update() {
+        let mut rng = OsRng;
+        let mut input = [Fp::zero(); 3 * RATE_WIDTH + 1];
+        for element in input.iter_mut() {
+            *element = Fp::random(&mut rng);
+        }
+
+        for len in 0..=input.len() {
+            let expected = RescueHash::hash(&input[..len]);
+
+            for chunk_size in 1..=RATE_WIDTH + 1 {
+                let mut hasher = RescueHash::default();
+                for chunk in input[..len].chunks(chunk_size) {
+                    hasher.update(chunk);
+                }
+
+                assert_eq!(expected, hasher.finalize());
+            }
+        }
+    }
+
+    #[test]
+    fn test_rescue_hash_bytes() {
+        let inputs: [&[u8]; 5] = [b"", b"\0", b"\0\0", b"0123456", b"01234567"];
+
+        for (i, input) in inputs.iter().enumerate() {
+            let digest = RescueHash::hash_bytes(input);
+            assert_eq!(digest, RescueHash::hash(&bytes_to_elements(input)));
+
+            for other in inputs.iter().skip(i + 1) {
+                assert_ne!(digest, RescueHash::hash_bytes(other));
+            }
+        }
+    }
+
+    #[test]
+    fn test_rescue_hash_xof() {
+        let input = [Fp::zero(), Fp::one(), Fp::new(2)];
+
+        // Output spanning more than two rate blocks, to cover squeezing
+        // beyond the first permutation.
+        let expected = [
+            Fp::new(16549989863096163682),
+            Fp::new(14966254259774762066),
+            Fp::new(15795006934302401033),
+            Fp::new(8561146585344448922),
+            Fp::new(17449221730694162477),
+            Fp::new(9239967731489162341),
+            Fp::new(15676180955375858444),
+            Fp::new(3408450994145195579),
+            Fp::new(3139081284846169698),
+            Fp::new(10891285949161771224),
+            Fp::new(4411427383763453174),
+            Fp::new(177373983292828299),
+            Fp::new(574618852277040325),
+            Fp::new(13282283426009564452),
+            Fp::new(13355091521506742782),
+            Fp::new(14655370068768484730),
+            Fp::new(11352201052791012166),
+        ];
+
+        let mut output = [Fp::zero(); 17];
+        RescueHash::hash_xof(&input).read(&mut output);
+        assert_eq!(output, expected);
+        assert_eq!(
+            output[..DIGEST_SIZE],
+            RescueHash::hash(&input).to_elements()
+        );
+
+        let mut hasher = RescueHash::default();
+        hasher.update(&input);
+        let mut reader = hasher.finalize_xof();
+        let mut output = [Fp::zero(); 17];
+        for chunk in output.chunks_mut(3) {
+            reader.read(chunk);
+        }
+        assert_eq!(output, expected);
+    }
+
+    #[test]
+    fn test_rescue_merge() {
+        let input_data = [
+            [RescueDigest::default(), RescueDigest::default()],
+            [
+                RescueDigest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+                RescueDigest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+            ],
+            [
+                RescueDigest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+                RescueDigest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+            ],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(8957274432841348180),
+                Fp::new(3462715397810997323),
+                Fp::new(9112765074729698987),
+                Fp::new(11355028649842365827),
+            ],
+            [
+                Fp::new(5487903022809051988),
+                Fp::new(5310722838991035806),
+                Fp::new(8857518235244476208),
+                Fp::new(5610814025993197921),
+            ],
+            [
+                Fp::new(1498278301664836293),
+                Fp::new(11971089577073244499),
+                Fp::new(11603329955119948949),
+                Fp::new(11283500741446114317),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, RescueHash::merge(input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_rescue_mac() {
+        let key = [Fp::new(1), Fp::new(2), Fp::new(3), Fp::new(4)];
+        let message = [
+            Fp::new(0),
+            Fp::new(1),
+            Fp::new(2),
+            Fp::new(3),
+            Fp::new(4),
+            Fp::new(5),
+            Fp::new(6),
+            Fp::new(7),
+        ];
+
+        let output_data = [
+            [
+                Fp::new(17378183549087189909),
+                Fp::new(4557321384703956974),
+                Fp::new(15497082910974282733),
+                Fp::new(15027799856771528310),
+            ],
+            [
+                Fp::new(8728934471259093420),
+                Fp::new(6176567073496340256),
+                Fp::new(9923384091586195642),
+                Fp::new(10525319268486090772),
+            ],
+            [
+                Fp::new(12464611536504751659),
+                Fp::new(5710884346633380723),
+                Fp::new(15547830311068787060),
+                Fp::new(1187113475396395663),
+            ],
+        ];
+
+        for (len, expected) in [0, 3, 8].into_iter().zip(output_data) {
+            let input = &message[..len];
+            let tag = RescueHash::mac(&key, input);
+            assert_eq!(expected, tag.to_elements());
+
+            let mut hasher = RescueHash::new_keyed(&key);
+            hasher.update(input);
+            assert_eq!(tag, hasher.finalize());
+
+            assert!(RescueHash::verify(&key, input, &tag));
+            assert!(!RescueHash::verify(
+                &[Fp::zero(); CAPACITY_WIDTH],
+                input,
+                &tag
+            ));
+            assert!(!RescueHash::verify(&key, &message[1..], &tag));
+
+            let mut forged = tag.to_elements();
+            forged[DIGEST_SIZE - 1] += Fp::one();
+            assert!(!RescueHash::verify(&key, input, &RescueDigest::new(forged)));
+        }
+
+        // Appending a one or a zero to a message invalidates its tag,
+        // wherever the message ends within the rate.
+        for len in 0..message.len() {
+            let tag = RescueHash::mac(&key, &message[..len]);
+            let mut extended = message[..=len].to_vec();
+            extended[len] = Fp::one();
+            assert!(!RescueHash::verify(&key, &extended, &tag));
+            extended[len] = Fp::zero();
+            assert!(!RescueHash::verify(&key, &extended, &tag));
+        }
+    }
 }
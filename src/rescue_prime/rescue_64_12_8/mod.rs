@@ -6,6 +6,7 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
+use crate::duplex::Duplex;
 use cheetah::Fp;
 
 use crate::f64_utils::{apply_rescue_inv_sbox, apply_rescue_sbox};
@@ -16,11 +17,17 @@ mod digest;
 mod hasher;
 /// MDS matrix for Rescue
 mod mds;
+/// Permutation for Rescue
+mod permutation;
 /// Round constants for Rescue
 mod round_constants;
 
 pub use digest::RescueDigest;
 pub use hasher::RescueHash;
+pub use permutation::RescuePermutation;
+
+/// A duplex sponge over the Rescue permutation, with the rate and capacity of this instance.
+pub type RescueDuplex = Duplex<RescuePermutation, RATE_WIDTH, CAPACITY_WIDTH>;
 
 // RESCUE CONSTANTS
 // ================================================================================================
@@ -30,6 +37,8 @@ pub use hasher::RescueHash;
 pub const STATE_WIDTH: usize = 12;
 /// 8 elements of the state are reserved for rate
 pub const RATE_WIDTH: usize = 8;
+/// 4 elements of the state are reserved for capacity
+pub const CAPACITY_WIDTH: usize = STATE_WIDTH - RATE_WIDTH;
 
 /// Seven elements (32-bytes) are returned as digest.
 pub const DIGEST_SIZE: usize = 4;
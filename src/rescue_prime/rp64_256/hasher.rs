@@ -0,0 +1,278 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Hasher trait implementation for Rp64_256
+
+use core::convert::TryInto;
+
+use super::{
+    Rp64_256Digest, Rp64_256Permutation, DIGEST_RANGE, RATE_RANGE, RATE_WIDTH, STATE_WIDTH,
+};
+use crate::traits::{Hasher, Permutation};
+
+use cheetah::Fp;
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+/// A Rescue-Prime Hash over Fp, following the sponge mode of Winterfell's
+/// `Rp64_256::hash_elements` and `Rp64_256::merge`.
+///
+/// See the [module documentation](super) for the differences with Winterfell.
+pub struct Rp64_256Hash;
+
+impl Hasher<Fp> for Rp64_256Hash {
+    type Digest = Rp64_256Digest;
+
+    fn hash(bytes: &[Fp]) -> Self::Digest {
+        // The first capacity element is set to the number of elements to be
+        // hashed, so that appending zeros always yields a different digest.
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        state[0] = Fp::new(bytes.len() as u64);
+
+        // Absorb elements in additive mode, permuting after each full rate block.
+        let mut i = 0;
+        for &element in bytes {
+            state[RATE_RANGE.start + i] += element;
+            i += 1;
+            if i == RATE_WIDTH {
+                Rp64_256Permutation::permute(&mut state);
+                i = 0;
+            }
+        }
+
+        // A partial last block is permuted as is, without padding: the
+        // remaining rate elements keep their value from the previous permutation.
+        if i > 0 {
+            Rp64_256Permutation::permute(&mut state);
+        }
+
+        Rp64_256Digest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        // Merging is equivalent to hashing the 8 elements of both digests.
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        state[0] = Fp::new(RATE_WIDTH as u64);
+        state[RATE_RANGE.start..DIGEST_RANGE.end].copy_from_slice(values[0].as_elements());
+        state[DIGEST_RANGE.end..].copy_from_slice(values[1].as_elements());
+        Rp64_256Permutation::permute(&mut state);
+
+        Rp64_256Digest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_rp64_256_hash() {
+        // Hardcoded outputs of the hash of the sequences `[0, 1, ..., i - 1]`
+        // for i in 1..=19, generated from a Python port of this instance.
+        // These are regression vectors, not Winterfell's ones.
+        let output_data = [
+            [
+                Fp::new(15147819462508105081),
+                Fp::new(12857175893157809636),
+                Fp::new(10848908533051226231),
+                Fp::new(6207492596389100701),
+            ],
+            [
+                Fp::new(4542911548850561992),
+                Fp::new(3043128267004029916),
+                Fp::new(7064178585905787500),
+                Fp::new(10952992094318827521),
+            ],
+            [
+                Fp::new(12944734084564219083),
+                Fp::new(6215789654428897069),
+                Fp::new(5978516066722638300),
+                Fp::new(17364865857043128081),
+            ],
+            [
+                Fp::new(13220301898472718643),
+                Fp::new(13785910682847594498),
+                Fp::new(5973758190906697617),
+                Fp::new(11844977683597552362),
+            ],
+            [
+                Fp::new(6002140708292503904),
+                Fp::new(2808345377468905012),
+                Fp::new(9035126252993967654),
+                Fp::new(1073811067575282224),
+            ],
+            [
+                Fp::new(8909609669835467094),
+                Fp::new(14498785661497271127),
+                Fp::new(15993684677371476039),
+                Fp::new(8095084779937558083),
+            ],
+            [
+                Fp::new(11040213629145232105),
+                Fp::new(14025005303061756164),
+                Fp::new(88376147421197962),
+                Fp::new(12695839676241674891),
+            ],
+            [
+                Fp::new(1622985893048257556),
+                Fp::new(5541054663857452726),
+                Fp::new(7163390512418448553),
+                Fp::new(2902339770521041813),
+            ],
+            [
+                Fp::new(16584053397966747669),
+                Fp::new(16453485653845467818),
+                Fp::new(7950503478978134371),
+                Fp::new(11638626709306826000),
+            ],
+            [
+                Fp::new(2026468472764343147),
+                Fp::new(6230084597780871124),
+                Fp::new(16646797412900928513),
+                Fp::new(11358975827799934678),
+            ],
+            [
+                Fp::new(5251506499448900101),
+                Fp::new(485000309744569663),
+                Fp::new(13348006254779461480),
+                Fp::new(640857659701084450),
+            ],
+            [
+                Fp::new(11423471900696350581),
+                Fp::new(11270917997428096451),
+                Fp::new(8190044383565996025),
+                Fp::new(12713654381612866095),
+            ],
+            [
+                Fp::new(12394488901672508314),
+                Fp::new(2205935987200809177),
+                Fp::new(8348093807638988891),
+                Fp::new(11698163939734708953),
+            ],
+            [
+                Fp::new(12244410360295056553),
+                Fp::new(4024794179859924341),
+                Fp::new(2305730861855048888),
+                Fp::new(13595067139385147563),
+            ],
+            [
+                Fp::new(1633585008571922696),
+                Fp::new(16240328888117752908),
+                Fp::new(7310178074244884790),
+                Fp::new(2962076562181588326),
+            ],
+            [
+                Fp::new(16030941230557466744),
+                Fp::new(4074980312796899686),
+                Fp::new(3146317739733402183),
+                Fp::new(6538940441537872074),
+            ],
+            [
+                Fp::new(16498068447396236404),
+                Fp::new(8957332678103691076),
+                Fp::new(456916633360691678),
+                Fp::new(7698259270867843929),
+            ],
+            [
+                Fp::new(17577781091069861997),
+                Fp::new(18234378032189123646),
+                Fp::new(16622414042577052348),
+                Fp::new(9067613164655208990),
+            ],
+            [
+                Fp::new(12037987607633799263),
+                Fp::new(3264878013852516932),
+                Fp::new(5638906640247807332),
+                Fp::new(11285972574438379755),
+            ],
+        ];
+
+        for (i, expected) in output_data.iter().enumerate() {
+            let input: Vec<Fp> = (0..=i as u64).map(Fp::new).collect();
+            assert_eq!(*expected, Rp64_256Hash::hash(&input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_rp64_256_hash_padding() {
+        // Appending zeros to the input changes its digest
+        let input = [Fp::new(7), Fp::new(8), Fp::new(9)];
+        let digest = Rp64_256Hash::hash(&input);
+
+        for len in 1..=RATE_WIDTH {
+            let mut padded = [Fp::zero(); 3 + RATE_WIDTH];
+            padded[..3].copy_from_slice(&input);
+            assert_ne!(digest, Rp64_256Hash::hash(&padded[..3 + len]));
+        }
+    }
+
+    #[test]
+    fn test_rp64_256_hash_state() {
+        // Elements are added to the rate, which is not cleared between blocks.
+        let input: Vec<Fp> = (1..=RATE_WIDTH as u64 + 3).map(Fp::new).collect();
+
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        state[0] = Fp::new(input.len() as u64);
+        state[RATE_RANGE].copy_from_slice(&input[..RATE_WIDTH]);
+        Rp64_256Permutation::permute(&mut state);
+        for (s, &element) in state[RATE_RANGE].iter_mut().zip(&input[RATE_WIDTH..]) {
+            *s += element;
+        }
+        Rp64_256Permutation::permute(&mut state);
+
+        assert_eq!(
+            Rp64_256Hash::hash(&input).to_elements(),
+            state[DIGEST_RANGE]
+        );
+    }
+
+    #[test]
+    fn test_rp64_256_merge() {
+        let input_data = [
+            [Rp64_256Digest::default(), Rp64_256Digest::default()],
+            [
+                Rp64_256Digest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+                Rp64_256Digest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+            ],
+            [
+                Rp64_256Digest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+                Rp64_256Digest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+            ],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(11186000002732133418),
+                Fp::new(8645692160519118129),
+                Fp::new(9492292523963489601),
+                Fp::new(2109807785438599718),
+            ],
+            [
+                Fp::new(1622985893048257556),
+                Fp::new(5541054663857452726),
+                Fp::new(7163390512418448553),
+                Fp::new(2902339770521041813),
+            ],
+            [
+                Fp::new(8652328797039098274),
+                Fp::new(10228312152122891787),
+                Fp::new(17321811967906396285),
+                Fp::new(2732899850465023456),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, Rp64_256Hash::merge(input).to_elements());
+            assert_eq!(
+                Rp64_256Hash::merge(input),
+                Rp64_256Hash::hash(&Rp64_256Digest::digests_to_elements(input))
+            );
+        }
+    }
+}
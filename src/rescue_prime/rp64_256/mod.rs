@@ -0,0 +1,102 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Rescue-Prime instance following the sponge mode of Winterfell's
+//! `Rp64_256` hash function.
+//!
+//! The input length is set in the capacity and the input is added to
+//! the rate, without padding. The permutation is a 7-round Rescue-Prime
+//! permutation, reusing the MDS matrix and round constants of
+//! [`rescue_64_12_8`](super::rescue_64_12_8), which were generated with
+//! the reference algorithms of <https://eprint.iacr.org/2020/1143.pdf>.
+//! These are not Winterfell's `MDS`, `ARK1` and `ARK2` tables, hence
+//! digests differ from Winterfell's ones, and this instance has no
+//! identifier in the serialization format of Merkle tree data.
+
+use core::ops::Range;
+
+use super::rescue_64_12_8::{apply_round, RescueDigest};
+use cheetah::Fp;
+
+/// Hasher for Rp64_256
+mod hasher;
+/// Permutation for Rp64_256
+mod permutation;
+
+pub use hasher::Rp64_256Hash;
+pub use permutation::Rp64_256Permutation;
+
+// RP64_256 CONSTANTS
+// ================================================================================================
+
+/// Function state is set to 12 field elements or 96 bytes;
+/// 4 elements of the state are reserved for capacity
+pub const STATE_WIDTH: usize = 12;
+/// 8 elements of the state are reserved for rate
+pub const RATE_WIDTH: usize = 8;
+/// 4 elements of the state are reserved for capacity
+pub const CAPACITY_WIDTH: usize = STATE_WIDTH - RATE_WIDTH;
+
+/// As in Winterfell, the capacity occupies the first
+/// elements of the state and is followed by the rate.
+pub const CAPACITY_RANGE: Range<usize> = 0..CAPACITY_WIDTH;
+/// The rate occupies the last 8 elements of the state
+pub const RATE_RANGE: Range<usize> = CAPACITY_WIDTH..STATE_WIDTH;
+
+/// Four elements (32-bytes) are returned as digest.
+pub const DIGEST_SIZE: usize = 4;
+/// The digest is read from the first half of the rate
+pub const DIGEST_RANGE: Range<usize> = CAPACITY_WIDTH..CAPACITY_WIDTH + DIGEST_SIZE;
+
+/// The number of rounds is set to 7, as in Winterfell's `Rp64_256`
+pub const NUM_HASH_ROUNDS: usize = 7;
+
+/// A digest of the Rp64_256 hash, identical to the Rescue one.
+pub type Rp64_256Digest = RescueDigest;
+
+// RP64_256 PERMUTATION
+// ================================================================================================
+
+/// Applies the Rescue-Prime permutation, truncated to `NUM_HASH_ROUNDS` rounds.
+pub(crate) fn apply_permutation(state: &mut [Fp; STATE_WIDTH]) {
+    for i in 0..NUM_HASH_ROUNDS {
+        apply_round(state, i);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_permutation() {
+        // Hardcoded output of the permutation of `[0, 1, ..., 11]`, generated
+        // from a Python port of the `rescue_64_12_8` permutation with 7 rounds.
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        for (i, s) in state.iter_mut().enumerate() {
+            *s = Fp::new(i as u64);
+        }
+        apply_permutation(&mut state);
+
+        let expected = [
+            Fp::new(8830151182902853374),
+            Fp::new(13187004865337989000),
+            Fp::new(15101162352278848053),
+            Fp::new(14435613191222483279),
+            Fp::new(1292156311573281399),
+            Fp::new(8764222276562584657),
+            Fp::new(3431628250940046033),
+            Fp::new(3850389327580572944),
+            Fp::new(14991535190515157846),
+            Fp::new(5109801939400497066),
+            Fp::new(15005662634163591475),
+            Fp::new(2058051616562672581),
+        ];
+        assert_eq!(state, expected);
+    }
+}
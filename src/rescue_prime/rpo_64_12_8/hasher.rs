@@ -0,0 +1,206 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Hasher trait implementation for RPO
+
+use core::convert::TryInto;
+
+use super::digest::RpoDigest;
+use super::{
+    apply_permutation, CAPACITY_RANGE, DIGEST_RANGE, DIGEST_SIZE, RATE_RANGE, RATE_WIDTH,
+    STATE_WIDTH,
+};
+use crate::traits::Hasher;
+
+use cheetah::Fp;
+
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+/// An RPO Hash over Fp
+///
+/// As the capacity is initialized from the length of the input before
+/// any of its elements is absorbed, this hasher does not expose a
+/// streaming interface and only implements the `Hasher` trait.
+pub struct RpoHash;
+
+impl Hasher<Fp> for RpoHash {
+    type Digest = RpoDigest;
+
+    // This follows `Rpo256::hash_elements` from Miden: the first capacity
+    // element is set to one if and only if the last rate block is partial,
+    // in which case this block is padded with a single one followed by zeros.
+    fn hash(bytes: &[Fp]) -> Self::Digest {
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        if bytes.len() % RATE_WIDTH != 0 {
+            state[CAPACITY_RANGE.start] = Fp::one();
+        }
+
+        // Absorb elements in overwrite mode, permuting after each full rate block.
+        let mut i = 0;
+        for &element in bytes {
+            state[RATE_RANGE.start + i] = element;
+            i += 1;
+            if i == RATE_WIDTH {
+                apply_permutation(&mut state);
+                i = 0;
+            }
+        }
+
+        // Pad a partial last block with a single one followed by zeros.
+        if i > 0 {
+            state[RATE_RANGE.start + i] = Fp::one();
+            for s in state[RATE_RANGE.start + i + 1..].iter_mut() {
+                *s = Fp::zero();
+            }
+            apply_permutation(&mut state);
+        }
+
+        RpoDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+
+    fn merge(values: &[Self::Digest; 2]) -> Self::Digest {
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        state[RATE_RANGE.start..RATE_RANGE.start + DIGEST_SIZE]
+            .copy_from_slice(values[0].as_elements());
+        state[RATE_RANGE.start + DIGEST_SIZE..].copy_from_slice(values[1].as_elements());
+        apply_permutation(&mut state);
+
+        RpoDigest::new(state[DIGEST_RANGE].try_into().unwrap())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(not(feature = "std"))]
+    use alloc::vec::Vec;
+
+    #[test]
+    fn test_rpo_hash() {
+        // Outputs of the hash of the sequences `[0]` and `[0, 1]`, taken
+        // from the `EXPECTED` test vectors of Miden's `Rpo256`.
+        let output_data = [
+            [
+                Fp::new(1502364727743950833),
+                Fp::new(5880949717274681448),
+                Fp::new(162790463902224431),
+                Fp::new(6901340476773664264),
+            ],
+            [
+                Fp::new(7478710183745780580),
+                Fp::new(3308077307559720969),
+                Fp::new(3383561985796182409),
+                Fp::new(17205078494700259815),
+            ],
+        ];
+
+        for (i, expected) in output_data.iter().enumerate() {
+            let input: Vec<Fp> = (0..=i as u64).map(Fp::new).collect();
+            assert_eq!(*expected, RpoHash::hash(&input).to_elements());
+        }
+    }
+
+    #[test]
+    fn test_rpo_hash_padding() {
+        // Trailing zeros must not collide with the one-zeros padding
+        let input = [Fp::new(7), Fp::new(8), Fp::new(9)];
+        let digest = RpoHash::hash(&input);
+
+        for len in 1..=RATE_WIDTH {
+            let mut padded = [Fp::zero(); 3 + RATE_WIDTH];
+            padded[..3].copy_from_slice(&input);
+            assert_ne!(digest, RpoHash::hash(&padded[..3 + len]));
+        }
+
+        let mut padded = [Fp::zero(); RATE_WIDTH];
+        padded[..3].copy_from_slice(&input);
+        padded[3] = Fp::one();
+        assert_ne!(digest, RpoHash::hash(&padded));
+    }
+
+    #[test]
+    fn test_rpo_hash_state() {
+        let input: Vec<Fp> = (0..2 * RATE_WIDTH as u64 + 3).map(Fp::new).collect();
+
+        // A partial last block sets the first capacity element to one
+        // and is padded with a single one followed by zeros.
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        state[CAPACITY_RANGE.start] = Fp::one();
+        state[RATE_RANGE.start..RATE_RANGE.start + 3].copy_from_slice(&input[..3]);
+        state[RATE_RANGE.start + 3] = Fp::one();
+        apply_permutation(&mut state);
+        assert_eq!(
+            RpoHash::hash(&input[..3]).to_elements(),
+            state[DIGEST_RANGE]
+        );
+
+        // Full blocks leave the capacity untouched and are not padded.
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        for chunk in input[..2 * RATE_WIDTH].chunks_exact(RATE_WIDTH) {
+            state[RATE_RANGE].copy_from_slice(chunk);
+            apply_permutation(&mut state);
+        }
+        assert_eq!(
+            RpoHash::hash(&input[..2 * RATE_WIDTH]).to_elements(),
+            state[DIGEST_RANGE]
+        );
+
+        // Both rules apply to inputs with full and partial blocks.
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        state[CAPACITY_RANGE.start] = Fp::one();
+        for chunk in input[..2 * RATE_WIDTH].chunks_exact(RATE_WIDTH) {
+            state[RATE_RANGE].copy_from_slice(chunk);
+            apply_permutation(&mut state);
+        }
+        let mut last = [Fp::zero(); RATE_WIDTH];
+        last[..3].copy_from_slice(&input[2 * RATE_WIDTH..]);
+        last[3] = Fp::one();
+        state[RATE_RANGE].copy_from_slice(&last);
+        apply_permutation(&mut state);
+        assert_eq!(RpoHash::hash(&input).to_elements(), state[DIGEST_RANGE]);
+    }
+
+    #[test]
+    fn test_rpo_merge() {
+        let input_data = [
+            [RpoDigest::default(), RpoDigest::default()],
+            [
+                RpoDigest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+                RpoDigest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+            ],
+            [
+                RpoDigest::new([Fp::new(4), Fp::new(5), Fp::new(6), Fp::new(7)]),
+                RpoDigest::new([Fp::new(0), Fp::new(1), Fp::new(2), Fp::new(3)]),
+            ],
+        ];
+
+        let output_data = [
+            [
+                Fp::new(8635338869442206704),
+                Fp::new(11671305615285950885),
+                Fp::new(15253023094703789604),
+                Fp::new(7398108415970215319),
+            ],
+            [
+                Fp::new(2242391899857912644),
+                Fp::new(12689382052053305418),
+                Fp::new(235236990017815546),
+                Fp::new(5046143039268215739),
+            ],
+            [
+                Fp::new(4806449271019909763),
+                Fp::new(9578302631012273724),
+                Fp::new(5489831445702533572),
+                Fp::new(5952001851541246403),
+            ],
+        ];
+
+        for (input, expected) in input_data.iter().zip(output_data) {
+            assert_eq!(expected, RpoHash::merge(input).to_elements());
+        }
+    }
+}
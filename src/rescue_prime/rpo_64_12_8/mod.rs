@@ -0,0 +1,195 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use core::ops::Range;
+
+use cheetah::fp_arith_utils::reduce_u96;
+use cheetah::Fp;
+
+use crate::f64_utils::{apply_rescue_inv_sbox, apply_rescue_sbox};
+
+/// Digest for RPO
+mod digest;
+/// Hasher for RPO
+mod hasher;
+/// MDS matrix for RPO
+mod mds;
+/// Permutation for RPO
+mod permutation;
+/// Round constants for RPO
+mod round_constants;
+
+pub use digest::RpoDigest;
+pub use hasher::RpoHash;
+pub use permutation::RpoPermutation;
+
+// RPO CONSTANTS
+// ================================================================================================
+
+/// Function state is set to 12 field elements or 96 bytes;
+/// 4 elements of the state are reserved for capacity
+pub const STATE_WIDTH: usize = 12;
+/// 8 elements of the state are reserved for rate
+pub const RATE_WIDTH: usize = 8;
+/// 4 elements of the state are reserved for capacity
+pub const CAPACITY_WIDTH: usize = STATE_WIDTH - RATE_WIDTH;
+
+/// Unlike the other instances, the capacity occupies the
+/// first elements of the state and is followed by the rate.
+pub const CAPACITY_RANGE: Range<usize> = 0..CAPACITY_WIDTH;
+/// The rate occupies the last 8 elements of the state
+pub const RATE_RANGE: Range<usize> = CAPACITY_WIDTH..STATE_WIDTH;
+
+/// Four elements (32-bytes) are returned as digest.
+pub const DIGEST_SIZE: usize = 4;
+/// The digest is read from the first half of the rate
+pub const DIGEST_RANGE: Range<usize> = CAPACITY_WIDTH..CAPACITY_WIDTH + DIGEST_SIZE;
+
+/// The number of rounds is set to 7 to provide 128-bit security level;
+/// see <https://eprint.iacr.org/2022/1577.pdf>
+pub const NUM_HASH_ROUNDS: usize = 7;
+
+// HELPER FUNCTIONS
+// ================================================================================================
+
+#[inline(always)]
+/// Applies matrix-vector multiplication of the current
+/// hash state with the RPO MDS matrix.
+///
+/// Each element is split into 32-bit halves, which are multiplied
+/// separately in the frequency domain before being recombined.
+pub(crate) fn apply_mds(state: &mut [Fp; STATE_WIDTH]) {
+    let mut state_l = [0u64; STATE_WIDTH];
+    let mut state_h = [0u64; STATE_WIDTH];
+
+    for (i, s) in state.iter().enumerate() {
+        let s = s.output_unreduced_internal();
+        state_h[i] = s >> 32;
+        state_l[i] = s & 0xffff_ffff;
+    }
+
+    let state_h = mds::mds_multiply_freq(state_h);
+    let state_l = mds::mds_multiply_freq(state_l);
+
+    for (i, s) in state.iter_mut().enumerate() {
+        let v = state_l[i] as u128 + ((state_h[i] as u128) << 32);
+        *s = Fp::from_raw_unchecked(reduce_u96(v));
+    }
+}
+
+// RPO PERMUTATION
+// ================================================================================================
+
+/// Applies RPO permutation to the provided state.
+pub(crate) fn apply_permutation(state: &mut [Fp; STATE_WIDTH]) {
+    for i in 0..NUM_HASH_ROUNDS {
+        apply_round(state, i);
+    }
+}
+
+/// RPO round function;
+/// implementation based on algorithm 1 of <https://eprint.iacr.org/2022/1577.pdf>
+#[inline(always)]
+pub(crate) fn apply_round(state: &mut [Fp; STATE_WIDTH], step: usize) {
+    // apply first half of RPO round
+    apply_mds(state);
+    for (s, k) in state.iter_mut().zip(round_constants::ARK1[step]) {
+        *s += k;
+    }
+    apply_rescue_sbox(state);
+
+    // apply second half of RPO round
+    apply_mds(state);
+    for (s, k) in state.iter_mut().zip(round_constants::ARK2[step]) {
+        *s += k;
+    }
+    apply_rescue_inv_sbox(state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand_core::OsRng;
+
+    /// Applies matrix-vector multiplication of the current
+    /// hash state with the RPO MDS matrix, without the FFT.
+    fn apply_naive_mds(state: &mut [Fp; STATE_WIDTH]) {
+        let mut result = [Fp::zero(); STATE_WIDTH];
+        for (i, r) in result.iter_mut().enumerate() {
+            for (j, s) in state.iter().enumerate() {
+                *r += Fp::new(mds::MDS[i * STATE_WIDTH + j]) * s;
+            }
+        }
+
+        state.copy_from_slice(&result);
+    }
+
+    #[test]
+    fn test_mds() {
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        let mut rng = OsRng;
+
+        for _ in 0..100 {
+            for s in state.iter_mut() {
+                *s = Fp::random(&mut rng);
+            }
+
+            let mut expected = state;
+            apply_naive_mds(&mut expected);
+            apply_mds(&mut state);
+            assert_eq!(state, expected);
+        }
+
+        // Check the largest possible element, whose 32-bit halves
+        // maximize the intermediate values of the frequency-domain product.
+        let mut state = [-Fp::one(); STATE_WIDTH];
+        let mut expected = state;
+        apply_naive_mds(&mut expected);
+        apply_mds(&mut state);
+        assert_eq!(state, expected);
+    }
+
+    #[test]
+    fn test_round_constants() {
+        // First constants published with the RPO specification
+        assert_eq!(
+            round_constants::ARK1[0][..3],
+            [
+                Fp::new(5789762306288267392),
+                Fp::new(6522564764413701783),
+                Fp::new(17809893479458208203),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_permutation() {
+        let mut state = [Fp::zero(); STATE_WIDTH];
+        for (i, s) in state.iter_mut().enumerate() {
+            *s = Fp::new(i as u64);
+        }
+
+        let expected = [
+            Fp::new(15056646954853821376),
+            Fp::new(594518210294093573),
+            Fp::new(10395398226526937664),
+            Fp::new(3903707756219396109),
+            Fp::new(7670128982698747483),
+            Fp::new(4249514323476682720),
+            Fp::new(16506822133651532340),
+            Fp::new(10593868791806571942),
+            Fp::new(9413309068803954142),
+            Fp::new(15946782832277734471),
+            Fp::new(7904287043744270535),
+            Fp::new(16548919317472389167),
+        ];
+
+        apply_permutation(&mut state);
+        assert_eq!(state, expected);
+    }
+}
@@ -0,0 +1,216 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+use super::{NUM_HASH_ROUNDS, STATE_WIDTH};
+use cheetah::Fp;
+
+/// Additive Round Keys constants for the first half of each RPO round,
+/// generated with SHAKE256 from the seed `RPO(p,12,4,128)`
+/// following <https://eprint.iacr.org/2022/1577.pdf>
+pub(crate) const ARK1: [[Fp; STATE_WIDTH]; NUM_HASH_ROUNDS] = [
+    [
+        Fp::new(5789762306288267392),
+        Fp::new(6522564764413701783),
+        Fp::new(17809893479458208203),
+        Fp::new(107145243989736508),
+        Fp::new(6388978042437517382),
+        Fp::new(15844067734406016715),
+        Fp::new(9975000513555218239),
+        Fp::new(3344984123768313364),
+        Fp::new(9959189626657347191),
+        Fp::new(12960773468763563665),
+        Fp::new(9602914297752488475),
+        Fp::new(16657542370200465908),
+    ],
+    [
+        Fp::new(12987190162843096997),
+        Fp::new(653957632802705281),
+        Fp::new(4441654670647621225),
+        Fp::new(4038207883745915761),
+        Fp::new(5613464648874830118),
+        Fp::new(13222989726778338773),
+        Fp::new(3037761201230264149),
+        Fp::new(16683759727265180203),
+        Fp::new(8337364536491240715),
+        Fp::new(3227397518293416448),
+        Fp::new(8110510111539674682),
+        Fp::new(2872078294163232137),
+    ],
+    [
+        Fp::new(18072785500942327487),
+        Fp::new(6200974112677013481),
+        Fp::new(17682092219085884187),
+        Fp::new(10599526828986756440),
+        Fp::new(975003873302957338),
+        Fp::new(8264241093196931281),
+        Fp::new(10065763900435475170),
+        Fp::new(2181131744534710197),
+        Fp::new(6317303992309418647),
+        Fp::new(1401440938888741532),
+        Fp::new(8884468225181997494),
+        Fp::new(13066900325715521532),
+    ],
+    [
+        Fp::new(5674685213610121970),
+        Fp::new(5759084860419474071),
+        Fp::new(13943282657648897737),
+        Fp::new(1352748651966375394),
+        Fp::new(17110913224029905221),
+        Fp::new(1003883795902368422),
+        Fp::new(4141870621881018291),
+        Fp::new(8121410972417424656),
+        Fp::new(14300518605864919529),
+        Fp::new(13712227150607670181),
+        Fp::new(17021852944633065291),
+        Fp::new(6252096473787587650),
+    ],
+    [
+        Fp::new(4887609836208846458),
+        Fp::new(3027115137917284492),
+        Fp::new(9595098600469470675),
+        Fp::new(10528569829048484079),
+        Fp::new(7864689113198939815),
+        Fp::new(17533723827845969040),
+        Fp::new(5781638039037710951),
+        Fp::new(17024078752430719006),
+        Fp::new(109659393484013511),
+        Fp::new(7158933660534805869),
+        Fp::new(2955076958026921730),
+        Fp::new(7433723648458773977),
+    ],
+    [
+        Fp::new(16308865189192447297),
+        Fp::new(11977192855656444890),
+        Fp::new(12532242556065780287),
+        Fp::new(14594890931430968898),
+        Fp::new(7291784239689209784),
+        Fp::new(5514718540551361949),
+        Fp::new(10025733853830934803),
+        Fp::new(7293794580341021693),
+        Fp::new(6728552937464861756),
+        Fp::new(6332385040983343262),
+        Fp::new(13277683694236792804),
+        Fp::new(2600778905124452676),
+    ],
+    [
+        Fp::new(7123075680859040534),
+        Fp::new(1034205548717903090),
+        Fp::new(7717824418247931797),
+        Fp::new(3019070937878604058),
+        Fp::new(11403792746066867460),
+        Fp::new(10280580802233112374),
+        Fp::new(337153209462421218),
+        Fp::new(13333398568519923717),
+        Fp::new(3596153696935337464),
+        Fp::new(8104208463525993784),
+        Fp::new(14345062289456085693),
+        Fp::new(17036731477169661256),
+    ],
+];
+
+/// Additive Round Keys constants for the second half of each RPO round
+pub(crate) const ARK2: [[Fp; STATE_WIDTH]; NUM_HASH_ROUNDS] = [
+    [
+        Fp::new(6077062762357204287),
+        Fp::new(15277620170502011191),
+        Fp::new(5358738125714196705),
+        Fp::new(14233283787297595718),
+        Fp::new(13792579614346651365),
+        Fp::new(11614812331536767105),
+        Fp::new(14871063686742261166),
+        Fp::new(10148237148793043499),
+        Fp::new(4457428952329675767),
+        Fp::new(15590786458219172475),
+        Fp::new(10063319113072092615),
+        Fp::new(14200078843431360086),
+    ],
+    [
+        Fp::new(6202948458916099932),
+        Fp::new(17690140365333231091),
+        Fp::new(3595001575307484651),
+        Fp::new(373995945117666487),
+        Fp::new(1235734395091296013),
+        Fp::new(14172757457833931602),
+        Fp::new(707573103686350224),
+        Fp::new(15453217512188187135),
+        Fp::new(219777875004506018),
+        Fp::new(17876696346199469008),
+        Fp::new(17731621626449383378),
+        Fp::new(2897136237748376248),
+    ],
+    [
+        Fp::new(8023374565629191455),
+        Fp::new(15013690343205953430),
+        Fp::new(4485500052507912973),
+        Fp::new(12489737547229155153),
+        Fp::new(9500452585969030576),
+        Fp::new(2054001340201038870),
+        Fp::new(12420704059284934186),
+        Fp::new(355990932618543755),
+        Fp::new(9071225051243523860),
+        Fp::new(12766199826003448536),
+        Fp::new(9045979173463556963),
+        Fp::new(12934431667190679898),
+    ],
+    [
+        Fp::new(18389244934624494276),
+        Fp::new(16731736864863925227),
+        Fp::new(4440209734760478192),
+        Fp::new(17208448209698888938),
+        Fp::new(8739495587021565984),
+        Fp::new(17000774922218161967),
+        Fp::new(13533282547195532087),
+        Fp::new(525402848358706231),
+        Fp::new(16987541523062161972),
+        Fp::new(5466806524462797102),
+        Fp::new(14512769585918244983),
+        Fp::new(10973956031244051118),
+    ],
+    [
+        Fp::new(6982293561042362913),
+        Fp::new(14065426295947720331),
+        Fp::new(16451845770444974180),
+        Fp::new(7139138592091306727),
+        Fp::new(9012006439959783127),
+        Fp::new(14619614108529063361),
+        Fp::new(1394813199588124371),
+        Fp::new(4635111139507788575),
+        Fp::new(16217473952264203365),
+        Fp::new(10782018226466330683),
+        Fp::new(6844229992533662050),
+        Fp::new(7446486531695178711),
+    ],
+    [
+        Fp::new(3736792340494631448),
+        Fp::new(577852220195055341),
+        Fp::new(6689998335515779805),
+        Fp::new(13886063479078013492),
+        Fp::new(14358505101923202168),
+        Fp::new(7744142531772274164),
+        Fp::new(16135070735728404443),
+        Fp::new(12290902521256031137),
+        Fp::new(12059913662657709804),
+        Fp::new(16456018495793751911),
+        Fp::new(4571485474751953524),
+        Fp::new(17200392109565783176),
+    ],
+    [
+        Fp::new(17130398059294018733),
+        Fp::new(519782857322261988),
+        Fp::new(9625384390925085478),
+        Fp::new(1664893052631119222),
+        Fp::new(7629576092524553570),
+        Fp::new(3485239601103661425),
+        Fp::new(9755891797164033838),
+        Fp::new(15218148195153269027),
+        Fp::new(16460604813734957368),
+        Fp::new(9643968136937729763),
+        Fp::new(3611348709641382851),
+        Fp::new(18256379591337759196),
+    ],
+];
@@ -0,0 +1,299 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! Generic sponge construction over a permutation.
+//!
+//! A `Sponge<P, RATE, CAPACITY>` splits the state of the permutation `P`
+//! into `RATE` elements used for absorbing and squeezing, followed by
+//! `CAPACITY` elements which are never directly exposed.
+
+use crate::traits::Permutation;
+
+use cheetah::Fp;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// Padding rule applied when switching from absorbing to squeezing.
+pub enum Padding {
+    /// If the input length is not a multiple of the rate, adds 1 to the
+    /// next rate element and overwrites the remaining rate elements with
+    /// zeros before applying the permutation, as in Algorithm 2 of
+    /// <https://eprint.iacr.org/2020/1143.pdf>.
+    OneZeros,
+    /// If the input length is not a multiple of the rate, adds 1 to the
+    /// next rate element before applying the permutation. Otherwise, adds
+    /// 1 to the last capacity element, as done by the Anemoi sponge.
+    Sigma,
+    /// If the input length is not a multiple of the rate, adds 1 to the
+    /// next rate element before applying the permutation. This is meant
+    /// to be combined with a flag in the initial capacity, as done by
+    /// the Griffin sponge.
+    One,
+    /// Adds 1 to the next rate element and applies the permutation, even
+    /// if the input length is a multiple of the rate. Unlike the other
+    /// rules, this makes the padding injective on its own, as needed by
+    /// keyed sponges whose capacity cannot carry a flag.
+    AlwaysOne,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// A sponge construction over the permutation `P`, with `RATE`
+/// elements of rate and `CAPACITY` elements of capacity.
+pub struct Sponge<P: Permutation<Fp>, const RATE: usize, const CAPACITY: usize> {
+    state: P::State,
+    idx: usize,
+    padding: Padding,
+    squeezing: bool,
+}
+
+impl<P: Permutation<Fp>, const RATE: usize, const CAPACITY: usize> Sponge<P, RATE, CAPACITY> {
+    /// Returns a new sponge with an all-zero state and the given padding rule.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `RATE` is zero or if `RATE + CAPACITY` differs from
+    /// the width of the permutation.
+    pub fn new(padding: Padding) -> Self {
+        Self::with_initial_capacity(padding, [Fp::zero(); CAPACITY])
+    }
+
+    /// Returns a new sponge with an all-zero rate, the given initial capacity
+    /// elements and the given padding rule.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `RATE` is zero or if `RATE + CAPACITY` differs from
+    /// the width of the permutation.
+    pub fn with_initial_capacity(padding: Padding, capacity: [Fp; CAPACITY]) -> Self {
+        assert!(RATE > 0, "sponge rate must be non-zero");
+        assert_eq!(
+            RATE + CAPACITY,
+            P::WIDTH,
+            "sponge rate and capacity must match the permutation width"
+        );
+
+        let mut state = P::State::default();
+        state.as_mut()[RATE..].copy_from_slice(&capacity);
+
+        Self {
+            state,
+            idx: 0,
+            padding,
+            squeezing: false,
+        }
+    }
+
+    /// Returns the position of the next rate element to be absorbed or squeezed.
+    pub(crate) fn position(&self) -> usize {
+        self.idx
+    }
+
+    /// Absorbs a sequence of field elements into the sponge state,
+    /// applying the permutation each time `RATE` elements are absorbed.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the sponge has already started squeezing.
+    pub fn absorb(&mut self, input: &[Fp]) {
+        assert!(!self.squeezing, "cannot absorb after squeezing");
+
+        for &element in input.iter() {
+            self.state.as_mut()[self.idx] += element;
+            self.idx += 1;
+            if self.idx == RATE {
+                P::permute(&mut self.state);
+                self.idx = 0;
+            }
+        }
+    }
+
+    /// Fills `output` with elements squeezed from the sponge state.
+    ///
+    /// The padding rule is applied on the first call, and the permutation is
+    /// applied again each time `RATE` elements have been squeezed.
+    pub fn squeeze(&mut self, output: &mut [Fp]) {
+        if !self.squeezing {
+            self.apply_padding();
+            self.squeezing = true;
+            self.idx = 0;
+        }
+
+        for element in output.iter_mut() {
+            if self.idx == RATE {
+                P::permute(&mut self.state);
+                self.idx = 0;
+            }
+            *element = self.state.as_ref()[self.idx];
+            self.idx += 1;
+        }
+    }
+
+    /// Converts this sponge into a reader squeezing an arbitrary number of elements.
+    pub fn into_reader(self) -> XofReader<P, RATE, CAPACITY> {
+        XofReader { sponge: self }
+    }
+
+    fn apply_padding(&mut self) {
+        if self.idx == 0 && self.padding != Padding::AlwaysOne {
+            if self.padding == Padding::Sigma {
+                self.state.as_mut()[P::WIDTH - 1] += Fp::one();
+            }
+            return;
+        }
+
+        let state = self.state.as_mut();
+        state[self.idx] += Fp::one();
+        if self.padding == Padding::OneZeros {
+            for element in state[self.idx + 1..RATE].iter_mut() {
+                *element = Fp::zero();
+            }
+        }
+
+        P::permute(&mut self.state);
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// An extendable-output reader over a sponge, returning
+/// any number of field elements.
+pub struct XofReader<P: Permutation<Fp>, const RATE: usize, const CAPACITY: usize> {
+    sponge: Sponge<P, RATE, CAPACITY>,
+}
+
+impl<P: Permutation<Fp>, const RATE: usize, const CAPACITY: usize> XofReader<P, RATE, CAPACITY> {
+    /// Fills `output` with the next elements of the output stream.
+    ///
+    /// Reading the stream in several calls yields the same
+    /// elements as reading it at once.
+    pub fn read(&mut self, output: &mut [Fp]) {
+        self.sponge.squeeze(output);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{anemoi_64_8_4, griffin_64_8_4, poseidon_64_12_8, rescue_64_12_8};
+    use rand_core::OsRng;
+
+    fn check_chunked_absorb_and_squeeze<
+        P: Permutation<Fp>,
+        const RATE: usize,
+        const CAPACITY: usize,
+    >(
+        padding: Padding,
+    ) {
+        let mut rng = OsRng;
+        let mut input = [Fp::zero(); 25];
+        for element in input.iter_mut() {
+            *element = Fp::random(&mut rng);
+        }
+
+        for len in 0..=input.len() {
+            let mut sponge = Sponge::<P, RATE, CAPACITY>::new(padding);
+            sponge.absorb(&input[..len]);
+            let mut expected = [Fp::zero(); 20];
+            sponge.squeeze(&mut expected);
+
+            for chunk_size in 1..=RATE + 1 {
+                let mut sponge = Sponge::<P, RATE, CAPACITY>::new(padding);
+                for chunk in input[..len].chunks(chunk_size) {
+                    sponge.absorb(chunk);
+                }
+
+                let mut output = [Fp::zero(); 20];
+                for chunk in output.chunks_mut(chunk_size) {
+                    sponge.squeeze(chunk);
+                }
+
+                assert_eq!(expected, output);
+            }
+        }
+    }
+
+    #[test]
+    fn test_sponge_chunks() {
+        check_chunked_absorb_and_squeeze::<anemoi_64_8_4::AnemoiPermutation, 4, 4>(Padding::Sigma);
+        check_chunked_absorb_and_squeeze::<griffin_64_8_4::GriffinPermutation, 4, 4>(Padding::One);
+        check_chunked_absorb_and_squeeze::<griffin_64_8_4::GriffinPermutation, 4, 4>(
+            Padding::AlwaysOne,
+        );
+        check_chunked_absorb_and_squeeze::<rescue_64_12_8::RescuePermutation, 8, 4>(
+            Padding::OneZeros,
+        );
+        // A rate / capacity split not used by any of the crate hashers.
+        check_chunked_absorb_and_squeeze::<poseidon_64_12_8::PoseidonPermutation, 6, 6>(
+            Padding::OneZeros,
+        );
+    }
+
+    #[test]
+    fn test_sponge_padding() {
+        let input = [Fp::new(42), Fp::zero()];
+
+        for padding in [
+            Padding::OneZeros,
+            Padding::Sigma,
+            Padding::One,
+            Padding::AlwaysOne,
+        ] {
+            let mut sponge = Sponge::<anemoi_64_8_4::AnemoiPermutation, 4, 4>::new(padding);
+            sponge.absorb(&input[..1]);
+            let mut output1 = [Fp::zero(); 4];
+            sponge.squeeze(&mut output1);
+
+            let mut sponge = Sponge::<anemoi_64_8_4::AnemoiPermutation, 4, 4>::new(padding);
+            sponge.absorb(&input);
+            let mut output2 = [Fp::zero(); 4];
+            sponge.squeeze(&mut output2);
+
+            assert_ne!(output1, output2);
+        }
+    }
+
+    #[test]
+    fn test_sponge_always_one_padding() {
+        // With the other rules, a partial block is padded into the same
+        // full block as the one obtained by appending a one to the input.
+        let input = [Fp::new(42), Fp::new(43), Fp::new(44), Fp::one()];
+
+        let mut sponge = Sponge::<anemoi_64_8_4::AnemoiPermutation, 4, 4>::new(Padding::OneZeros);
+        sponge.absorb(&input[..3]);
+        let mut output1 = [Fp::zero(); 4];
+        sponge.squeeze(&mut output1);
+
+        let mut sponge = Sponge::<anemoi_64_8_4::AnemoiPermutation, 4, 4>::new(Padding::OneZeros);
+        sponge.absorb(&input);
+        let mut output2 = [Fp::zero(); 4];
+        sponge.squeeze(&mut output2);
+        assert_eq!(output1, output2);
+
+        let mut sponge = Sponge::<anemoi_64_8_4::AnemoiPermutation, 4, 4>::new(Padding::AlwaysOne);
+        sponge.absorb(&input[..3]);
+        sponge.squeeze(&mut output1);
+
+        let mut sponge = Sponge::<anemoi_64_8_4::AnemoiPermutation, 4, 4>::new(Padding::AlwaysOne);
+        sponge.absorb(&input);
+        sponge.squeeze(&mut output2);
+        assert_ne!(output1, output2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sponge_absorb_after_squeeze() {
+        let mut sponge = Sponge::<anemoi_64_8_4::AnemoiPermutation, 4, 4>::new(Padding::Sigma);
+        sponge.absorb(&[Fp::one()]);
+        sponge.squeeze(&mut [Fp::zero(); 4]);
+        sponge.absorb(&[Fp::one()]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_sponge_invalid_width() {
+        Sponge::<anemoi_64_8_4::AnemoiPermutation, 4, 8>::new(Padding::Sigma);
+    }
+}
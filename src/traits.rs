@@ -6,15 +6,40 @@
 // option. This file may not be copied, modified, or distributed
 // except according to those terms.
 
-use core::fmt::Debug;
+use core::fmt::{self, Debug, Display};
 use group::ff::Field;
 
 /// Defines output type of a cryptographic hash function.
 pub trait Digest: Debug + Default + Copy + Clone + Eq + PartialEq + Send + Sync {
     /// Returns this digest serialized into an array of bytes.
     fn to_bytes(&self) -> [u8; 32];
+
+    /// Returns a digest deserialized from an array of bytes.
+    ///
+    /// Fails if any field element of the digest is not canonically encoded.
+    fn from_bytes(bytes: &[u8; 32]) -> Result<Self, DigestError>;
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// Errors that can occur when deserializing a digest.
+pub enum DigestError {
+    /// The field element at the given index is not canonically encoded.
+    NonCanonicalElement(usize),
+}
+
+impl Display for DigestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DigestError::NonCanonicalElement(index) => {
+                write!(f, "digest element {} is not canonically encoded", index)
+            }
+        }
+    }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for DigestError {}
+
 /// Trait for implementing a cryptographic hash function.
 pub trait Hasher<F: Field> {
     /// Specifies a digest type returned by this hasher.
@@ -27,3 +52,15 @@ pub trait Hasher<F: Field> {
     /// This method is intended for use in construction of Merkle trees.
     fn merge(values: &[Self::Digest; 2]) -> Self::Digest;
 }
+
+/// Trait for exposing the underlying permutation of a hash function.
+pub trait Permutation<F: Field> {
+    /// Number of field elements the permutation operates on.
+    const WIDTH: usize;
+
+    /// Specifies the state type of this permutation, an array of `WIDTH` field elements.
+    type State: Debug + Default + Copy + Clone + Eq + PartialEq + AsRef<[F]> + AsMut<[F]>;
+
+    /// Applies the permutation to the provided state.
+    fn permute(state: &mut Self::State);
+}
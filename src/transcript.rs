@@ -0,0 +1,304 @@
+// Copyright (c) 2021-2023 Toposware, Inc.
+//
+// Licensed under the Apache License, Version 2.0 <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT license
+// <LICENSE-MIT or http://opensource.org/licenses/MIT>, at your
+// option. This file may not be copied, modified, or distributed
+// except according to those terms.
+
+//! A `Transcript<H>` keeps a running digest of the hasher `H`, which is
+//! updated by every operation performed on the transcript.
+//!
+//! Each operation replaces the running digest `s` with
+//! `H::hash(s || kind || n || label || m || data)`, where `kind` is a
+//! field element identifying the operation (initialization, message,
+//! digest or challenge), `label` is the byte label of the operation packed
+//! with [`bytes_to_elements`], `n` is the number of elements of this packed
+//! label, and `data` contains the `m` appended elements, if any. As `s` and
+//! `kind` have a fixed length and both the label and the data are prefixed
+//! by their lengths, two distinct sequences of operations never hash the
+//! same inputs, and no input is a prefix of another one. The latter rules
+//! out collisions between an input and its extensions, such as those of
+//! sponges which only pad partial blocks.
+//!
+//! Challenges are the first element of the running digest obtained after
+//! the corresponding operation. Integers are then sampled by rejection,
+//! so that they are uniformly distributed over the requested range.
+//!
+//! [`bytes_to_elements`]: crate::f64_utils::bytes_to_elements
+
+use crate::f64_utils::{bytes_to_elements, digest_to_elements, MODULUS};
+use crate::traits::Hasher;
+
+use cheetah::Fp;
+
+#[cfg(not(feature = "std"))]
+use alloc::vec::Vec;
+
+/// Operation tag used when creating a transcript.
+const INIT: u64 = 0;
+/// Operation tag used when appending field elements.
+const MESSAGE: u64 = 1;
+/// Operation tag used when appending a digest.
+const DIGEST: u64 = 2;
+/// Operation tag used when drawing a challenge.
+const CHALLENGE: u64 = 3;
+
+/// A Fiat-Shamir transcript over the hasher `H`.
+pub struct Transcript<H: Hasher<Fp>> {
+    state: H::Digest,
+}
+
+impl<H: Hasher<Fp>> core::fmt::Debug for Transcript<H> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Transcript")
+            .field("state", &self.state)
+            .finish()
+    }
+}
+
+impl<H: Hasher<Fp>> Clone for Transcript<H> {
+    fn clone(&self) -> Self {
+        Self { state: self.state }
+    }
+}
+
+impl<H: Hasher<Fp>> Transcript<H> {
+    /// Returns a new transcript, bound to the provided protocol label.
+    pub fn new(label: &[u8]) -> Self {
+        let mut transcript = Self {
+            state: H::Digest::default(),
+        };
+        transcript.update(INIT, label, &[]);
+
+        transcript
+    }
+
+    /// Appends a labeled sequence of field elements to this transcript.
+    pub fn append_message(&mut self, label: &[u8], message: &[Fp]) {
+        self.update(MESSAGE, label, message);
+    }
+
+    /// Appends a labeled digest to this transcript.
+    pub fn append_digest(&mut self, label: &[u8], digest: &H::Digest) {
+        self.update(DIGEST, label, &digest_to_elements(digest));
+    }
+
+    /// Draws a labeled challenge field element from this transcript.
+    pub fn challenge(&mut self, label: &[u8]) -> Fp {
+        self.update(CHALLENGE, label, &[]);
+
+        digest_to_elements(&self.state)[0]
+    }
+
+    /// Draws a labeled integer uniformly distributed in `[0, n)`.
+    ///
+    /// Challenge field elements falling in the last incomplete
+    /// range of `n` values are rejected and drawn again.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `n` is zero or greater than the field modulus.
+    pub fn challenge_integer(&mut self, label: &[u8], n: u64) -> u64 {
+        assert!(n > 0, "cannot sample from an empty range");
+        assert!(n <= MODULUS, "range exceeds the field modulus");
+
+        let limit = MODULUS - MODULUS % n;
+        loop {
+            let value = u64::from_le_bytes(self.challenge(label).to_bytes());
+            if value < limit {
+                return value % n;
+            }
+        }
+    }
+
+    /// Draws `num_queries` distinct labeled query positions in
+    /// `[0, domain_size)`, in the order they were sampled.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `num_queries` is greater than `domain_size`, or if
+    /// `domain_size` is zero or greater than the field modulus.
+    pub fn challenge_query_positions(
+        &mut self,
+        label: &[u8],
+        num_queries: usize,
+        domain_size: usize,
+    ) -> Vec<usize> {
+        assert!(domain_size > 0, "cannot sample from an empty range");
+        assert!(
+            domain_size as u64 <= MODULUS,
+            "range exceeds the field modulus"
+        );
+        assert!(
+            num_queries <= domain_size,
+            "cannot draw more distinct positions than the domain size"
+        );
+
+        let mut positions = Vec::with_capacity(num_queries);
+        while positions.len() < num_queries {
+            let position = self.challenge_integer(label, domain_size as u64) as usize;
+            if !positions.contains(&position) {
+                positions.push(position);
+            }
+        }
+
+        positions
+    }
+
+    fn update(&mut self, kind: u64, label: &[u8], data: &[Fp]) {
+        let label = bytes_to_elements(label);
+
+        let mut input = Vec::with_capacity(7 + label.len() + data.len());
+        input.extend_from_slice(&digest_to_elements(&self.state));
+        input.push(Fp::new(kind));
+        input.push(Fp::new(label.len() as u64));
+        input.extend_from_slice(&label);
+        input.push(Fp::new(data.len() as u64));
+        input.extend_from_slice(data);
+
+        self.state = H::hash(&input);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{anemoi_64_12_8, anemoi_64_16_8, anemoi_64_8_4};
+    use crate::{griffin_64_12_8, griffin_64_8_4};
+    use crate::{poseidon_64_12_8, poseidon_64_8_4};
+    use crate::{rescue_64_12_8, rescue_64_8_4, rp64_256, rpo_64_12_8};
+    use rand_core::OsRng;
+
+    // Runs the same protocol on the prover and verifier sides,
+    // checking that both derive the same challenges.
+    fn check_prover_verifier<H: Hasher<Fp>>() {
+        let mut rng = OsRng;
+        let message = [Fp::random(&mut rng), Fp::random(&mut rng)];
+        let commitment = H::hash(&message);
+
+        let mut prover = Transcript::<H>::new(b"protocol");
+        prover.append_message(b"public inputs", &message);
+        prover.append_digest(b"trace commitment", &commitment);
+        let alpha = prover.challenge(b"alpha");
+        let beta = prover.challenge_integer(b"beta", 1000);
+        let positions = prover.challenge_query_positions(b"queries", 30, 64);
+
+        let mut verifier = Transcript::<H>::new(b"protocol");
+        verifier.append_message(b"public inputs", &message);
+        verifier.append_digest(b"trace commitment", &commitment);
+        assert_eq!(verifier.challenge(b"alpha"), alpha);
+        assert_eq!(verifier.challenge_integer(b"beta", 1000), beta);
+        assert_eq!(
+            verifier.challenge_query_positions(b"queries", 30, 64),
+            positions
+        );
+
+        assert!(beta < 1000);
+        assert_eq!(positions.len(), 30);
+        for (i, p) in positions.iter().enumerate() {
+            assert!(*p < 64);
+            assert!(!positions[..i].contains(p));
+        }
+
+        // A full domain is drawn as a permutation of its positions.
+        let mut all = prover.challenge_query_positions(b"queries", 16, 16);
+        all.sort_unstable();
+        assert_eq!(all, (0..16).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_transcript_prover_verifier() {
+        check_prover_verifier::<anemoi_64_8_4::AnemoiHash>();
+        check_prover_verifier::<anemoi_64_12_8::AnemoiHash>();
+        check_prover_verifier::<anemoi_64_16_8::AnemoiHash>();
+        check_prover_verifier::<griffin_64_8_4::GriffinHash>();
+        check_prover_verifier::<griffin_64_12_8::GriffinHash>();
+        check_prover_verifier::<rescue_64_8_4::RescueHash>();
+        check_prover_verifier::<rescue_64_12_8::RescueHash>();
+        check_prover_verifier::<rpo_64_12_8::RpoHash>();
+        check_prover_verifier::<rp64_256::Rp64_256Hash>();
+        check_prover_verifier::<poseidon_64_8_4::PoseidonHash>();
+        check_prover_verifier::<poseidon_64_12_8::PoseidonHash>();
+    }
+
+    #[test]
+    fn test_transcript_domain_separation() {
+        type T = Transcript<anemoi_64_8_4::AnemoiHash>;
+
+        let base = T::new(b"protocol").challenge(b"alpha");
+
+        // Protocol label
+        assert_ne!(T::new(b"other protocol").challenge(b"alpha"), base);
+        // Challenge label
+        assert_ne!(T::new(b"protocol").challenge(b"beta"), base);
+
+        // Label and message boundaries
+        let mut t1 = T::new(b"protocol");
+        t1.append_message(b"ab", &[]);
+        let mut t2 = T::new(b"protocol");
+        t2.append_message(b"a", &bytes_to_elements(b"b"));
+        assert_ne!(t1.challenge(b"alpha"), t2.challenge(b"alpha"));
+
+        // Messages and digests
+        let digest = anemoi_64_8_4::AnemoiDigest::new([Fp::one(); 4]);
+        let mut t1 = T::new(b"protocol");
+        t1.append_message(b"data", &[Fp::one(); 4]);
+        let mut t2 = T::new(b"protocol");
+        t2.append_digest(b"data", &digest);
+        assert_ne!(t1.challenge(b"alpha"), t2.challenge(b"alpha"));
+
+        // Messages and their extensions, wherever they end within the rate
+        let data = [Fp::new(5); 9];
+        for len in 0..data.len() {
+            for last in [Fp::one(), Fp::zero()] {
+                let mut t1 = T::new(b"protocol");
+                t1.append_message(b"data", &data[..len]);
+                let mut t2 = T::new(b"protocol");
+                let mut extended = data[..=len].to_vec();
+                extended[len] = last;
+                t2.append_message(b"data", &extended);
+                assert_ne!(t1.challenge(b"alpha"), t2.challenge(b"alpha"));
+            }
+        }
+
+        // Successive challenges with the same label
+        let mut t = T::new(b"protocol");
+        assert_eq!(t.challenge(b"alpha"), base);
+        assert_ne!(t.challenge(b"alpha"), base);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_transcript_too_many_queries() {
+        let mut transcript = Transcript::<anemoi_64_8_4::AnemoiHash>::new(b"protocol");
+        transcript.challenge_query_positions(b"queries", 9, 8);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_transcript_empty_query_domain() {
+        let mut transcript = Transcript::<anemoi_64_8_4::AnemoiHash>::new(b"protocol");
+        transcript.challenge_query_positions(b"queries", 0, 0);
+    }
+
+    #[test]
+    fn test_transcript_vectors() {
+        let mut transcript = Transcript::<anemoi_64_8_4::AnemoiHash>::new(b"protocol");
+        transcript.append_message(b"public inputs", &[Fp::new(0), Fp::new(1), Fp::new(2)]);
+        transcript.append_digest(
+            b"trace commitment",
+            &anemoi_64_8_4::AnemoiHash::hash(&[Fp::new(3)]),
+        );
+
+        assert_eq!(
+            transcript.challenge(b"alpha"),
+            Fp::new(15724933023812397809)
+        );
+        assert_eq!(transcript.challenge_integer(b"beta", 1000), 315);
+        assert_eq!(
+            transcript.challenge_query_positions(b"queries", 8, 1 << 10),
+            [882, 765, 88, 395, 697, 565, 673, 164]
+        );
+    }
+}